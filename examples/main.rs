@@ -16,11 +16,11 @@ fn main() {
     // Arithmetic works pretty much seamlessly
     let a = 12i32.set_bits::<5>().unwrap();
     let b = (-1i32).set_bits::<1>().unwrap();
-    let c = a + b; // type of C is I32<6, 0>
+    let _c = a + b; // type of C is I32<6, 0>
 
     // Addition is associative in value, but not in type
-    let d: fp::I32<6, 0> = a + (b + b);
-    let e: fp::I32<7, 0> = (a + b) + b;
+    let _d: fp::I32<6, 0> = a + (b + b);
+    let _e: fp::I32<7, 0> = (a + b) + b;
 
     let x = fp::I32::<21, 20>::from_f32(0.497).unwrap();
     let y = x.div_const::<12>();
@@ -0,0 +1,172 @@
+use crate::{
+    bfp::round_shr,
+    const_math,
+    soft_clip::{tanh_q30, TANH_LIMIT},
+    Num,
+};
+
+/// Number of intervals per unit input in the `exp` table.
+const EXP_STEPS: usize = 64;
+/// Largest input magnitude in the `exp` table; beyond this, `exp(-x)` is
+/// below 1.2e-7 and is treated as zero.
+const EXP_LIMIT: usize = 16;
+
+/// `exp(-i / EXP_STEPS)` in unsigned Q1.31, for `0 <= i <= EXP_LIMIT * EXP_STEPS`.
+static EXP_NEG: [u32; EXP_LIMIT * EXP_STEPS + 1] = {
+    let mut table = [0; EXP_LIMIT * EXP_STEPS + 1];
+    let mut i = 0;
+    while i < table.len() {
+        let x = i as f64 / EXP_STEPS as f64;
+        table[i] = (const_math::exp(-x) * (1u64 << 31) as f64 + 0.5) as u32;
+        i += 1;
+    }
+    table
+};
+
+/// `exp(-x)` in Q30, for `x` in Q30 and at least zero, by linear interpolation
+/// in the table.
+fn exp_neg_q30(x: u64) -> i128 {
+    if x >= (EXP_LIMIT as u64) << 30 {
+        return 0;
+    }
+    let (index, frac) = ((x >> 24) as usize, (x & ((1 << 24) - 1)) as i128);
+    let (y0, y1) = (EXP_NEG[index] as i128, EXP_NEG[index + 1] as i128);
+    let y = y0 + (((y1 - y0) * frac + (1 << 23)) >> 24);
+    (y + 1) >> 1
+}
+
+/// Convert `x` to Q30, clamped to `+/- limit`.
+fn to_q30<In: Num>(x: In, limit: usize) -> i128
+where
+    In::Raw: Into<i128>,
+{
+    let (raw, limit): (i128, i128) = (x.raw().into(), limit as i128);
+    let x = if In::SHIFT >= 30 {
+        raw >> (In::SHIFT - 30).min(127)
+    } else if In::SHIFT >= 0 {
+        raw.clamp(-limit << In::SHIFT, limit << In::SHIFT) << (30 - In::SHIFT)
+    } else if In::SHIFT > -64 {
+        // clamp to the smallest raw value at or beyond the limit
+        let m = In::SHIFT.unsigned_abs();
+        let bound = (limit + (1 << m) - 1) >> m;
+        raw.clamp(-bound, bound) << (30 - In::SHIFT)
+    } else {
+        // every nonzero value is beyond the limit
+        raw.signum() * (limit << 30)
+    };
+    x.clamp(-limit << 30, limit << 30)
+}
+
+/// Compile-time checks on the input and output formats.
+struct Formats<In, Out>(core::marker::PhantomData<(In, Out)>);
+
+impl<In: Num, Out: Num> Formats<In, Out> {
+    const OUT: () = assert!(Out::SHIFT <= 64, "output shift must be at most 64");
+    const SOFTMAX: () = {
+        assert!(In::SHIFT <= 64, "input shift must be at most 64");
+        assert!(
+            Out::SHIFT >= 0 && Out::SHIFT <= 64,
+            "output shift must be between 0 and 64"
+        );
+    };
+}
+
+/// Round `y` (in Q30, at most 2 in magnitude) to nearest `Out`, with ties away
+/// from zero, saturating to the range of `Out`.
+fn from_q30<Out: Num>(y: i128) -> Out
+where
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let y = if Out::SHIFT >= 30 {
+        y << (Out::SHIFT - 30)
+    } else {
+        round_shr(y, (30 - Out::SHIFT) as u32)
+    };
+    let (min, max) = (Out::MIN.raw().into(), Out::MAX.raw().into());
+    unsafe { Out::new_unchecked(y.clamp(min, max).try_into().ok().unwrap()) }
+}
+
+/// Hyperbolic tangent, from any input format to any output format.
+///
+/// Uses linear interpolation in a 512-interval table over `0 <= |x| <= 8`, which
+/// is accurate to within 3e-5; the result is then rounded to nearest in `Out` (so
+/// the maximum error is 3e-5 plus half an LSB of `Out`) and saturated to the range
+/// of `Out` (e.g. `tanh(x)` near 1 saturates to `Out::MAX` when `Out` cannot
+/// represent 1).  The result is odd in `x`.  `Out::SHIFT` must be at most 64.
+pub fn tanh<In: Num, Out: Num>(x: In) -> Out
+where
+    In::Raw: Into<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let () = Formats::<In, Out>::OUT;
+    let x = to_q30(x, TANH_LIMIT);
+    let magnitude = tanh_q30(x.unsigned_abs() as u64);
+    from_q30(if x < 0 { -magnitude } else { magnitude })
+}
+
+/// Logistic sigmoid, `1 / (1 + exp(-x))`, from any input format to any output
+/// format.
+///
+/// Evaluated as `(1 + tanh(x / 2)) / 2`, so the error before rounding is half
+/// that of [`tanh`], i.e. within 1.5e-5, and the maximum error is 1.5e-5 plus
+/// half an LSB of `Out`.  The result is saturated to the range of `Out`, and is
+/// symmetric (`sigmoid(-x) == 1 - sigmoid(x)`) before rounding.  `Out::SHIFT` must
+/// be at most 64.
+pub fn sigmoid<In: Num, Out: Num>(x: In) -> Out
+where
+    In::Raw: Into<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let () = Formats::<In, Out>::OUT;
+    let x = to_q30(x, 2 * TANH_LIMIT);
+    let magnitude = tanh_q30((x.unsigned_abs() >> 1) as u64);
+    let tanh = if x < 0 { -magnitude } else { magnitude };
+    // (1 + tanh) / 2 in Q31 is 1 + tanh in Q30
+    from_q30::<Out>(round_shr((1 << 30) + tanh, 1))
+}
+
+/// Softmax, `exp(x[i]) / sum(exp(x))`, from any input format to any output format.
+///
+/// The maximum input is subtracted from every input first, which is exact (in
+/// `i128`), so that every exponent is at most zero and no sum of exponentials can
+/// overflow, however large the inputs are.  The exponentials are taken from a
+/// 1024-interval table of `exp(-x)` over `0 <= x <= 16`, accurate to within 3.1e-5,
+/// and are treated as zero beyond; each output is then the quotient of its
+/// exponential and the sum, rounded to nearest in `Out`, so every output is within
+/// 2e-4 plus half an LSB of `Out` of the exact value.
+///
+/// `In::SHIFT` must be at most 64, and `Out::SHIFT` between 0 and 64.
+pub fn softmax<In: Num, Out: Num, const N: usize>(xs: &[In; N]) -> [Out; N]
+where
+    In::Raw: Into<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let () = Formats::<In, Out>::SOFTMAX;
+    let max: i128 = xs.iter().map(|x| x.raw().into()).max().unwrap_or(0);
+    let exps = xs.map(|x| {
+        // max - x, in Q30 and at most EXP_LIMIT (clamped before shifting, so
+        // that it cannot overflow)
+        let d = max - x.raw().into();
+        let limit = (EXP_LIMIT as i128) << In::SHIFT.max(0);
+        let d = if In::SHIFT >= 30 {
+            d.min(limit) >> (In::SHIFT - 30)
+        } else if In::SHIFT >= 0 {
+            d.min(limit) << (30 - In::SHIFT)
+        } else if In::SHIFT > -8 {
+            (d.min(EXP_LIMIT as i128) << (30 - In::SHIFT)).min((EXP_LIMIT as i128) << 30)
+        } else if d > 0 {
+            // every nonzero difference is at least 2^8, beyond the table
+            (EXP_LIMIT as i128) << 30
+        } else {
+            0
+        };
+        exp_neg_q30(d as u64)
+    });
+    // The maximum input contributes exactly 1, so the sum is at least 2^30.
+    let sum: i128 = exps.iter().sum();
+    let (min, max) = (Out::MIN.raw().into(), Out::MAX.raw().into());
+    exps.map(|e| {
+        let y = ((e << (Out::SHIFT + 1)) + sum) / (2 * sum);
+        unsafe { Out::new_unchecked(y.clamp(min, max).try_into().ok().unwrap()) }
+    })
+}
@@ -1,6 +1,6 @@
 use core::ops::{Add, Neg, Sub};
 
-use crate::Num;
+use crate::{finer_shift, Fits, Num};
 
 /// Needed for const-generic support, because the standard
 /// ways to compute maximum of two values are not const.
@@ -12,6 +12,13 @@ pub const fn max(a: u32, b: u32) -> u32 {
     }
 }
 
+/// Returns the bit width of the return type from `add_aligned`: the wider of the
+/// two inputs once they are aligned to the finer shift, plus 1 for the carry.
+pub const fn add_aligned_bits(b0: u32, s0: i32, b1: u32, s1: i32) -> u32 {
+    let shift = finer_shift(s0, s1);
+    max(b0 + (shift - s0) as u32, b1 + (shift - s1) as u32) + 1
+}
+
 macro_rules! fp_impl {
     ($Name:ident, $Iname:ident) => {
         use crate::$Name;
@@ -24,6 +31,7 @@ macro_rules! fp_impl {
         {
             type Output = $Name<{ max(B0, B1) + 1 }, S>;
             fn add(self: $Name<B0, S>, other: $Name<B1, S>) -> Self::Output {
+                let () = Fits::<Self::Output, { max(B0, B1) + 1 }>::SUM;
                 unsafe {
                     Self::Output::new_unchecked(
                         // use wrapping_sub to ensure we don't do overflow checks
@@ -44,6 +52,7 @@ macro_rules! fp_impl {
             // Subtraction output is always signed, even for unsigned inputs.
             type Output = $Iname<{ max(B0, B1) + 1 }, S>;
             fn sub(self: $Name<B0, S>, other: $Name<B1, S>) -> Self::Output {
+                let () = Fits::<Self::Output, { max(B0, B1) + 1 }>::SUM;
                 unsafe {
                     Self::Output::new_unchecked(
                         // use wrapping_sub to ensure we don't do overflow checks
@@ -53,6 +62,23 @@ macro_rules! fp_impl {
                 }
             }
         }
+        impl<const B0: u32, const S0: i32> $Name<B0, S0> {
+            /// Add a fixed-point integer with the same raw type but any shift.  The
+            /// operand with the coarser shift is first shifted left to the finer shift
+            /// (as `align_shifts` does), so the sum is exact.  The result has the finer
+            /// shift, and 1 more bit than the wider of the two aligned inputs.
+            /// Compilation will fail if that is too many bits for the raw type.
+            pub fn add_aligned<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> $Name<{ add_aligned_bits(B0, S0, B1, S1) }, { finer_shift(S0, S1) }> {
+                let () = Fits::<$Name<0, 0>, { add_aligned_bits(B0, S0, B1, S1) }>::SUM;
+                let shift = finer_shift(S0, S1);
+                let a = self.raw() << (shift - S0) as u32;
+                let b = other.raw() << (shift - S1) as u32;
+                unsafe { $Name::new_unchecked(a.wrapping_add(b)) }
+            }
+        }
         impl<const B: u32, const S: i32> Neg for $Name<B, S>
         where
             [(); (B + 1) as usize]:,
@@ -62,6 +88,7 @@ macro_rules! fp_impl {
             // signed values can overflow from MIN to -MIN = MAX + 1.
             type Output = $Iname<{ B + 1 }, S>;
             fn neg(self: $Name<B, S>) -> Self::Output {
+                let () = Fits::<Self::Output, { B + 1 }>::SUM;
                 unsafe { Self::Output::new_unchecked(-(self.raw() as <Self::Output as Num>::Raw)) }
             }
         }
@@ -78,5 +105,7 @@ fp_impl!(U64, I64);
 fp_impl!(I64, I64);
 fp_impl!(U128, I128);
 fp_impl!(I128, I128);
+#[cfg(not(feature = "deterministic"))]
 fp_impl!(Usize, Isize);
+#[cfg(not(feature = "deterministic"))]
 fp_impl!(Isize, Isize);
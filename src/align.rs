@@ -0,0 +1,44 @@
+use crate::Num;
+
+/// The finer (larger) of two shifts.  Needed for const-generic support, because
+/// the standard ways to compute the maximum of two values are not const.
+pub const fn finer_shift(s0: i32, s1: i32) -> i32 {
+    if s0 > s1 {
+        s0
+    } else {
+        s1
+    }
+}
+
+/// Convert two fixed-point numbers with the same raw type to the finer of their
+/// two shifts, so that they can be compared, added or subtracted.  Both logical
+/// values are unchanged: the raw value of the coarser operand is shifted left
+/// (as `raw_shl` does), and its number of bits grows by the same amount.
+/// Compilation will fail if the new number of bits is too large for the raw type.
+#[allow(clippy::type_complexity)]
+pub fn align_shifts<A: Num, B: Num<Raw = A::Raw>>(
+    a: A,
+    b: B,
+) -> (
+    A::Output<
+        { A::BITS + (finer_shift(A::SHIFT, B::SHIFT) - A::SHIFT) as u32 },
+        { finer_shift(A::SHIFT, B::SHIFT) },
+    >,
+    B::Output<
+        { B::BITS + (finer_shift(A::SHIFT, B::SHIFT) - B::SHIFT) as u32 },
+        { finer_shift(A::SHIFT, B::SHIFT) },
+    >,
+)
+where
+    [(); (A::BITS + (finer_shift(A::SHIFT, B::SHIFT) - A::SHIFT) as u32) as usize]:,
+    [(); (B::BITS + (finer_shift(A::SHIFT, B::SHIFT) - B::SHIFT) as u32) as usize]:,
+    [(); finer_shift(A::SHIFT, B::SHIFT) as usize]:,
+{
+    let shift = finer_shift(A::SHIFT, B::SHIFT);
+    unsafe {
+        (
+            A::Output::new_unchecked(a.raw() << (shift - A::SHIFT) as u32),
+            B::Output::new_unchecked(b.raw() << (shift - B::SHIFT) as u32),
+        )
+    }
+}
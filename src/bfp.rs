@@ -0,0 +1,280 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::{LengthMismatch, Num, RangeError, SliceError};
+
+/// Shift `v` right by `k` bits, rounding to nearest with ties away from zero.
+pub(crate) fn round_shr(v: i128, k: u32) -> i128 {
+    match k {
+        0 => v,
+        1..=126 => {
+            let magnitude = ((v.unsigned_abs() + (1 << (k - 1))) >> k) as i128;
+            if v < 0 {
+                -magnitude
+            } else {
+                magnitude
+            }
+        }
+        _ => 0,
+    }
+}
+
+/// The right shift which moves a mantissa from exponent `from` to exponent `to`
+/// (at least `from`), clamped to the width of `i128`.
+fn shift_to(from: i32, to: i32) -> u32 {
+    (to as i64 - from as i64).min(i128::BITS as i64) as u32
+}
+
+/// Number of bits needed to hold `v` as a signed integer (zero for `v == 0`).
+fn signed_bits(v: i128) -> u32 {
+    if v == 0 {
+        0
+    } else if v < 0 {
+        i128::BITS + 1 - v.leading_ones()
+    } else {
+        i128::BITS + 1 - v.leading_zeros()
+    }
+}
+
+/// Block floating-point numbers: `N` signed mantissas of raw type `Raw`
+/// (`i8`, `i16`, `i32` or `i64`) sharing a single exponent, so that element `i`
+/// has the logical value `mantissas[i] * 2^exponent`.
+///
+/// Blocks are kept normalized: unless every mantissa is zero, the exponent is
+/// as small as possible, so the largest mantissa uses the full width of `Raw`.
+/// Every operation that produces a block rounds the exact result to nearest
+/// (with ties away from zero) at the exponent it chooses, and adjusts that
+/// exponent so that no mantissa overflows.
+///
+/// Exponents are computed in `i64` and saturate to the range of `i32`: a result
+/// whose exponent would be below `i32::MIN` is rounded to a multiple of
+/// `2^i32::MIN` (so, in practice, to zero), and one whose exponent would be above
+/// `i32::MAX` keeps its mantissas with an exponent of `i32::MAX`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BfpBlock<const N: usize, Raw: Num<Raw = Raw>> {
+    mantissas: [Raw; N],
+    exponent: i32,
+}
+
+/// A block floating-point container of `N` mantissas of raw type `T`, in the
+/// argument order of the block-oriented DSP literature.  See [`BfpBlock`].
+pub type BlockFp<T, const N: usize> = BfpBlock<N, T>;
+
+impl<const N: usize, Raw: Num<Raw = Raw>> BfpBlock<N, Raw>
+where
+    Raw: Into<i128> + TryFrom<i128>,
+{
+    const CHECK: () = {
+        assert!(Raw::SIGNED, "mantissas must be signed");
+        assert!(
+            Raw::BITS <= 64,
+            "mantissas wider than 64 bits are not supported"
+        );
+    };
+    /// Create a normalized block whose element `i` has the logical value
+    /// `values[i] * 2^exponent`, rounded to the precision of the block, with the
+    /// exponent saturated to the range of `i32`.
+    fn from_wide(values: [i128; N], exponent: i64) -> Self {
+        let () = Self::CHECK;
+        let saturate = |e: i64| e.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        let bits = values.iter().map(|&v| signed_bits(v)).max().unwrap_or(0);
+        if bits == 0 {
+            return Self {
+                mantissas: [0i128; N].map(|v| v.try_into().ok().unwrap()),
+                exponent: saturate(exponent),
+            };
+        }
+        let mut k = bits as i64 - Raw::BITS as i64;
+        if k > 0 {
+            // rounding can carry into one more bit
+            let (min, max): (i128, i128) = (Raw::MIN.into(), Raw::MAX.into());
+            if values
+                .iter()
+                .any(|&v| !(min..=max).contains(&round_shr(v, k as u32)))
+            {
+                k += 1;
+            }
+        }
+        // shifting further right only makes the mantissas smaller
+        k = k.max(i32::MIN as i64 - exponent);
+        let mantissas = values.map(|v| {
+            let m = if k >= 0 {
+                round_shr(v, k.min(i128::BITS as i64) as u32)
+            } else {
+                v << -k
+            };
+            m.try_into().ok().unwrap()
+        });
+        Self {
+            mantissas,
+            exponent: saturate(exponent + k),
+        }
+    }
+    fn wide(&self) -> [i128; N] {
+        self.mantissas.map(|m| m.into())
+    }
+    /// Create a normalized block from raw mantissas and an exponent.
+    pub fn new(mantissas: [Raw; N], exponent: i32) -> Self {
+        Self::from_wide(mantissas.map(|m| m.into()), exponent as i64)
+    }
+    /// Create a block holding the values of `xs`, rounded to the precision of
+    /// the block (which is exact when `F::BITS <= Raw::BITS`).
+    pub fn from_fp<F: Num>(xs: &[F; N]) -> Self
+    where
+        F::Raw: Into<i128>,
+    {
+        Self::from_wide(xs.map(|x| x.raw().into()), -(F::SHIFT as i64))
+    }
+    /// Convert the block to an array of `F`, rounding each value to the nearest
+    /// multiple of `2^-F::SHIFT`, or return a `RangeError` if any value is out of
+    /// the range of `F`.
+    pub fn to_fp<F: Num>(&self) -> Result<[F; N], RangeError>
+    where
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let shift = self.exponent as i64 + F::SHIFT as i64;
+        let (min, max) = (F::MIN.raw().into(), F::MAX.raw().into());
+        let mut raw = [0; N];
+        for (r, m) in raw.iter_mut().zip(self.wide()) {
+            *r = if m == 0 {
+                0
+            } else if shift >= 0 {
+                if signed_bits(m) as i64 + shift > i128::BITS as i64 - 1 {
+                    return Err(if m < 0 {
+                        RangeError::TooSmall
+                    } else {
+                        RangeError::TooLarge
+                    });
+                }
+                m << shift
+            } else {
+                round_shr(m, shift.unsigned_abs().min(i128::BITS as u64) as u32)
+            };
+            if *r < min {
+                return Err(RangeError::TooSmall);
+            } else if *r > max {
+                return Err(RangeError::TooLarge);
+            }
+        }
+        Ok(raw.map(|r| unsafe { F::new_unchecked(r.try_into().ok().unwrap()) }))
+    }
+    /// Create a block holding the values of `xs`, as `from_fp` does, or return
+    /// `LengthMismatch` if `xs` does not have `N` elements.
+    pub fn from_slice<F: Num>(xs: &[F]) -> Result<Self, LengthMismatch>
+    where
+        F::Raw: Into<i128>,
+    {
+        let xs: &[F; N] = xs.try_into().map_err(|_| LengthMismatch)?;
+        Ok(Self::from_fp(xs))
+    }
+    /// Write the values of the block to `out`, rounded as by `to_fp`.  Returns
+    /// `SliceError::LengthMismatch` if `out` does not have `N` elements, or
+    /// `SliceError::Range` if any value is out of the range of `F`, leaving `out`
+    /// unchanged in either case.
+    pub fn to_slice<F: Num>(&self, out: &mut [F]) -> Result<(), SliceError>
+    where
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let out: &mut [F; N] = out.try_into().map_err(|_| LengthMismatch)?;
+        *out = self.to_fp()?;
+        Ok(())
+    }
+    /// Round every element to the nearest multiple of `2^exponent` (with ties
+    /// away from zero), discarding precision, and normalize the result.  An
+    /// `exponent` at or below the current exponent leaves the block unchanged.
+    pub fn renormalize(self, exponent: i32) -> Self {
+        let k = exponent as i64 - self.exponent as i64;
+        if k <= 0 {
+            return self;
+        }
+        let k = k.min(i128::BITS as i64) as u32;
+        Self::from_wide(self.wide().map(|m| round_shr(m, k)), exponent as i64)
+    }
+    /// Return the mantissas.
+    pub fn mantissas(&self) -> &[Raw; N] {
+        &self.mantissas
+    }
+    /// Return true if every element is zero.
+    pub fn is_zero(&self) -> bool {
+        self.wide().iter().all(|&m| m == 0)
+    }
+    /// Return the shared exponent.
+    pub fn exponent(&self) -> i32 {
+        self.exponent
+    }
+    /// Return the logical values as `f64`.  Truncation is possible.
+    pub fn to_f64(&self) -> [f64; N] {
+        let scale = crate::fp_impl::pow2_f64(self.exponent);
+        self.wide().map(|m| m as f64 * scale)
+    }
+    /// Multiply every element by `2^n`, by adjusting the exponent.  This is exact
+    /// unless the exponent saturates.
+    pub fn scale_pow2(self, n: i32) -> Self {
+        Self::from_wide(self.wide(), self.exponent as i64 + n as i64)
+    }
+    /// Elementwise sum or difference, at the larger of the two exponents.
+    fn add_sub(self, other: Self, negate: bool) -> Self {
+        // The exponent of an all-zero block is arbitrary, so must not be used.
+        if other.is_zero() {
+            return self;
+        } else if self.is_zero() {
+            return if negate { -other } else { other };
+        }
+        let exponent = self.exponent.max(other.exponent);
+        let (a, b) = (self.wide(), other.wide());
+        let mut sum = [0; N];
+        for i in 0..N {
+            // Align both mantissas to the larger exponent.  Shifting before the
+            // addition rounds twice, but keeps the intermediate in range.
+            let x = round_shr(a[i], shift_to(self.exponent, exponent));
+            let y = round_shr(b[i], shift_to(other.exponent, exponent));
+            sum[i] = if negate { x - y } else { x + y };
+        }
+        Self::from_wide(sum, exponent as i64)
+    }
+}
+
+impl<const N: usize, Raw: Num<Raw = Raw>> Add for BfpBlock<N, Raw>
+where
+    Raw: Into<i128> + TryFrom<i128>,
+{
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.add_sub(other, false)
+    }
+}
+
+impl<const N: usize, Raw: Num<Raw = Raw>> Sub for BfpBlock<N, Raw>
+where
+    Raw: Into<i128> + TryFrom<i128>,
+{
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self.add_sub(other, true)
+    }
+}
+
+impl<const N: usize, Raw: Num<Raw = Raw>> Mul for BfpBlock<N, Raw>
+where
+    Raw: Into<i128> + TryFrom<i128>,
+{
+    type Output = Self;
+    /// Elementwise product.  The products are formed exactly and then rounded.
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self.wide(), other.wide());
+        let mut product = [0; N];
+        for i in 0..N {
+            product[i] = a[i] * b[i];
+        }
+        Self::from_wide(product, self.exponent as i64 + other.exponent as i64)
+    }
+}
+
+impl<const N: usize, Raw: Num<Raw = Raw>> Neg for BfpBlock<N, Raw>
+where
+    Raw: Into<i128> + TryFrom<i128>,
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_wide(self.wide().map(|m| -m), self.exponent as i64)
+    }
+}
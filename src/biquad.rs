@@ -0,0 +1,162 @@
+use core::f64::consts::{LN_10, TAU};
+
+use crate::{const_math, Num, I32};
+
+/// Biquad (second-order IIR) filter coefficients, normalized so that `a0 == 1`,
+/// for the difference equation
+/// `y[n] = b0 x[n] + b1 x[n-1] + b2 x[n-2] - a1 y[n-1] - a2 y[n-2]`.
+///
+/// The constructors follow Robert Bristow-Johnson's "Audio EQ Cookbook", and are
+/// all `const fn`, so a filter can be designed and [quantized](Self::quantize)
+/// at compile time:
+///
+/// ```
+/// use fp::*;
+/// const LOWPASS: BiquadCoeffs<30> =
+///     BiquadDesign::lowpass(48000., 1000., core::f64::consts::FRAC_1_SQRT_2)
+///         .quantize()
+///         .unwrap();
+/// assert!(LOWPASS.max_error() <= 0.5 / (1 << 30) as f64);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BiquadDesign {
+    pub b0: f64,
+    pub b1: f64,
+    pub b2: f64,
+    pub a1: f64,
+    pub a2: f64,
+}
+
+/// Intermediate quantities shared by the cookbook formulas: `cos(w0)` and `alpha`.
+const fn prewarp(sample_rate: f64, freq: f64, q: f64) -> (f64, f64) {
+    assert!(
+        freq > 0. && freq < sample_rate / 2.,
+        "frequency must be between zero and the Nyquist frequency"
+    );
+    assert!(q > 0., "Q must be positive");
+    let w0 = TAU * freq / sample_rate;
+    (const_math::cos(w0), const_math::sin(w0) / (2. * q))
+}
+
+impl BiquadDesign {
+    /// Normalize by `a0`.
+    const fn new(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self {
+            b0: b[0] / a[0],
+            b1: b[1] / a[0],
+            b2: b[2] / a[0],
+            a1: a[1] / a[0],
+            a2: a[2] / a[0],
+        }
+    }
+    /// Second-order low-pass filter with cutoff `freq` (in the same units as
+    /// `sample_rate`) and quality factor `q` (`FRAC_1_SQRT_2` for a Butterworth response).
+    pub const fn lowpass(sample_rate: f64, freq: f64, q: f64) -> Self {
+        let (cos, alpha) = prewarp(sample_rate, freq, q);
+        let b = (1. - cos) / 2.;
+        Self::new([b, 2. * b, b], [1. + alpha, -2. * cos, 1. - alpha])
+    }
+    /// Second-order high-pass filter; see [`lowpass`](Self::lowpass).
+    pub const fn highpass(sample_rate: f64, freq: f64, q: f64) -> Self {
+        let (cos, alpha) = prewarp(sample_rate, freq, q);
+        let b = (1. + cos) / 2.;
+        Self::new([b, -2. * b, b], [1. + alpha, -2. * cos, 1. - alpha])
+    }
+    /// Notch (band-stop) filter centered on `freq`, with bandwidth `freq / q`.
+    pub const fn notch(sample_rate: f64, freq: f64, q: f64) -> Self {
+        let (cos, alpha) = prewarp(sample_rate, freq, q);
+        Self::new([1., -2. * cos, 1.], [1. + alpha, -2. * cos, 1. - alpha])
+    }
+    /// Low-shelf filter, with gain `gain_db` (in decibels) below `freq`.
+    pub const fn low_shelf(sample_rate: f64, freq: f64, q: f64, gain_db: f64) -> Self {
+        let (cos, alpha) = prewarp(sample_rate, freq, q);
+        let a = const_math::exp(gain_db / 40. * LN_10);
+        let s = 2. * const_math::sqrt(a) * alpha;
+        Self::new(
+            [
+                a * ((a + 1.) - (a - 1.) * cos + s),
+                2. * a * ((a - 1.) - (a + 1.) * cos),
+                a * ((a + 1.) - (a - 1.) * cos - s),
+            ],
+            [
+                (a + 1.) + (a - 1.) * cos + s,
+                -2. * ((a - 1.) + (a + 1.) * cos),
+                (a + 1.) + (a - 1.) * cos - s,
+            ],
+        )
+    }
+    /// High-shelf filter, with gain `gain_db` (in decibels) above `freq`.
+    pub const fn high_shelf(sample_rate: f64, freq: f64, q: f64, gain_db: f64) -> Self {
+        let (cos, alpha) = prewarp(sample_rate, freq, q);
+        let a = const_math::exp(gain_db / 40. * LN_10);
+        let s = 2. * const_math::sqrt(a) * alpha;
+        Self::new(
+            [
+                a * ((a + 1.) + (a - 1.) * cos + s),
+                -2. * a * ((a - 1.) + (a + 1.) * cos),
+                a * ((a + 1.) + (a - 1.) * cos - s),
+            ],
+            [
+                (a + 1.) - (a - 1.) * cos + s,
+                2. * ((a - 1.) - (a + 1.) * cos),
+                (a + 1.) - (a - 1.) * cos - s,
+            ],
+        )
+    }
+    /// Return the coefficients as `[b0, b1, b2, a1, a2]`.
+    pub const fn to_array(&self) -> [f64; 5] {
+        [self.b0, self.b1, self.b2, self.a1, self.a2]
+    }
+    /// Round each coefficient to the nearest `I32<32, SHIFT>`, or return `None` if
+    /// any coefficient is out of range.
+    pub const fn quantize<const SHIFT: i32>(&self) -> Option<BiquadCoeffs<SHIFT>> {
+        let coeffs = self.to_array();
+        let scale = crate::fp_impl::pow2_f64(SHIFT);
+        let mut raw = [0; 5];
+        let mut error: f64 = 0.;
+        let mut i = 0;
+        while i < 5 {
+            let scaled = coeffs[i] * scale;
+            let rounded = if scaled < 0. {
+                scaled - 0.5
+            } else {
+                scaled + 0.5
+            };
+            if !(rounded > i32::MIN as f64 - 1. && rounded < i32::MAX as f64 + 1.) {
+                return None;
+            }
+            raw[i] = rounded as i32;
+            let diff = raw[i] as f64 / scale - coeffs[i];
+            let diff = if diff < 0. { -diff } else { diff };
+            if diff > error {
+                error = diff;
+            }
+            i += 1;
+        }
+        Some(BiquadCoeffs { raw, error })
+    }
+}
+
+/// Biquad coefficients quantized to `I32<32, SHIFT>`, as produced by
+/// [`BiquadDesign::quantize`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BiquadCoeffs<const SHIFT: i32> {
+    raw: [i32; 5],
+    error: f64,
+}
+
+impl<const SHIFT: i32> BiquadCoeffs<SHIFT> {
+    /// Return the quantized coefficients as `[b0, b1, b2, a1, a2]`.
+    pub fn coeffs(&self) -> [I32<32, SHIFT>; 5] {
+        self.raw.map(|c| unsafe { I32::new_unchecked(c) })
+    }
+    /// Return the raw values of the quantized coefficients, as `[b0, b1, b2, a1, a2]`.
+    pub const fn raw(&self) -> [i32; 5] {
+        self.raw
+    }
+    /// Return the largest absolute difference between a quantized coefficient
+    /// and the exact coefficient it represents.
+    pub const fn max_error(&self) -> f64 {
+        self.error
+    }
+}
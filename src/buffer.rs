@@ -0,0 +1,102 @@
+//! Zero-copy views of slices of fixed-point numbers as slices of their raw
+//! values, together with the metadata needed to describe those buffers to numpy
+//! (e.g. from a Python binding layer) without losing their scale.
+
+use crate::{Num, RangeError};
+
+/// The layout of a buffer of fixed-point numbers of one type, in the terms of
+/// numpy's [array interface](https://numpy.org/doc/stable/reference/arrays.interface.html).
+/// The logical value of each element is its raw value times [`scale`](Self::scale).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NumpyFormat {
+    /// The numpy type string of the raw values, e.g. `"<i2"` for `i16` on a
+    /// little-endian target.
+    pub typestr: &'static str,
+    pub bits: u32,
+    pub shift: i32,
+}
+
+impl NumpyFormat {
+    /// Return the format of a buffer of `F`, or `None` if numpy has no integer
+    /// type of the same width as `F::Raw` (i.e. for 128-bit raw types).
+    pub fn of<F: Num>() -> Option<Self> {
+        let typestr = match (F::SIGNED, core::mem::size_of::<F::Raw>()) {
+            (true, 1) => "|i1",
+            (false, 1) => "|u1",
+            #[cfg(target_endian = "little")]
+            (true, 2) => "<i2",
+            #[cfg(target_endian = "little")]
+            (false, 2) => "<u2",
+            #[cfg(target_endian = "little")]
+            (true, 4) => "<i4",
+            #[cfg(target_endian = "little")]
+            (false, 4) => "<u4",
+            #[cfg(target_endian = "little")]
+            (true, 8) => "<i8",
+            #[cfg(target_endian = "little")]
+            (false, 8) => "<u8",
+            #[cfg(target_endian = "big")]
+            (true, 2) => ">i2",
+            #[cfg(target_endian = "big")]
+            (false, 2) => ">u2",
+            #[cfg(target_endian = "big")]
+            (true, 4) => ">i4",
+            #[cfg(target_endian = "big")]
+            (false, 4) => ">u4",
+            #[cfg(target_endian = "big")]
+            (true, 8) => ">i8",
+            #[cfg(target_endian = "big")]
+            (false, 8) => ">u8",
+            _ => return None,
+        };
+        Some(Self {
+            typestr,
+            bits: F::BITS,
+            shift: F::SHIFT,
+        })
+    }
+    /// Return the factor, `2^-SHIFT`, from raw values to logical values.
+    pub fn scale(&self) -> f64 {
+        crate::fp_impl::pow2_f64(-self.shift)
+    }
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// View a slice of fixed-point numbers as a slice of their raw values,
+            /// without copying.
+            pub fn as_raw_slice(xs: &[Self]) -> &[$T] {
+                // Safety: `Self` is `repr(transparent)` over `$T`.
+                unsafe { core::slice::from_raw_parts(xs.as_ptr().cast(), xs.len()) }
+            }
+            /// View a slice of raw values as a slice of fixed-point numbers, without
+            /// copying, or return a `RangeError` if any value is out of the range of
+            /// `Self`.
+            pub fn from_raw_slice(raw: &[$T]) -> Result<&[Self], RangeError> {
+                for &r in raw {
+                    Self::new(r)?;
+                }
+                // Safety: `Self` is `repr(transparent)` over `$T`, and every value
+                // has been checked.
+                Ok(unsafe { core::slice::from_raw_parts(raw.as_ptr().cast(), raw.len()) })
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+fp_impl!(I128, i128);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
@@ -0,0 +1,189 @@
+use crate::{Num, RangeError};
+
+/// The product of two magnitudes, as the `(high, low)` halves of a 256-bit value.
+pub(crate) fn mul_u128(x: u128, y: u128) -> (u128, u128) {
+    let (x1, x0) = (x >> 64, x & u64::MAX as u128);
+    let (y1, y0) = (y >> 64, y & u64::MAX as u128);
+    let (mid, carry) = (x0 * y1).overflowing_add(x1 * y0);
+    let (lo, c) = (x0 * y0).overflowing_add(mid << 64);
+    let hi = x1 * y1 + (mid >> 64) + ((carry as u128) << 64) + c as u128;
+    (hi, lo)
+}
+
+/// `floor(x * y / 2^k)`, for a product of sign `negative` and magnitudes `x` and
+/// `y`, as its sign and its magnitude modulo `2^128`, along with whether the
+/// magnitude is exact (i.e. less than `2^128`).  The product is formed exactly,
+/// so the only rounding is the floor.
+pub(crate) fn mul_shift(negative: bool, x: u128, y: u128, k: i32) -> (bool, u128, bool) {
+    let (hi, lo) = mul_u128(x, y);
+    if hi == 0 && lo == 0 {
+        return (false, 0, true);
+    }
+    let n = k.unsigned_abs();
+    if k <= 0 {
+        let exact = hi == 0 && (n == 0 || (n < 128 && lo >> (128 - n) == 0));
+        return (negative, if n < 128 { lo << n } else { 0 }, exact);
+    }
+    let (hi, lo, inexact) = match n {
+        1..=127 => (hi >> n, (lo >> n) | (hi << (128 - n)), lo << (128 - n) != 0),
+        128 => (0, hi, lo != 0),
+        129..=255 => (0, hi >> (n - 128), lo != 0 || hi << (256 - n) != 0),
+        _ => (0, 0, true),
+    };
+    // the floor of a negative quotient rounds its magnitude up
+    let (lo, carry) = lo.overflowing_add((negative && inexact) as u128);
+    let exact = hi == 0 && !carry;
+    (negative && (lo != 0 || !exact), lo, exact)
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// Return the sign and magnitude of the raw value.
+            pub(crate) fn sign_magnitude(self) -> (bool, u128) {
+                let raw = self.raw();
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let negative = raw < 0;
+                let magnitude = if negative {
+                    (raw as i128).unsigned_abs()
+                } else {
+                    raw as u128
+                };
+                (negative, magnitude)
+            }
+            /// Return the raw value with sign `negative` and magnitude `m`, or `None`
+            /// if it is out of the range of `Self`.
+            fn from_sign_magnitude(negative: bool, m: u128) -> Option<Self> {
+                let raw = if negative {
+                    0i128
+                        .checked_sub_unsigned(m)
+                        .and_then(|v| <$T>::try_from(v).ok())
+                } else {
+                    <$T>::try_from(m).ok()
+                };
+                raw.and_then(|raw| Self::new(raw).ok())
+            }
+            /// Add `other`, or return `None` if the sum is out of the range of `Self`.
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                self.raw()
+                    .checked_add(other.raw())
+                    .and_then(|raw| Self::new(raw).ok())
+            }
+            /// Subtract `other`, or return `None` if the difference is out of the
+            /// range of `Self`.
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                self.raw()
+                    .checked_sub(other.raw())
+                    .and_then(|raw| Self::new(raw).ok())
+            }
+            /// Negate, or return `None` if the result is out of the range of `Self`
+            /// (i.e. for `MIN` of a signed type, or any nonzero unsigned value).
+            pub fn checked_neg(self) -> Option<Self> {
+                self.raw().checked_neg().and_then(|raw| Self::new(raw).ok())
+            }
+            /// Multiply by `other`, rounding the product toward negative infinity to
+            /// the shift of `Self` (as `raw_shr` does), or return `None` if it is out
+            /// of the range of `Self`.  The product is formed exactly before the
+            /// rounding, even for 128-bit raw types.
+            pub fn checked_mul(self, other: Self) -> Option<Self> {
+                let (a_negative, a) = self.sign_magnitude();
+                let (b_negative, b) = other.sign_magnitude();
+                match mul_shift(a_negative != b_negative, a, b, SHIFT) {
+                    (negative, m, true) => Self::from_sign_magnitude(negative, m),
+                    _ => None,
+                }
+            }
+            /// Add `other` (which may have fewer bits) to this number in place, or
+            /// return a `RangeError`, leaving it unchanged, if the sum is out of the
+            /// range of `Self`.
+            pub fn checked_add_assign<const B1: u32>(
+                &mut self,
+                other: $Name<B1, SHIFT>,
+            ) -> Result<(), RangeError> {
+                let sum = match self.raw().checked_add(other.raw()) {
+                    Some(sum) => Self::new(sum)?,
+                    None if other.raw() < 0 as $T => return Err(RangeError::TooSmall),
+                    None => return Err(RangeError::TooLarge),
+                };
+                *self = sum;
+                Ok(())
+            }
+            /// Subtract `other` (which may have fewer bits) from this number in place,
+            /// or return a `RangeError`, leaving it unchanged, if the difference is out
+            /// of the range of `Self`.
+            pub fn checked_sub_assign<const B1: u32>(
+                &mut self,
+                other: $Name<B1, SHIFT>,
+            ) -> Result<(), RangeError> {
+                let difference = match self.raw().checked_sub(other.raw()) {
+                    Some(difference) => Self::new(difference)?,
+                    None if other.raw() < 0 as $T => return Err(RangeError::TooLarge),
+                    None => return Err(RangeError::TooSmall),
+                };
+                *self = difference;
+                Ok(())
+            }
+            /// Add `other` (which may have fewer bits) to this number in place,
+            /// saturating at `MIN` or `MAX`.
+            pub fn saturating_add_assign<const B1: u32>(&mut self, other: $Name<B1, SHIFT>) {
+                *self = Self::clamp_raw(self.raw().saturating_add(other.raw()));
+            }
+            /// Subtract `other` (which may have fewer bits) from this number in place,
+            /// saturating at `MIN` or `MAX`.
+            pub fn saturating_sub_assign<const B1: u32>(&mut self, other: $Name<B1, SHIFT>) {
+                *self = Self::clamp_raw(self.raw().saturating_sub(other.raw()));
+            }
+            /// Clamp a raw value into the range of `Self`.
+            fn clamp_raw(raw: $T) -> Self {
+                unsafe { Self::new_unchecked(raw.clamp(Self::MIN.raw(), Self::MAX.raw())) }
+            }
+            /// Add `other`, saturating at `MIN` or `MAX` if the sum is out of the range
+            /// of `Self`.
+            pub fn saturating_add(self, other: Self) -> Self {
+                Self::clamp_raw(self.raw().saturating_add(other.raw()))
+            }
+            /// Subtract `other`, saturating at `MIN` or `MAX` if the difference is out
+            /// of the range of `Self`.
+            pub fn saturating_sub(self, other: Self) -> Self {
+                Self::clamp_raw(self.raw().saturating_sub(other.raw()))
+            }
+            /// Negate, saturating at `MAX` (for `MIN` of a signed type) or at zero
+            /// (for any unsigned value).
+            pub fn saturating_neg(self) -> Self {
+                Self::clamp_raw((0 as $T).saturating_sub(self.raw()))
+            }
+            /// Multiply by `other`, rounding as `checked_mul` does, and saturating at
+            /// `MIN` or `MAX` if the product is out of the range of `Self`.
+            pub fn saturating_mul(self, other: Self) -> Self {
+                let (a_negative, a) = self.sign_magnitude();
+                let (b_negative, b) = other.sign_magnitude();
+                let negative = a_negative != b_negative;
+                let product = match mul_shift(negative, a, b, SHIFT) {
+                    (negative, m, true) => Self::from_sign_magnitude(negative, m),
+                    _ => None,
+                };
+                match product {
+                    Some(product) => product,
+                    None if negative => Self::MIN,
+                    None => Self::MAX,
+                }
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+fp_impl!(I128, i128);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
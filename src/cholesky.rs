@@ -0,0 +1,168 @@
+use crate::{Matrix, Num, RangeError};
+
+/// Error from [`Matrix::cholesky`] or a triangular solve.
+#[derive(Debug, Eq, PartialEq)]
+pub enum SolveError {
+    /// The matrix is not positive definite, at least at the precision of its
+    /// elements (a pivot was zero or negative, given the rounding of the earlier
+    /// elements of the factor).
+    NotPositiveDefinite,
+    /// The triangular matrix has a zero on its diagonal.
+    Singular,
+    TooSmall,
+    TooLarge,
+}
+
+impl From<RangeError> for SolveError {
+    fn from(err: RangeError) -> Self {
+        match err {
+            RangeError::TooSmall => SolveError::TooSmall,
+            RangeError::TooLarge => SolveError::TooLarge,
+        }
+    }
+}
+
+/// `n / d`, rounded to nearest with ties away from zero, for `d > 0` and
+/// `|n| < 2^126`.
+fn round_div(n: i128, d: i128) -> i128 {
+    let magnitude = (2 * n.unsigned_abs() + d as u128) / (2 * d as u128);
+    if n < 0 {
+        -(magnitude as i128)
+    } else {
+        magnitude as i128
+    }
+}
+
+/// Return the raw value `raw` (at the shift of `F`) as an `F`, or a
+/// `RangeError` if it is out of range.
+fn checked<F: Num>(raw: i128) -> Result<F, RangeError>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    let (min, max): (i128, i128) = (F::MIN.raw().into(), F::MAX.raw().into());
+    if raw < min {
+        Err(RangeError::TooSmall)
+    } else if raw > max {
+        Err(RangeError::TooLarge)
+    } else {
+        Ok(unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) })
+    }
+}
+
+impl<F: Num, const N: usize> Matrix<F, N, N>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// The sums of products are accumulated at twice the shift of `F`, exactly,
+    /// in an `i128`.
+    const CHECK: () = {
+        assert!(F::SIGNED, "solver elements must be signed");
+        assert!(
+            F::SHIFT >= 0,
+            "solver elements must not have a negative shift"
+        );
+        let top = if F::BITS as i32 + F::SHIFT > 2 * F::BITS as i32 {
+            F::BITS as i32 - 1 + F::SHIFT
+        } else {
+            2 * F::BITS as i32 - 2
+        };
+        // one bit for each of the N products and the initial term
+        let terms = (usize::BITS - (N + 1).leading_zeros()) as i32;
+        assert!(top + terms < 127, "solver accumulator too wide for i128");
+    };
+    /// Return the lower triangular Cholesky factor `L` of this symmetric matrix,
+    /// such that `L L^T` is the matrix, or a `SolveError` if the matrix is not
+    /// positive definite or an element of `L` is out of the range of `F`.  Only
+    /// the lower triangle of the matrix is read.
+    ///
+    /// The elements of `L` are rounded to nearest, with ties away from zero, but
+    /// every sum of products is formed exactly, so the only errors are the
+    /// roundings of the elements themselves.  Compilation will fail unless `F` is
+    /// signed, with a nonnegative shift, and narrow enough for those sums to fit
+    /// in an `i128` (e.g. at most 61 bits for a 4x4 matrix with shift 0).
+    pub fn cholesky(&self) -> Result<Self, SolveError> {
+        let () = Self::CHECK;
+        let a = self.rows();
+        let s = F::SHIFT as u32;
+        let mut l = [[0i128; N]; N];
+        for j in 0..N {
+            // the sums are at shift 2 * SHIFT
+            let dot =
+                |l: &[[i128; N]; N], i: usize| -> i128 { (0..j).map(|k| l[i][k] * l[j][k]).sum() };
+            let pivot = (a[j][j].raw().into() << s) - dot(&l, j);
+            if pivot <= 0 {
+                return Err(SolveError::NotPositiveDefinite);
+            }
+            let root = (pivot as u128).isqrt();
+            // round to nearest: (root + 1/2)^2 = root^2 + root + 1/4
+            let root = (root + (pivot as u128 - root * root > root) as u128) as i128;
+            l[j][j] = checked::<F>(root)?.raw().into();
+            for i in j + 1..N {
+                let sum = (a[i][j].raw().into() << s) - dot(&l, i);
+                l[i][j] = checked::<F>(round_div(sum, l[j][j]))?.raw().into();
+            }
+        }
+        let mut out = [[F::MIN; N]; N];
+        for (out, l) in out.iter_mut().zip(l) {
+            for (out, x) in out.iter_mut().zip(l) {
+                *out = checked(x)?;
+            }
+        }
+        Ok(Matrix::new(out))
+    }
+    /// Solve `L x = b` by forward substitution, where `L` is this matrix, which
+    /// must be lower triangular (its upper triangle is not read).  Each element
+    /// of `x` is rounded to nearest, with ties away from zero.
+    pub fn solve_lower(&self, b: [F; N]) -> Result<[F; N], SolveError> {
+        let () = Self::CHECK;
+        self.substitute(b, |i| (0..i, i))
+    }
+    /// Solve `U x = b` by back substitution, where `U` is this matrix, which must
+    /// be upper triangular (its lower triangle is not read).  Each element of `x`
+    /// is rounded to nearest, with ties away from zero.
+    pub fn solve_upper(&self, b: [F; N]) -> Result<[F; N], SolveError> {
+        let () = Self::CHECK;
+        self.substitute(b, |i| (N - i..N, N - 1 - i))
+    }
+    /// Solve `A x = b`, where `A` is this symmetric positive definite matrix, by
+    /// way of its Cholesky factor `L`: first `L y = b`, then `L^T x = y`.  (To
+    /// solve for several right-hand sides, factor once with
+    /// [`cholesky`](Self::cholesky) and call the triangular solves directly.)
+    pub fn cholesky_solve(&self, b: [F; N]) -> Result<[F; N], SolveError> {
+        let l = self.cholesky()?;
+        l.transpose().solve_upper(l.solve_lower(b)?)
+    }
+    /// Substitute for the elements of `x` in the order given by `order`, which
+    /// maps each step to the range of already-solved columns and the row (and
+    /// diagonal column) to solve.
+    fn substitute(
+        &self,
+        b: [F; N],
+        order: impl Fn(usize) -> (core::ops::Range<usize>, usize),
+    ) -> Result<[F; N], SolveError> {
+        let a = self.rows();
+        let s = F::SHIFT as u32;
+        let mut x = [0i128; N];
+        for step in 0..N {
+            let (solved, i) = order(step);
+            let diagonal: i128 = a[i][i].raw().into();
+            if diagonal == 0 {
+                return Err(SolveError::Singular);
+            }
+            let dot: i128 = solved.map(|k| a[i][k].raw().into() * x[k]).sum();
+            let sum = (b[i].raw().into() << s) - dot;
+            // dividing by a negative diagonal negates both terms
+            let (sum, diagonal) = if diagonal < 0 {
+                (-sum, -diagonal)
+            } else {
+                (sum, diagonal)
+            };
+            x[i] = checked::<F>(round_div(sum, diagonal))?.raw().into();
+        }
+        let mut out = [F::MIN; N];
+        for (out, x) in out.iter_mut().zip(x) {
+            *out = checked(x)?;
+        }
+        Ok(out)
+    }
+}
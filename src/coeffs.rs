@@ -0,0 +1,104 @@
+use core::marker::PhantomData;
+
+use crate::fp_impl::pow2_f64;
+use crate::{table_quantize, Num};
+
+/// Quantize an array of `f64` filter coefficients (e.g. FIR taps from a float
+/// design) to a fixed-point format at compile time, giving [`FpCoeffs`] with the
+/// typed coefficients and the worst-case quantization error.
+///
+/// Each coefficient is rounded to the nearest value of the format, with ties away
+/// from zero.  The coefficients are always quantized at compile time, even when
+/// assigned to a `let` binding, so compilation fails if any coefficient is out of
+/// the range of the format, or if the largest error exceeds the given bound:
+///
+/// ```
+/// use fp::*;
+/// const TAPS: FpCoeffs<I16<16, 15>, 5> =
+///     fp_coeffs!([0.1, 0.2, 0.4, 0.2, 0.1], I16<16, 15>, 0.5 / 32768.);
+/// assert_eq!(TAPS.raw(), &[3277, 6554, 13107, 6554, 3277]);
+/// assert!(TAPS.max_error() < 1.5e-5);
+/// let taps: [I16<16, 15>; 5] = TAPS.coeffs();
+/// ```
+///
+/// ```compile_fail
+/// use fp::*;
+/// // 1/3 is not within 2^-10 of any value with 8 fractional bits
+/// const TAPS: FpCoeffs<I16<16, 8>, 1> = fp_coeffs!([1. / 3.], I16<16, 8>, 1. / 1024.);
+/// ```
+///
+/// ```compile_fail
+/// use fp::*;
+/// // 1.5 is out of the range of the format
+/// let taps = fp_coeffs!([0.5, 1.5], I16<16, 15>, 1.);
+/// ```
+#[macro_export]
+macro_rules! fp_coeffs {
+    ($coeffs:expr, $F:ty, $max_error:expr $(,)?) => {
+        const {
+            const COEFFS: &[f64] = &$coeffs;
+            const N: usize = COEFFS.len();
+            let mut raw = [0 as <$F as $crate::Num>::Raw; N];
+            let mut error: f64 = 0.;
+            let mut i = 0;
+            while i < N {
+                let (r, e) = $crate::coeff_quantize::<$F>(COEFFS[i]);
+                raw[i] = r as <$F as $crate::Num>::Raw;
+                if e > error {
+                    error = e;
+                }
+                i += 1;
+            }
+            assert!(error <= $max_error, "quantization error exceeds the bound");
+            // Safety: `coeff_quantize` checks that every coefficient is in range.
+            unsafe { $crate::FpCoeffs::<$F, N>::from_raw_unchecked(raw, error) }
+        }
+    };
+}
+
+/// The raw value nearest to `c` in format `F`, and the absolute error of that
+/// value.
+#[doc(hidden)]
+pub const fn coeff_quantize<F: Num>(c: f64) -> (i128, f64) {
+    let raw = table_quantize::<F>(c);
+    let error = raw as f64 * pow2_f64(-F::SHIFT) - c;
+    (raw, if error < 0. { -error } else { error })
+}
+
+/// Filter coefficients quantized to the fixed-point format `F`, with the largest
+/// quantization error.  Usually built at compile time by [`fp_coeffs!`].
+#[derive(Clone, Copy, Debug)]
+pub struct FpCoeffs<F: Num, const N: usize> {
+    raw: [F::Raw; N],
+    error: f64,
+    _format: PhantomData<F>,
+}
+
+impl<F: Num, const N: usize> FpCoeffs<F, N> {
+    /// Create coefficients from their raw values and their largest error.
+    ///
+    /// # Safety
+    ///
+    /// Every raw value must lie between `F::MIN.raw()` and `F::MAX.raw()`.
+    #[doc(hidden)]
+    pub const unsafe fn from_raw_unchecked(raw: [F::Raw; N], error: f64) -> Self {
+        Self {
+            raw,
+            error,
+            _format: PhantomData,
+        }
+    }
+    /// Return the coefficients.
+    pub fn coeffs(&self) -> [F; N] {
+        self.raw.map(|r| unsafe { F::new_unchecked(r) })
+    }
+    /// Return the raw values of the coefficients.
+    pub const fn raw(&self) -> &[F::Raw; N] {
+        &self.raw
+    }
+    /// Return the largest absolute difference between a quantized coefficient and
+    /// the coefficient it was quantized from.
+    pub const fn max_error(&self) -> f64 {
+        self.error
+    }
+}
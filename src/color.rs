@@ -0,0 +1,248 @@
+//! Helpers for 8-bit color channels, represented as `U8<8, 8>` (0.8 format).
+//! A channel value `c` stands for `c / 255`, as is standard in graphics, so that
+//! `0xff` is fully on (or fully opaque).  This is slightly different from the
+//! logical value `c / 256` of the fixed-point type; the functions below divide by
+//! 255 exactly (with rounding to nearest) rather than shifting by 8.
+
+use crate::{const_math::powf, Num, U16, U8};
+
+/// Divide `t` by 255, rounding to nearest, for `t <= 255 * 255 + 127`.
+/// This is the standard `+128`, `(t + (t >> 8)) >> 8` idiom, which is exact
+/// over that range.
+fn div_255(t: u16) -> u8 {
+    let t = t + 128;
+    ((t + (t >> 8)) >> 8) as u8
+}
+
+/// Multiply two channel values, e.g. to apply an alpha or a tint:
+/// `a * b / 255`, rounded to nearest.
+pub fn mul_channel(a: U8<8, 8>, b: U8<8, 8>) -> U8<8, 8> {
+    unsafe { U8::new_unchecked(div_255(a.raw() as u16 * b.raw() as u16)) }
+}
+
+/// Blend `src` over `dst` with opacity `alpha`: `(src * alpha + dst * (255 - alpha)) / 255`,
+/// rounded to nearest.  `alpha == 0xff` returns `src` and `alpha == 0` returns `dst`.
+pub fn blend(src: U8<8, 8>, dst: U8<8, 8>, alpha: U8<8, 8>) -> U8<8, 8> {
+    let a = alpha.raw() as u16;
+    let t = src.raw() as u16 * a + dst.raw() as u16 * (255 - a);
+    unsafe { U8::new_unchecked(div_255(t)) }
+}
+
+/// Blend two 8.8-format values (e.g. intermediate results of a gradient or a
+/// filter) with opacity `alpha`, which is again taken to mean `alpha / 255`.
+pub fn blend_8_8(src: U16<16, 8>, dst: U16<16, 8>, alpha: U8<8, 8>) -> U16<16, 8> {
+    let a = alpha.raw() as u32;
+    let t = src.raw() as u32 * a + dst.raw() as u32 * (255 - a);
+    unsafe { U16::new_unchecked(((t + 127) / 255) as u16) }
+}
+
+/// Add two channel values, saturating at `0xff`.
+pub fn add_channel(a: U8<8, 8>, b: U8<8, 8>) -> U8<8, 8> {
+    unsafe { U8::new_unchecked(a.raw().saturating_add(b.raw())) }
+}
+
+/// Subtract channel value `b` from `a`, saturating at `0`.
+pub fn sub_channel(a: U8<8, 8>, b: U8<8, 8>) -> U8<8, 8> {
+    unsafe { U8::new_unchecked(a.raw().saturating_sub(b.raw())) }
+}
+
+/// Number of fractional bits in the [`YuvMatrix`] coefficients.
+const COEFF_SHIFT: u32 = 14;
+
+/// Quantize `x` to Q14, rounding to nearest (with ties away from zero).
+const fn q14(x: f64) -> i32 {
+    let scaled = x * (1 << COEFF_SHIFT) as f64;
+    if scaled < 0. {
+        (scaled - 0.5) as i32
+    } else {
+        (scaled + 0.5) as i32
+    }
+}
+
+/// Multiply-accumulate three channel values by Q14 coefficients, rounding
+/// to nearest, add `offset`, and saturate to the channel range.
+fn dot_q14(coeffs: [i32; 3], x: [i32; 3], offset: i32) -> U8<8, 8> {
+    let sum = coeffs[0] * x[0] + coeffs[1] * x[1] + coeffs[2] * x[2];
+    let y = ((sum + (1 << (COEFF_SHIFT - 1))) >> COEFF_SHIFT) + offset;
+    unsafe { U8::new_unchecked(y.clamp(0, 255) as u8) }
+}
+
+/// Coefficients for conversion between 8-bit R'G'B' and Y'CbCr ("YUV"),
+/// quantized to Q14 at compile time.
+///
+/// Both directions round to nearest and saturate to the 8-bit channel range.
+/// In limited ("studio swing") range, Y' spans 16 to 235 and the chroma
+/// components span 16 to 240; in full range (as in JPEG), all components
+/// span 0 to 255.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct YuvMatrix {
+    forward: [[i32; 3]; 3],
+    inverse: [[i32; 3]; 3],
+    y_offset: i32,
+}
+
+impl YuvMatrix {
+    /// ITU-R BT.601 (standard definition), limited range.
+    pub const BT601: Self = Self::new(0.299, 0.114, false);
+    /// ITU-R BT.601, full range (as used by JPEG/JFIF).
+    pub const BT601_FULL: Self = Self::new(0.299, 0.114, true);
+    /// ITU-R BT.709 (high definition), limited range.
+    pub const BT709: Self = Self::new(0.2126, 0.0722, false);
+    /// ITU-R BT.709, full range.
+    pub const BT709_FULL: Self = Self::new(0.2126, 0.0722, true);
+
+    /// Compute the coefficients from the luma weights of red (`kr`) and blue (`kb`).
+    pub const fn new(kr: f64, kb: f64, full_range: bool) -> Self {
+        let kg = 1. - kr - kb;
+        let (y_scale, c_scale) = if full_range {
+            (1., 1.)
+        } else {
+            (219. / 255., 224. / 255.)
+        };
+        let (cb, cr) = (c_scale / (2. * (1. - kb)), c_scale / (2. * (1. - kr)));
+        let forward = [
+            [q14(y_scale * kr), q14(y_scale * kg), q14(y_scale * kb)],
+            [q14(-cb * kr), q14(-cb * kg), q14(cb * (1. - kb))],
+            [q14(cr * (1. - kr)), q14(-cr * kg), q14(-cr * kb)],
+        ];
+        let (y, cb, cr) = (
+            1. / y_scale,
+            2. * (1. - kb) / c_scale,
+            2. * (1. - kr) / c_scale,
+        );
+        let inverse = [
+            [q14(y), 0, q14(cr)],
+            [q14(y), q14(-cb * kb / kg), q14(-cr * kr / kg)],
+            [q14(y), q14(cb), 0],
+        ];
+        Self {
+            forward,
+            inverse,
+            y_offset: if full_range { 0 } else { 16 },
+        }
+    }
+    /// Convert `[R', G', B']` to `[Y', Cb, Cr]`.
+    pub fn rgb_to_yuv(&self, rgb: [U8<8, 8>; 3]) -> [U8<8, 8>; 3] {
+        let x = rgb.map(|c| c.raw() as i32);
+        [
+            dot_q14(self.forward[0], x, self.y_offset),
+            dot_q14(self.forward[1], x, 128),
+            dot_q14(self.forward[2], x, 128),
+        ]
+    }
+    /// Convert `[Y', Cb, Cr]` to `[R', G', B']`.
+    pub fn yuv_to_rgb(&self, yuv: [U8<8, 8>; 3]) -> [U8<8, 8>; 3] {
+        let [y, cb, cr] = yuv.map(|c| c.raw() as i32);
+        let x = [y - self.y_offset, cb - 128, cr - 128];
+        self.inverse.map(|row| dot_q14(row, x, 0))
+    }
+}
+
+/// The sRGB electro-optical transfer function: encoded value to linear light.
+const fn srgb_decode(e: f64) -> f64 {
+    if e <= 0.04045 {
+        e / 12.92
+    } else {
+        powf((e + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// The inverse of [`srgb_decode`]: linear light to encoded value.
+const fn srgb_encode(l: f64) -> f64 {
+    if l <= 0.003_130_8 {
+        l * 12.92
+    } else {
+        1.055 * powf(l, 1. / 2.4) - 0.055
+    }
+}
+
+/// Linear light in `U16<16, 15>` (so that 1.0 is exactly `1 << 15`) of each 8-bit code.
+static SRGB_TO_LINEAR: [u16; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (srgb_decode(i as f64 / 255.) * 32768. + 0.5) as u16;
+        i += 1;
+    }
+    table
+};
+
+/// `LINEAR_TO_SRGB_THRESHOLD[k]` is the smallest linear value (in `U16<16, 15>`)
+/// which encodes to an 8-bit code greater than `k`, i.e. whose encoded value is
+/// at least `(k + 0.5) / 255`.
+static LINEAR_TO_SRGB_THRESHOLD: [u16; 255] = {
+    let mut table = [0; 255];
+    let mut k = 0;
+    while k < 255 {
+        let t = srgb_decode((k as f64 + 0.5) / 255.) * 32768.;
+        let ceil = t as u16;
+        table[k] = if (ceil as f64) < t { ceil + 1 } else { ceil };
+        k += 1;
+    }
+    table
+};
+
+/// Linear light in `U16<16, 15>` at 257 evenly-spaced encoded values, for interpolation.
+static SRGB_TO_LINEAR_INTERP: [u16; 257] = {
+    let mut table = [0; 257];
+    let mut i = 0;
+    while i <= 256 {
+        table[i] = (srgb_decode(i as f64 / 256.) * 32768. + 0.5) as u16;
+        i += 1;
+    }
+    table
+};
+
+/// Encoded value in units of 2^-16 at 1025 evenly-spaced linear values, for interpolation.
+static LINEAR_TO_SRGB_INTERP: [u32; 1025] = {
+    let mut table = [0; 1025];
+    let mut i = 0;
+    while i <= 1024 {
+        table[i] = (srgb_encode(i as f64 / 1024.) * 65536. + 0.5) as u32;
+        i += 1;
+    }
+    table
+};
+
+/// Decode an 8-bit sRGB channel value (where `0xff` is full intensity) to linear
+/// light, by table lookup.  The result is correctly rounded; full intensity is
+/// exactly 1.0.
+pub fn srgb_to_linear(c: U8<8, 8>) -> U16<16, 15> {
+    unsafe { U16::new_unchecked(SRGB_TO_LINEAR[c.raw() as usize]) }
+}
+
+/// Encode linear light as an 8-bit sRGB channel value, rounding to the nearest
+/// code in the encoded domain (by binary search of a table of decision thresholds).
+/// Values above 1.0 saturate at `0xff`.
+pub fn linear_to_srgb(l: U16<16, 15>) -> U8<8, 8> {
+    let code = LINEAR_TO_SRGB_THRESHOLD.partition_point(|&t| t <= l.raw());
+    unsafe { U8::new_unchecked(code as u8) }
+}
+
+/// Decode a high-precision sRGB-encoded value to linear light, by linear interpolation
+/// in a 257-entry table.  The error is below 4e-5 (about one LSB of the output).
+pub fn srgb_to_linear_interp(e: U16<16, 16>) -> U16<16, 15> {
+    let (i, frac) = ((e.raw() >> 8) as usize, (e.raw() & 0xff) as u32);
+    let (y0, y1) = (
+        SRGB_TO_LINEAR_INTERP[i] as u32,
+        SRGB_TO_LINEAR_INTERP[i + 1] as u32,
+    );
+    let y = (y0 * (256 - frac) + y1 * frac + 128) >> 8;
+    unsafe { U16::new_unchecked(y as u16) }
+}
+
+/// Encode linear light as a high-precision sRGB value, by linear interpolation in a
+/// 1025-entry table.  The error is below 2.5e-4, and is largest just above the linear
+/// segment of the sRGB curve (at linear values near 0.003), where the curvature is
+/// greatest.  Values of 1.0 and above saturate at `U16::<16, 16>::MAX`.
+pub fn linear_to_srgb_interp(l: U16<16, 15>) -> U16<16, 16> {
+    let raw = l.raw().min(1 << 15) as u32;
+    let (i, frac) = ((raw >> 5) as usize, raw & 31);
+    let y = if i == 1024 {
+        LINEAR_TO_SRGB_INTERP[1024]
+    } else {
+        let (y0, y1) = (LINEAR_TO_SRGB_INTERP[i], LINEAR_TO_SRGB_INTERP[i + 1]);
+        (y0 * (32 - frac) + y1 * frac + 16) >> 5
+    };
+    unsafe { U16::new_unchecked(y.min(u16::MAX as u32) as u16) }
+}
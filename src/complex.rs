@@ -0,0 +1,15 @@
+use crate::Num;
+
+/// Complex number with fixed-point real and imaginary parts, e.g. an I/Q sample.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Complex<F: Num> {
+    pub re: F,
+    pub im: F,
+}
+
+impl<F: Num> Complex<F> {
+    /// Create the complex number `re + im i`.
+    pub fn new(re: F, im: F) -> Self {
+        Self { re, im }
+    }
+}
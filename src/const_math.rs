@@ -0,0 +1,120 @@
+//! Floating-point functions usable in const context, for generating tables and
+//! coefficients at compile time.  (The standard library's `f64::ln` etc. are not
+//! `const fn`.)  They are accurate to a few ULPs over their documented domains,
+//! which is far finer than any fixed-point quantization they feed.
+
+use core::f64::consts::{FRAC_PI_2, LN_2, TAU};
+
+/// Natural logarithm, for `x > 0`.
+pub const fn ln(x: f64) -> f64 {
+    assert!(x > 0., "ln of non-positive number");
+    // Reduce to x = m * 2^e with m in [1, 2).
+    let (mut m, mut e) = (x, 0);
+    while m >= 2. {
+        m /= 2.;
+        e += 1;
+    }
+    while m < 1. {
+        m *= 2.;
+        e -= 1;
+    }
+    // ln(m) = 2 atanh(z) = 2 (z + z^3/3 + z^5/5 + ...), with z <= 1/3.
+    let z = (m - 1.) / (m + 1.);
+    let (mut term, mut sum, mut k) = (z, 0., 1);
+    while k < 60 {
+        sum += term / k as f64;
+        term *= z * z;
+        k += 2;
+    }
+    2. * sum + e as f64 * LN_2
+}
+
+/// Exponential function.
+pub const fn exp(x: f64) -> f64 {
+    // Reduce to x = k ln(2) + r with |r| <= ln(2) / 2.
+    let k = (x / LN_2 + if x < 0. { -0.5 } else { 0.5 }) as i32;
+    let r = x - k as f64 * LN_2;
+    let (mut term, mut sum, mut n) = (1., 1., 1);
+    while n < 30 {
+        term *= r / n as f64;
+        sum += term;
+        n += 1;
+    }
+    let mut scale = 1.;
+    let mut i = 0;
+    while i < k.unsigned_abs() {
+        scale *= 2.;
+        i += 1;
+    }
+    if k < 0 {
+        sum / scale
+    } else {
+        sum * scale
+    }
+}
+
+/// `x` raised to the power `y`, for `x >= 0`.
+pub const fn powf(x: f64, y: f64) -> f64 {
+    if x == 0. {
+        0.
+    } else {
+        exp(y * ln(x))
+    }
+}
+
+/// Square root, for `x >= 0`.
+pub const fn sqrt(x: f64) -> f64 {
+    assert!(x >= 0., "sqrt of negative number");
+    if x == 0. {
+        return 0.;
+    }
+    // Newton's method from above converges monotonically, so stop as soon as
+    // the estimate stops decreasing.
+    let mut y = if x > 1. { x } else { 1. };
+    loop {
+        let next = (y + x / y) / 2.;
+        if next >= y {
+            return y;
+        }
+        y = next;
+    }
+}
+
+/// Sine of `x` (in radians).
+pub const fn sin(x: f64) -> f64 {
+    // Reduce to |r| <= pi, then sum the Taylor series.
+    let k = (x / TAU + if x < 0. { -0.5 } else { 0.5 }) as i64;
+    let r = x - k as f64 * TAU;
+    let (mut term, mut sum, mut n) = (r, r, 1);
+    while n < 20 {
+        term = -term * r * r / ((2 * n) as f64 * (2 * n + 1) as f64);
+        sum += term;
+        n += 1;
+    }
+    sum
+}
+
+/// Cosine of `x` (in radians).
+pub const fn cos(x: f64) -> f64 {
+    sin(x + FRAC_PI_2)
+}
+
+/// Arctangent of `x` (in radians), for `|x| <= 1`.
+pub const fn atan(x: f64) -> f64 {
+    assert!(x >= -1. && x <= 1., "atan argument out of range");
+    // atan(x) = 2 atan(x / (1 + sqrt(1 + x^2))), twice, reduces to |z| <= 0.2;
+    // then sum the Taylor series.
+    let mut z = x;
+    let mut i = 0;
+    while i < 2 {
+        z /= 1. + sqrt(1. + z * z);
+        i += 1;
+    }
+    let (mut term, mut sum, mut k) = (z, 0., 1);
+    while k < 50 {
+        sum += term / k as f64;
+        term *= -z * z;
+        k += 2;
+    }
+    4. * sum
+}
@@ -0,0 +1,139 @@
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::{clog2, Num, RangeError};
+
+/// Which part of the full convolution or correlation to return, following the
+/// conventions of `numpy.convolve`.  With input lengths `m >= n`:
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConvolveMode {
+    /// Every point where the inputs overlap at all: `m + n - 1` outputs.
+    Full,
+    /// The central part of the full output, of the same length as the longer
+    /// input: `m` outputs, starting `(n - 1) / 2` points into the full output.
+    Same,
+    /// Only the points where the shorter input lies entirely within the longer
+    /// one: `m - n + 1` outputs.
+    Valid,
+}
+
+/// The indices of the full convolution of inputs of lengths `m` and `n` (both
+/// nonzero) which `mode` selects.
+fn output_range(m: usize, n: usize, mode: ConvolveMode) -> Range<usize> {
+    let (long, short) = (m.max(n), m.min(n));
+    let (start, len) = match mode {
+        ConvolveMode::Full => (0, long + short - 1),
+        ConvolveMode::Same => ((short - 1) / 2, long),
+        ConvolveMode::Valid => (short - 1, long - short + 1),
+    };
+    start..start + len
+}
+
+/// Outputs `range` of the full convolution of raw values.  Overflow safety is
+/// the caller's responsibility.
+pub(crate) fn convolve_raw<Acc: Num>(a: &[i128], b: &[i128], range: Range<usize>) -> Vec<Acc>
+where
+    Acc::Raw: TryFrom<i128>,
+{
+    range
+        .map(|n| {
+            // the terms a[i] * b[n - i] for which both indices are in range
+            let lo = (n + 1).saturating_sub(b.len());
+            let hi = n.min(a.len() - 1);
+            let y: i128 = (lo..=hi).map(|i| a[i] * b[n - i]).sum();
+            unsafe { Acc::new_unchecked(y.try_into().ok().unwrap()) }
+        })
+        .collect()
+}
+
+/// Check the accumulator, convert the inputs to raw values (reversing `b` for
+/// a correlation), and evaluate the outputs selected by `mode` with `eval`.
+pub(crate) fn convolve_with<A: Num, B: Num, Acc: Num>(
+    a: &[A],
+    b: &[B],
+    mode: ConvolveMode,
+    correlate: bool,
+    eval: impl FnOnce(&[i128], &[i128], Range<usize>) -> Vec<Acc>,
+) -> Result<Vec<Acc>, RangeError>
+where
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+{
+    check_acc::<A, B, Acc>(a.len().min(b.len()))?;
+    if a.is_empty() || b.is_empty() {
+        return Ok(Vec::new());
+    }
+    let a: Vec<i128> = a.iter().map(|x| x.raw().into()).collect();
+    let mut b: Vec<i128> = b.iter().map(|x| x.raw().into()).collect();
+    if correlate {
+        b.reverse();
+    }
+    let range = output_range(a.len(), b.len(), mode);
+    Ok(eval(&a, &b, range))
+}
+
+/// Compile-time checks on the accumulator type of a product of `A` and `B`.
+struct AccFormat<A, B, Acc>(PhantomData<(A, B, Acc)>);
+
+impl<A: Num, B: Num, Acc: Num> AccFormat<A, B, Acc> {
+    const CHECK: () = {
+        assert!(
+            Acc::SHIFT == A::SHIFT + B::SHIFT,
+            "accumulator shift must be the sum of the input shifts"
+        );
+        assert!(
+            Acc::SIGNED == (A::SIGNED || B::SIGNED),
+            "accumulator must be signed if and only if either input is signed"
+        );
+    };
+}
+
+/// Check the accumulator type of a product of `A` and `B`, summed over at
+/// most `terms` terms.
+pub(crate) fn check_acc<A: Num, B: Num, Acc: Num>(terms: usize) -> Result<(), RangeError> {
+    let () = AccFormat::<A, B, Acc>::CHECK;
+    let bits = A::BITS + B::BITS + clog2(terms);
+    if bits > Acc::BITS || bits > i128::BITS - 1 {
+        return Err(RangeError::TooLarge);
+    }
+    Ok(())
+}
+
+/// Convolve `a` with `b`, returning the part of the result selected by `mode`.
+///
+/// Each output is a sum of at most `min(a.len(), b.len())` exact products, which
+/// is accumulated exactly into the caller's choice of accumulator type `Acc`.
+/// `Acc` must have shift `A::SHIFT + B::SHIFT`, and must be signed if either
+/// input is signed (or compilation will fail).  Returns a `RangeError` if `Acc` is not guaranteed to hold
+/// every output (i.e. if `A::BITS + B::BITS + clog2(min(a.len(), b.len()))` exceeds
+/// `Acc::BITS`, or 127).  Returns no outputs if either input is empty.
+pub fn convolve<A: Num, B: Num, Acc: Num>(
+    a: &[A],
+    b: &[B],
+    mode: ConvolveMode,
+) -> Result<Vec<Acc>, RangeError>
+where
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+    Acc::Raw: TryFrom<i128>,
+{
+    convolve_with(a, b, mode, false, convolve_raw)
+}
+
+/// Cross-correlate `a` with `b`, returning the part of the result selected by
+/// `mode`: output `k` of the full correlation is the sum over `i` of
+/// `a[i + k - (b.len() - 1)] * b[i]`, i.e. the first full output is `b` lagged so
+/// that its last element meets the start of `a`.  The accumulator type `Acc` is
+/// as for [`convolve`].
+pub fn correlate<A: Num, B: Num, Acc: Num>(
+    a: &[A],
+    b: &[B],
+    mode: ConvolveMode,
+) -> Result<Vec<Acc>, RangeError>
+where
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+    Acc::Raw: TryFrom<i128>,
+{
+    convolve_with(a, b, mode, true, convolve_raw)
+}
@@ -0,0 +1,66 @@
+use core::marker::PhantomData;
+
+use crate::Num;
+
+/// One-pole DC-blocking filter, `y[n] = x[n] - x[n-1] + a * y[n-1]`, with the
+/// pole at `a = 1 - 2^-K`.  The -3 dB cutoff frequency is approximately
+/// `RATE / (2 * pi * 2^K)`; e.g. `K = 8` gives about 30 Hz at 48 kHz.
+///
+/// The filter state is kept with `K` extra fractional bits, so the fraction
+/// discarded when computing `a * y[n-1]` is carried into the next sample
+/// rather than lost.  (A naive implementation truncates on every sample, which
+/// biases the output and leaves a DC offset of up to `2^K` LSBs.)
+///
+/// The input type must be signed.  The output has the same raw type and shift,
+/// and one more bit, since the filter's gain approaches 2 at the Nyquist frequency.
+#[derive(Clone, Debug)]
+pub struct DcBlocker<F: Num, const K: u32> {
+    /// Previous input sample.
+    x1: i64,
+    /// Previous output sample, with `K` extra fractional bits.
+    y1: i64,
+    _sample: PhantomData<F>,
+}
+
+impl<F: Num, const K: u32> Default for DcBlocker<F, K>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+    [(); (F::BITS + 1) as usize]:,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num, const K: u32> DcBlocker<F, K>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+    [(); (F::BITS + 1) as usize]:,
+{
+    const CHECK: () = {
+        assert!(F::SIGNED, "DC blocker input must be signed");
+        assert!(K >= 1, "K must be at least 1");
+        assert!(F::BITS + 1 + K < i64::BITS, "state too wide for i64");
+    };
+    /// Create a DC blocker with zero initial state.
+    pub fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            x1: 0,
+            y1: 0,
+            _sample: PhantomData,
+        }
+    }
+    /// Filter one sample.
+    pub fn process(&mut self, x: F) -> F::Output<{ F::BITS + 1 }, { F::SHIFT }> {
+        let x: i64 = x.raw().into();
+        // y[n] = (x[n] - x[n-1]) + y[n-1] - 2^-K * y[n-1], at K extra fractional bits.
+        // The leakage term is rounded rather than truncated, so that it has no bias.
+        self.y1 += ((x - self.x1) << K) - ((self.y1 + (1 << (K - 1))) >> K);
+        self.x1 = x;
+        let y = (self.y1 + (1 << (K - 1))) >> K;
+        let max = (1 << F::BITS) - 1;
+        let y = y.clamp(-max - 1, max);
+        unsafe { F::Output::new_unchecked(y.try_into().ok().unwrap()) }
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Num;
+
+/// Error returned when an output buffer is too short.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BufferTooSmall;
+
+/// Upper bound on the decimal length of a number with `bits` bits and shift
+/// `shift`: a sign, the digits of `2^(bits - shift)`, a decimal point, and one
+/// digit per fractional bit.  (`2^n` has `floor(n log10(2)) + 1` digits.)
+pub(crate) const fn decimal_len(bits: u32, shift: i32) -> usize {
+    let int_bits = bits as i64 - if shift > 0 { 0 } else { shift as i64 };
+    let int_digits = (int_bits * 30103 / 100000 + 1) as usize;
+    let frac_digits = if shift > 0 { shift as usize + 1 } else { 0 };
+    1 + int_digits + frac_digits
+}
+
+/// Multiply the little-endian decimal digits `digits[..len]` by `factor`
+/// (at most 10), in place, and return the new length.
+fn mul_digits(digits: &mut [u8], mut len: usize, factor: u8) -> Result<usize, BufferTooSmall> {
+    let mut carry = 0;
+    for d in &mut digits[..len] {
+        let v = *d * factor + carry;
+        (*d, carry) = (v % 10, v / 10);
+    }
+    if carry > 0 {
+        *digits.get_mut(len).ok_or(BufferTooSmall)? = carry;
+        len += 1;
+    }
+    Ok(len)
+}
+
+/// Write the decimal digits of `n * factor^k` to the start of `buf`, padded with
+/// leading zeros to at least `width` digits, and return the number of digits.
+fn write_digits(
+    buf: &mut [u8],
+    mut n: u128,
+    factor: u8,
+    k: u32,
+    width: usize,
+) -> Result<usize, BufferTooSmall> {
+    // Work with little-endian digit values, then reverse and convert to ASCII.
+    let mut len = 0;
+    loop {
+        *buf.get_mut(len).ok_or(BufferTooSmall)? = (n % 10) as u8;
+        n /= 10;
+        len += 1;
+        if n == 0 {
+            break;
+        }
+    }
+    for _ in 0..k {
+        len = mul_digits(buf, len, factor)?;
+    }
+    while len < width {
+        *buf.get_mut(len).ok_or(BufferTooSmall)? = 0;
+        len += 1;
+    }
+    buf[..len].reverse();
+    for d in &mut buf[..len] {
+        *d += b'0';
+    }
+    Ok(len)
+}
+
+/// Write `-mag / 2^shift` (if `negative`) or `mag / 2^shift` in decimal.
+pub(crate) fn write_decimal(
+    buf: &mut [u8],
+    negative: bool,
+    mag: u128,
+    shift: i32,
+) -> Result<usize, BufferTooSmall> {
+    let mut len = 0;
+    if negative {
+        *buf.get_mut(len).ok_or(BufferTooSmall)? = b'-';
+        len += 1;
+    }
+    if shift <= 0 {
+        // mag * 2^-shift; shift in as many bits as fit first, to save doubling
+        let k = shift.unsigned_abs();
+        let s = k.min(mag.leading_zeros());
+        let n = if mag == 0 { 0 } else { mag << s };
+        let k = if mag == 0 { 0 } else { k - s };
+        return Ok(len + write_digits(&mut buf[len..], n, 2, k, 0)?);
+    }
+    let shift = shift as u32;
+    let (int, frac) = if shift >= 128 {
+        (0, mag)
+    } else {
+        (mag >> shift, mag & ((1 << shift) - 1))
+    };
+    len += write_digits(&mut buf[len..], int, 2, 0, 0)?;
+    if frac != 0 {
+        // frac / 2^shift == (frac >> t) / 2^(shift - t), which has exactly
+        // shift - t decimal places: the digits of (frac >> t) * 5^(shift - t).
+        let t = frac.trailing_zeros();
+        let places = shift - t;
+        *buf.get_mut(len).ok_or(BufferTooSmall)? = b'.';
+        len += 1;
+        len += write_digits(&mut buf[len..], frac >> t, 5, places, places as usize)?;
+    }
+    Ok(len)
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Upper bound on the number of bytes written by
+            /// [`write_decimal`](Self::write_decimal), for sizing a buffer on the stack.
+            pub const DECIMAL_LEN: usize = decimal_len(B, S);
+            /// Write the exact logical value in decimal (e.g. `-12.375`) at the start
+            /// of `buf`, without using `core::fmt`, floating point, or allocation, and
+            /// return the number of bytes written, or `BufferTooSmall` (with the contents
+            /// of `buf` unspecified) if `buf` is too short.
+            ///
+            /// Every fixed-point value has a finite decimal expansion, of at most
+            /// `SHIFT` decimal places; it is written in full, without trailing zeros
+            /// (or a decimal point, for integers).  A leading `-` is written for
+            /// negative values only.
+            pub fn write_decimal(self, buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+                let raw = self.raw();
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let negative = raw < 0;
+                let mag = if negative {
+                    (raw as i128).unsigned_abs()
+                } else {
+                    raw as u128
+                };
+                write_decimal(buf, negative, mag, S)
+            }
+            /// Write the exact logical value in decimal into `buf`, as
+            /// [`write_decimal`](Self::write_decimal) does, and return it as a string.
+            /// This is the building block for lightweight formatting traits (such as
+            /// those of the `ufmt` feature), which only need to write a `&str`; a buffer of
+            /// [`DECIMAL_LEN`](Self::DECIMAL_LEN) bytes is always long enough.
+            pub fn decimal_str(self, buf: &mut [u8]) -> Result<&str, BufferTooSmall> {
+                let len = self.write_decimal(buf)?;
+                // Safety: only ASCII digits, '-' and '.' are written.
+                Ok(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+fp_impl!(I128, i128);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
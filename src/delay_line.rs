@@ -0,0 +1,84 @@
+use crate::{Num, U32};
+
+/// Delay line of the `N` most recent samples, in a fixed-capacity ring buffer,
+/// for echo and comb filters, FIR filter state, and correlation.
+///
+/// Pushing a sample is `O(1)`, as is reading the sample at any delay, where tap
+/// `k` is the sample pushed `k` pushes ago (so tap 0 is the newest sample and tap
+/// `N - 1` the oldest).  Before `N` samples have been pushed, the line is padded
+/// with the initial value given to `new`.
+#[derive(Clone, Debug)]
+pub struct DelayLine<F: Num, const N: usize> {
+    /// Samples in arrival order (a ring buffer; `next` is the oldest).
+    ring: [F; N],
+    next: usize,
+}
+
+impl<F: Num, const N: usize> DelayLine<F, N> {
+    const CHECK: () = assert!(N > 0, "delay line must hold at least one sample");
+    /// Create a delay line initially holding `N` copies of `init`.
+    pub fn new(init: F) -> Self {
+        let () = Self::CHECK;
+        Self {
+            ring: [init; N],
+            next: 0,
+        }
+    }
+    /// Push a sample, and return the sample it evicts (the sample pushed `N`
+    /// pushes ago), which makes the line a delay of `N` samples.
+    pub fn push(&mut self, x: F) -> F {
+        let old = core::mem::replace(&mut self.ring[self.next], x);
+        self.next = if self.next + 1 == N { 0 } else { self.next + 1 };
+        old
+    }
+    /// Return the sample at tap `k`, or `None` if `k >= N`.
+    pub fn tap(&self, k: usize) -> Option<F> {
+        if k >= N {
+            return None;
+        }
+        // the newest sample is just before `next`
+        let i = if k < self.next {
+            self.next - 1 - k
+        } else {
+            self.next + N - 1 - k
+        };
+        Some(self.ring[i])
+    }
+    /// Return the samples from newest to oldest, i.e. taps `0` to `N - 1`.
+    pub fn iter(&self) -> impl Iterator<Item = F> + '_ {
+        // in arrival order, the ring is ring[next..] followed by ring[..next]
+        let (newer, older) = self.ring.split_at(self.next);
+        older.iter().chain(newer).rev().copied()
+    }
+}
+
+impl<F: Num, const N: usize> DelayLine<F, N>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    const CHECK_INTERPOLATE: () = assert!(
+        F::BITS <= 64,
+        "samples wider than 64 bits are not supported"
+    );
+    /// Return the sample at the fractional tap `delay` (e.g. `2.25` for a quarter
+    /// of the way from tap 2 to tap 3), by linear interpolation between the two
+    /// nearest taps, rounded to the nearest LSB of `F` with ties away from zero,
+    /// or `None` if `delay > N - 1`.
+    pub fn tap_interpolate(&self, delay: U32<32, 16>) -> Option<F> {
+        let () = Self::CHECK_INTERPOLATE;
+        let (k, frac) = ((delay.raw() >> 16) as usize, (delay.raw() & 0xffff) as i128);
+        let y0: i128 = self.tap(k)?.raw().into();
+        if frac == 0 {
+            return self.tap(k);
+        }
+        let y1: i128 = self.tap(k + 1)?.raw().into();
+        let y = (y0 << 16) + (y1 - y0) * frac;
+        let y = if y < 0 {
+            -((-y + (1 << 15)) >> 16)
+        } else {
+            (y + (1 << 15)) >> 16
+        };
+        // the result lies between y0 and y1, so it is in range
+        Some(unsafe { F::new_unchecked(y.try_into().ok().unwrap()) })
+    }
+}
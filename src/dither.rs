@@ -0,0 +1,63 @@
+//! Dithered requantization, for reducing the word length of audio (e.g. from Q31
+//! to Q15) without truncation distortion.
+//!
+//! Truncating a signal to fewer bits makes an error which is correlated with the
+//! signal: harmonics of a tone, and silence below the last bit.  Adding
+//! triangular-PDF dither of one LSB of the output before rounding makes the error
+//! white noise, independent of the signal, at the cost of a slightly higher noise
+//! floor.  [`raw_shr_dithered`](crate::I32::raw_shr_dithered) does this in place of
+//! [`raw_shr`](crate::Num::raw_shr), with the same output type, and a seeded
+//! [`Xorshift64`] keeps the noise reproducible.
+
+#[cfg(not(feature = "deterministic"))]
+use crate::{Isize, Usize};
+use crate::{Num, Xorshift64, I16, I32, I64, I8, U16, U32, U64, U8};
+
+/// Triangular dither on `(-2^n, 2^n)`, the difference of two uniform `n`-bit
+/// draws, for `n` from 1 to 64.
+fn tpdf(rng: &mut Xorshift64, n: u32) -> i128 {
+    let (a, b) = (rng.next_u64() >> (64 - n), rng.next_u64() >> (64 - n));
+    a as i128 - b as i128
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Shift the raw value right by N bits, as `raw_shr` does, after
+            /// adding triangular-PDF dither of one LSB of the result, and round
+            /// to nearest.  The output type is that of `raw_shr`, so a result
+            /// which would round past its `MIN` or `MAX` is clamped.
+            pub fn raw_shr_dithered<const N: u32>(
+                self,
+                rng: &mut Xorshift64,
+            ) -> $Name<{ B - N }, { S - N as i32 }>
+            where
+                [(); (B - N) as usize]:,
+                [(); (S - N as i32) as usize]:,
+            {
+                let raw = self.raw();
+                if N == 0 {
+                    return unsafe { $Name::new_unchecked(raw) };
+                }
+                let v = raw as i128 + tpdf(rng, N) + (1 << (N - 1));
+                let out = $Name::<{ B - N }, { S - N as i32 }>::MIN.raw() as i128
+                    ..=$Name::<{ B - N }, { S - N as i32 }>::MAX.raw() as i128;
+                let q = (v >> N).clamp(*out.start(), *out.end());
+                unsafe { $Name::new_unchecked(q as $T) }
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
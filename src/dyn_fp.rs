@@ -0,0 +1,175 @@
+use crate::{shift_i128, telemetry::Format, Num, RangeError};
+
+/// The largest number of bits of a format whose values are held in an `i128`.
+const fn max_bits(signed: bool) -> u32 {
+    128 - !signed as u32
+}
+
+/// The smallest and largest raw values of a supported format.
+const fn raw_range(format: Format) -> (i128, i128) {
+    match (format.bits, format.signed) {
+        (0, _) => (0, 0),
+        (bits, true) => (i128::MIN >> (128 - bits), i128::MAX >> (128 - bits)),
+        (bits, false) => (0, i128::MAX >> (127 - bits)),
+    }
+}
+
+struct Supported<F>(core::marker::PhantomData<F>);
+
+impl<F: Num> Supported<F> {
+    const CHECK: () = assert!(
+        F::BITS <= max_bits(F::SIGNED),
+        "128-bit unsigned formats are not supported"
+    );
+}
+
+/// A fixed-point number whose format (signedness, `BITS` and `SHIFT`) is known
+/// only at run time, e.g. from a device descriptor or a DBC signal definition,
+/// held as an `i128` raw value.
+///
+/// Arithmetic gives the same results, in the same formats, as the static types
+/// would (with the shifts of a sum or difference aligned, as `add_aligned` does,
+/// and with a signed result if either operand is signed), and returns `None` if
+/// the result would need more than 128 bits (or 127 bits, unsigned), or for
+/// division by zero.  So a value computed with `DynFp` converts to the static
+/// type of the same computation without loss.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DynFp {
+    format: Format,
+    raw: i128,
+}
+
+impl DynFp {
+    /// Interpret `raw` as a number of `format`, or return a `RangeError` if it
+    /// is out of the range of `format`, or if `format` has more than 128 bits
+    /// (or 127 bits, unsigned).
+    pub fn new(raw: i128, format: Format) -> Result<Self, RangeError> {
+        if format.bits > max_bits(format.signed) {
+            return Err(RangeError::TooLarge);
+        }
+        let (min, max) = raw_range(format);
+        if raw < min {
+            Err(RangeError::TooSmall)
+        } else if raw > max {
+            Err(RangeError::TooLarge)
+        } else {
+            Ok(Self { format, raw })
+        }
+    }
+    /// Return the number with the value and format of `x`.  Compilation will fail
+    /// for a 128-bit unsigned format.
+    pub fn from_fp<F: Num>(x: F) -> Self
+    where
+        F::Raw: Into<i128>,
+    {
+        let () = Supported::<F>::CHECK;
+        Self {
+            format: Format::of::<F>(),
+            raw: x.raw().into(),
+        }
+    }
+    /// Convert to `F` (which may differ in shift, signedness and bits), rounding
+    /// toward negative infinity at the shift of `F`, as `checked_cast` does, or
+    /// return a `RangeError` if the result is out of the range of `F`.
+    pub fn to_fp<F: Num>(self) -> Result<F, RangeError>
+    where
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let too_far = if self.raw < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        let (raw, exact) = shift_i128(self.raw, self.format.shift, F::SHIFT);
+        let (min, max): (i128, i128) = (F::MIN.raw().into(), F::MAX.raw().into());
+        if !exact {
+            Err(too_far)
+        } else if raw < min {
+            Err(RangeError::TooSmall)
+        } else if raw > max {
+            Err(RangeError::TooLarge)
+        } else {
+            Ok(unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) })
+        }
+    }
+    /// Return the format of this number.
+    pub fn format(self) -> Format {
+        self.format
+    }
+    /// Return the raw value of this number.
+    pub fn raw(self) -> i128 {
+        self.raw
+    }
+    /// Return the logical value of this number as `f64`.  Truncation is possible.
+    pub fn into_f64(self) -> f64 {
+        self.raw as f64 * crate::fp_impl::pow2_f64(self.format.shift.saturating_neg())
+    }
+    /// Return the number with `raw` and a format of `bits` bits at `shift`, or
+    /// `None` if the format is not supported.  `raw` must be in range.
+    fn result(raw: i128, signed: bool, bits: i64, shift: i64) -> Option<Self> {
+        let format = Format {
+            signed,
+            bits: u32::try_from(bits)
+                .ok()
+                .filter(|&b| b <= max_bits(signed))?,
+            shift: shift.try_into().ok()?,
+        };
+        Some(Self { format, raw })
+    }
+    /// The bits of this number once its raw value is shifted to `shift` (and given
+    /// a sign bit, if it is unsigned and `mixed` signedness makes the result signed),
+    /// and that shift.
+    fn aligned(self, mixed: bool, shift: i64) -> (i64, u32) {
+        let k = shift - self.format.shift as i64;
+        let bits = self.format.bits as i64 + (mixed && !self.format.signed) as i64;
+        (bits + k, k as u32)
+    }
+    /// Return the sum, at the finer shift of the two operands, or `None` if it
+    /// would have too many bits.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.add_sub(other, self.format.signed || other.format.signed, false)
+    }
+    /// Return the difference, which is always signed, at the finer shift of the
+    /// two operands, or `None` if it would have too many bits.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.add_sub(other, true, true)
+    }
+    fn add_sub(self, other: Self, signed: bool, subtract: bool) -> Option<Self> {
+        let shift = self.format.shift.max(other.format.shift) as i64;
+        let mixed = self.format.signed != other.format.signed;
+        let (b0, k0) = self.aligned(mixed, shift);
+        let (b1, k1) = other.aligned(mixed, shift);
+        let bits = b0.max(b1) + 1;
+        // check the format before shifting, so that the shifts cannot overflow
+        Self::result(0, signed, bits, shift)?;
+        let (a, b) = (self.raw << k0, other.raw << k1);
+        Self::result(if subtract { a - b } else { a + b }, signed, bits, shift)
+    }
+    /// Return the negation, which is always signed and has one more bit, or `None`
+    /// if it would have too many bits.
+    pub fn checked_neg(self) -> Option<Self> {
+        let bits = self.format.bits as i64 + 1;
+        Self::result(0, true, bits, self.format.shift as i64)?;
+        Self::result(-self.raw, true, bits, self.format.shift as i64)
+    }
+    /// Return the product, whose bits and shift are the sums of those of the
+    /// operands, or `None` if it would have too many bits.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let signed = self.format.signed || other.format.signed;
+        let bits = self.format.bits as i64 + other.format.bits as i64;
+        let shift = self.format.shift as i64 + other.format.shift as i64;
+        Self::result(0, signed, bits, shift)?;
+        Self::result(self.raw * other.raw, signed, bits, shift)
+    }
+    /// Return the quotient, rounded toward zero, at the difference of the shifts of
+    /// the operands, or `None` if the divisor is zero.  As for `Div`, a signed
+    /// quotient has one more bit than the dividend, so it may also be `None` if
+    /// it would have too many bits.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        let signed = self.format.signed || other.format.signed;
+        let bits = self.format.bits as i64 + signed as i64;
+        let shift = self.format.shift as i64 - other.format.shift as i64;
+        Self::result(0, signed, bits, shift)?;
+        Self::result(self.raw.checked_div(other.raw)?, signed, bits, shift)
+    }
+}
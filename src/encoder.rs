@@ -0,0 +1,100 @@
+use crate::{Num, I32, I64};
+
+/// Multiply `x` by `NUM / DEN`, rounding to nearest (with ties away from zero).
+fn mul_ratio<const NUM: u32, const DEN: u32>(x: i64) -> i128 {
+    let p = x.unsigned_abs() as u128 * NUM as u128;
+    let q = ((2 * p + DEN as u128) / (2 * DEN as u128)) as i128;
+    if x < 0 {
+        -q
+    } else {
+        q
+    }
+}
+
+/// Phase-locked-loop tracking observer for a quadrature encoder, estimating the
+/// position and velocity of the shaft from its quantized count.
+///
+/// Each update first advances the position estimate by the velocity estimate,
+/// and then corrects both by the tracking error `e` between the count and the
+/// predicted position:
+///
+/// ```text
+/// velocity += (KI_NUM / KI_DEN) * e
+/// position += (KP_NUM / KP_DEN) * e
+/// ```
+///
+/// This is a type 2 loop, so it tracks a constant velocity with no steady-state
+/// error.  As a starting point, `KP = 2 w` and `KI = w^2` gives a critically damped
+/// loop with a bandwidth of roughly `w / (2 pi)` times the update rate; both gains
+/// must be at most 1.
+///
+/// Position is in counts, and velocity in counts per update, both with 32
+/// fractional bits.  The count may wrap around from `i32::MAX` to `i32::MIN` (so a
+/// narrower hardware counter should be extended to 32 bits by accumulating its
+/// wrapping differences): the position estimate wraps with it, and the tracking
+/// error is computed modulo `2^32` counts, so nothing overflows however far the
+/// shaft turns.  The gain products are formed in
+/// `i128` and rounded to nearest, and the velocity estimate saturates.
+#[derive(Clone, Debug)]
+pub struct EncoderObserver<
+    const KP_NUM: u32,
+    const KP_DEN: u32,
+    const KI_NUM: u32,
+    const KI_DEN: u32,
+> {
+    /// Position in counts, in Q32.32, wrapping.
+    position: i64,
+    /// Velocity in counts per update, in Q32.32.
+    velocity: i64,
+}
+
+impl<const KP_NUM: u32, const KP_DEN: u32, const KI_NUM: u32, const KI_DEN: u32> Default
+    for EncoderObserver<KP_NUM, KP_DEN, KI_NUM, KI_DEN>
+{
+    fn default() -> Self {
+        Self::new(I32::new(0).unwrap())
+    }
+}
+
+impl<const KP_NUM: u32, const KP_DEN: u32, const KI_NUM: u32, const KI_DEN: u32>
+    EncoderObserver<KP_NUM, KP_DEN, KI_NUM, KI_DEN>
+{
+    const CHECK: () = {
+        assert!(
+            KP_DEN > 0 && KI_DEN > 0,
+            "gain denominators must be nonzero"
+        );
+        assert!(
+            KP_NUM <= KP_DEN && KI_NUM <= KI_DEN,
+            "gains must be at most 1"
+        );
+    };
+    /// Create an observer at rest at the position `count`.
+    pub fn new(count: I32<32, 0>) -> Self {
+        let () = Self::CHECK;
+        Self {
+            position: (count.raw() as i64) << 32,
+            velocity: 0,
+        }
+    }
+    /// Update the estimates from the latest count, and return the new position
+    /// and velocity estimates.
+    pub fn update(&mut self, count: I32<32, 0>) -> (I64<64, 32>, I64<64, 32>) {
+        let predicted = self.position.wrapping_add(self.velocity);
+        // modulo 2^32 counts, so a wrapped count gives a small error
+        let error = ((count.raw() as i64) << 32).wrapping_sub(predicted);
+        let velocity = self.velocity as i128 + mul_ratio::<KI_NUM, KI_DEN>(error);
+        self.velocity = velocity.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        // |KP * error| <= |error|, which fits in an i64
+        self.position = predicted.wrapping_add(mul_ratio::<KP_NUM, KP_DEN>(error) as i64);
+        (self.position(), self.velocity())
+    }
+    /// Return the position estimate, in counts (wrapping as the count does).
+    pub fn position(&self) -> I64<64, 32> {
+        unsafe { I64::new_unchecked(self.position) }
+    }
+    /// Return the velocity estimate, in counts per update.
+    pub fn velocity(&self) -> I64<64, 32> {
+        unsafe { I64::new_unchecked(self.velocity) }
+    }
+}
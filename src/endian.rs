@@ -0,0 +1,76 @@
+use crate::{Num, RangeError};
+
+macro_rules! endian_impl {
+    ($Wrapper:ident, $Name:ident, $T:ty, $N:literal, $order:literal, $to_bytes:ident, $from_bytes:ident) => {
+        #[repr(transparent)]
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        #[doc = concat!("A [`", stringify!($Name), "`] stored as the bytes of its raw value in ", $order)]
+        /// byte order, whatever the byte order of the target.  It has an alignment of 1,
+        /// so it can be used directly as a field of a `#[repr(C, packed)]` protocol or
+        /// register struct; convert it to the native type for arithmetic.
+        pub struct $Wrapper<const BITS: u32, const SHIFT: i32>([u8; $N]);
+
+        impl<const BITS: u32, const SHIFT: i32> $Wrapper<BITS, SHIFT> {
+            pub fn new(val: $Name<BITS, SHIFT>) -> Self {
+                Self(val.raw().$to_bytes())
+            }
+            /// Reinterpret the bytes of a raw value (e.g. from the wire).  They are
+            /// checked against the range of the native type on [`get`](Self::get).
+            pub const fn from_bytes(bytes: [u8; $N]) -> Self {
+                Self(bytes)
+            }
+            /// Return the stored bytes.
+            pub const fn to_bytes(self) -> [u8; $N] {
+                self.0
+            }
+            /// Return the value as the native type, or a `RangeError` if the stored
+            /// raw value is out of its range.
+            pub fn get(self) -> Result<$Name<BITS, SHIFT>, RangeError> {
+                $Name::new(<$T>::$from_bytes(self.0))
+            }
+            /// Store `val`.
+            pub fn set(&mut self, val: $Name<BITS, SHIFT>) {
+                *self = Self::new(val);
+            }
+        }
+
+        impl<const BITS: u32, const SHIFT: i32> From<$Name<BITS, SHIFT>> for $Wrapper<BITS, SHIFT> {
+            fn from(val: $Name<BITS, SHIFT>) -> Self {
+                Self::new(val)
+            }
+        }
+
+        #[doc = concat!("Same as [`", stringify!($Wrapper), "::get`].")]
+        impl<const BITS: u32, const SHIFT: i32> TryFrom<$Wrapper<BITS, SHIFT>> for $Name<BITS, SHIFT> {
+            type Error = RangeError;
+            fn try_from(val: $Wrapper<BITS, SHIFT>) -> Result<Self, RangeError> {
+                val.get()
+            }
+        }
+    };
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty, $N:literal, $Le:ident, $Be:ident) => {
+        use crate::$Name;
+        endian_impl!(
+            $Le,
+            $Name,
+            $T,
+            $N,
+            "little-endian",
+            to_le_bytes,
+            from_le_bytes
+        );
+        endian_impl!($Be, $Name, $T, $N, "big-endian", to_be_bytes, from_be_bytes);
+    };
+}
+
+fp_impl!(I16, i16, 2, LeI16, BeI16);
+fp_impl!(U16, u16, 2, LeU16, BeU16);
+fp_impl!(I32, i32, 4, LeI32, BeI32);
+fp_impl!(U32, u32, 4, LeU32, BeU32);
+fp_impl!(I64, i64, 8, LeI64, BeI64);
+fp_impl!(U64, u64, 8, LeU64, BeU64);
+fp_impl!(I128, i128, 16, LeI128, BeI128);
+fp_impl!(U128, u128, 16, LeU128, BeU128);
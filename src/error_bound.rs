@@ -0,0 +1,178 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::fp_impl::pow2_f64;
+use crate::{rescale_bits, Num, RangeError, Rounding, RoundingMode};
+
+/// A fixed-point number paired with a worst-case bound on the rounding error
+/// accumulated by the computation which produced it, i.e. on the distance
+/// between its value and the value the same computation would have produced
+/// in exact arithmetic.
+///
+/// Exact operations (`Add`, `Sub`, `Mul`, `Neg`) propagate the bounds of their
+/// operands, and the truncating ones (`raw_shr`, `Div`, `rescale`) add the most
+/// that their rounding can lose, so the bound of a final result says how many
+/// low bits of it may be wrong; see `guard_bits`.  Unlike [`Shadow`](crate::Shadow),
+/// which measures the error of particular inputs, the bound holds for every
+/// input within the bounds of the operands.
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorBound<F: Num> {
+    value: F,
+    bound: f64,
+}
+
+impl<F: Num> ErrorBound<F> {
+    /// Create an exact value, with an error bound of zero.
+    pub fn new(value: F) -> Self {
+        Self { value, bound: 0. }
+    }
+    /// Pair `value` with an error bound of `lsbs` least-significant bits of `F`,
+    /// e.g. half an LSB for a sample from a rounding ADC.
+    pub fn with_bound_lsbs(value: F, lsbs: f64) -> Self {
+        Self {
+            value,
+            bound: lsbs * pow2_f64(-F::SHIFT),
+        }
+    }
+    /// Quantize `val` to `F`, as `from_f64` does, with the error of the
+    /// quantization as the bound.
+    pub fn from_f64(val: f64) -> Result<Self, RangeError> {
+        let value = F::from_f64(val)?;
+        Ok(Self {
+            value,
+            bound: (value.into_f64() - val).abs(),
+        })
+    }
+    /// Return the fixed-point value.
+    pub fn value(self) -> F {
+        self.value
+    }
+    /// Return the error bound, in the logical units of the value.
+    pub fn bound(self) -> f64 {
+        self.bound
+    }
+    /// Return the error bound, in least-significant bits of `F`.
+    pub fn bound_lsbs(self) -> f64 {
+        self.bound * pow2_f64(F::SHIFT)
+    }
+    /// Return the number of low bits of the value which the accumulated error
+    /// may have corrupted: the smallest `g` for which the bound is at most `2^g`
+    /// LSBs.  This is the number of guard bits to carry below the precision which
+    /// the result must have.  (`u32::MAX` if the bound is infinite.)
+    pub fn guard_bits(self) -> u32 {
+        let lsbs = self.bound_lsbs();
+        if !lsbs.is_finite() {
+            return u32::MAX;
+        }
+        let mut g = 0;
+        while pow2_f64(g as i32) < lsbs {
+            g += 1;
+        }
+        g
+    }
+    /// Apply an arbitrary operation to the value, which rounds by at most
+    /// `lsbs` least-significant bits of its result, e.g.
+    /// `x.map(|v| v.round_lsb::<4>(), 0.5)`.
+    pub fn map<G: Num>(self, op: impl FnOnce(F) -> G, lsbs: f64) -> ErrorBound<G> {
+        ErrorBound {
+            value: op(self.value),
+            bound: self.bound + lsbs * pow2_f64(-G::SHIFT),
+        }
+    }
+    /// Shift the raw value right by N bits, as `raw_shr` does.  Truncation adds up
+    /// to `2^N - 1` LSBs of `F` to the bound.
+    pub fn raw_shr<const N: u32>(
+        self,
+    ) -> ErrorBound<F::Output<{ F::BITS - N }, { F::SHIFT - N as i32 }>>
+    where
+        [(); (F::BITS - N) as usize]:,
+        [(); (F::SHIFT - N as i32) as usize]:,
+    {
+        let lost = pow2_f64(N as i32 - F::SHIFT) - pow2_f64(-F::SHIFT);
+        ErrorBound {
+            value: self.value.raw_shr::<N>(),
+            bound: self.bound + lost,
+        }
+    }
+    /// Change the shift to `S`, as `rescale` does.  Rounding to nearest adds up to
+    /// half an LSB of the result to the bound, and any other rounding up to one
+    /// LSB of the result less one LSB of `F`.
+    pub fn rescale<const S: i32, R: Rounding>(
+        self,
+    ) -> ErrorBound<F::Output<{ rescale_bits(F::BITS, F::SIGNED, F::SHIFT - S, R::MODE) }, S>>
+    where
+        F::Raw: Into<i128> + TryFrom<i128>,
+        [(); rescale_bits(F::BITS, F::SIGNED, F::SHIFT - S, R::MODE) as usize]:,
+    {
+        let lost = if S >= F::SHIFT {
+            0.
+        } else if matches!(R::MODE, RoundingMode::Nearest | RoundingMode::NearestEven) {
+            pow2_f64(-S) / 2.
+        } else {
+            pow2_f64(-S) - pow2_f64(-F::SHIFT)
+        };
+        ErrorBound {
+            value: self.value.rescale::<S, R>(),
+            bound: self.bound + lost,
+        }
+    }
+}
+
+impl<A: Num + Add<B, Output = C>, B: Num, C: Num> Add<ErrorBound<B>> for ErrorBound<A> {
+    type Output = ErrorBound<C>;
+    fn add(self, other: ErrorBound<B>) -> Self::Output {
+        ErrorBound {
+            value: self.value + other.value,
+            bound: self.bound + other.bound,
+        }
+    }
+}
+
+impl<A: Num + Sub<B, Output = C>, B: Num, C: Num> Sub<ErrorBound<B>> for ErrorBound<A> {
+    type Output = ErrorBound<C>;
+    fn sub(self, other: ErrorBound<B>) -> Self::Output {
+        ErrorBound {
+            value: self.value - other.value,
+            bound: self.bound + other.bound,
+        }
+    }
+}
+
+impl<A: Num + Mul<B, Output = C>, B: Num, C: Num> Mul<ErrorBound<B>> for ErrorBound<A> {
+    type Output = ErrorBound<C>;
+    fn mul(self, other: ErrorBound<B>) -> Self::Output {
+        let (a, b) = (self.value.into_f64().abs(), other.value.into_f64().abs());
+        ErrorBound {
+            value: self.value * other.value,
+            bound: a * other.bound + b * self.bound + self.bound * other.bound,
+        }
+    }
+}
+
+impl<A: Num + Div<B, Output = C>, B: Num, C: Num> Div<ErrorBound<B>> for ErrorBound<A> {
+    type Output = ErrorBound<C>;
+    /// Quotient, rounded toward zero as `Div` does, which adds up to one LSB of
+    /// `C` to the propagated bound.  The bound is infinite if the error of the
+    /// divisor could make it zero.
+    fn div(self, other: ErrorBound<B>) -> Self::Output {
+        let (a, b) = (self.value.into_f64().abs(), other.value.into_f64().abs());
+        let propagated = if b > other.bound {
+            (self.bound * b + a * other.bound) / (b * (b - other.bound))
+        } else {
+            f64::INFINITY
+        };
+        ErrorBound {
+            value: self.value / other.value,
+            bound: propagated + pow2_f64(-C::SHIFT),
+        }
+    }
+}
+
+impl<A: Num + Neg<Output = C>, C: Num> Neg for ErrorBound<A> {
+    type Output = ErrorBound<C>;
+    fn neg(self) -> Self::Output {
+        ErrorBound {
+            value: -self.value,
+            bound: self.bound,
+        }
+    }
+}
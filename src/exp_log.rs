@@ -0,0 +1,171 @@
+use crate::{
+    clog2, sqrt::isqrt, Num, RangeError, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8,
+};
+#[cfg(not(feature = "deterministic"))]
+use crate::{Isize, Usize};
+
+/// Returns the bit width of the return type from `log2`: enough for the integer
+/// part of the logarithm of any raw value from 1 to `2^b - 1` at shift `s` (which
+/// lies in `[-s, b - s)`), plus `frac` fraction bits.
+pub const fn log2_bits(b: u32, s: i32, frac: u32) -> u32 {
+    let low = if s > 0 { s as u32 } else { 0 };
+    let high = if b as i32 > s {
+        (b as i32 - s) as u32
+    } else {
+        0
+    };
+    let m = if low > high { low } else { high };
+    clog2(m as usize) + 1 + frac
+}
+
+/// Returns the bit width of the return type from `exp2`: enough for the integer
+/// part of `2^MAX`, where `MAX` is the largest value of `b` signed bits at shift
+/// `s`, plus `frac` fraction bits.
+pub const fn exp2_bits(b: u32, s: i32, frac: u32) -> u32 {
+    let max = (1u128 << (b - 1)) - 1;
+    let floor = if s >= 0 {
+        if s >= 128 {
+            0
+        } else {
+            max >> s
+        }
+    } else {
+        let k = s.unsigned_abs();
+        if k >= 128 || max.leading_zeros() <= k {
+            u32::MAX as u128
+        } else {
+            max << k
+        }
+    };
+    if floor >= 32 {
+        u32::MAX
+    } else {
+        floor as u32 + 1 + frac
+    }
+}
+
+/// Fraction bits of the internal mantissas.
+const WORK: u32 = 62;
+
+/// `2^(2^-(k + 1))`, in units of `2^-WORK`, each the square root of the last (so
+/// each is exact to within the truncation of the square roots).
+static EXP2_FRACTIONS: [u128; WORK as usize] = {
+    let mut table = [0; WORK as usize];
+    let mut t = 2 << WORK;
+    let mut k = 0;
+    while k < WORK as usize {
+        t = isqrt(t << WORK, 0);
+        table[k] = t;
+        k += 1;
+    }
+    table
+};
+
+struct Log2<const B: u32, const S: i32, const FRAC: u32>;
+
+impl<const B: u32, const S: i32, const FRAC: u32> Log2<B, S, FRAC> {
+    const CHECK: () = assert!(
+        log2_bits(B, S, FRAC) <= 32,
+        "log2 result has too many bits for an i32"
+    );
+}
+
+struct Exp2<const B: u32, const S: i32, const FRAC: u32>;
+
+impl<const B: u32, const S: i32, const FRAC: u32> Exp2<B, S, FRAC> {
+    const CHECK: () = assert!(
+        exp2_bits(B, S, FRAC) <= 32,
+        "exp2 result has too many bits for a u32"
+    );
+}
+
+/// `log2(raw * 2^-shift)`, with `frac` fraction bits, rounded toward negative
+/// infinity, for `raw > 0`.
+fn log2_raw(raw: u128, shift: i32, frac: u32) -> i64 {
+    let n = u128::BITS - 1 - raw.leading_zeros();
+    // the mantissa, in [1, 2)
+    let mut m = if n <= WORK {
+        raw << (WORK - n)
+    } else {
+        raw >> (n - WORK)
+    };
+    // squaring the mantissa doubles its logarithm, so each square gives one more bit
+    let mut bits = 0;
+    for _ in 0..frac {
+        m = (m * m) >> WORK;
+        bits <<= 1;
+        if m >> (WORK + 1) != 0 {
+            m >>= 1;
+            bits |= 1;
+        }
+    }
+    ((n as i64 - shift as i64) << frac) | bits
+}
+
+/// `2^(raw * 2^-shift)`, with `frac` fraction bits, rounded to nearest, for a
+/// result which fits in `u32`.
+fn exp2_raw(raw: i128, shift: i32, frac: u32) -> u32 {
+    // the value, with WORK fraction bits, rounded toward negative infinity
+    let v = if shift <= WORK as i32 {
+        raw << (WORK as i32 - shift)
+    } else {
+        raw >> (shift - WORK as i32).min(127)
+    };
+    let (int, fraction) = (v >> WORK, v & ((1 << WORK) - 1));
+    let mut m = 1u128 << WORK;
+    for (k, factor) in EXP2_FRACTIONS.iter().enumerate() {
+        if fraction >> (WORK as usize - 1 - k) & 1 != 0 {
+            m = (m * factor) >> WORK;
+        }
+    }
+    let k = int + frac as i128 - WORK as i128;
+    if k >= 0 {
+        (m << k) as u32
+    } else if k > -128 {
+        let k = k.unsigned_abs() as u32;
+        ((m + (1 << (k - 1))) >> k) as u32
+    } else {
+        0
+    }
+}
+
+macro_rules! fp_impl {
+    ($Uname:ident, $Iname:ident) => {
+        impl<const B: u32, const S: i32> $Uname<B, S> {
+            /// Return the base-2 logarithm, with `FRAC` fraction bits, or
+            /// `RangeError::TooSmall` for zero.  The result rounds toward negative
+            /// infinity, and is within one LSB of the true value (and exact for
+            /// powers of two).  Compilation will fail if the result does not fit
+            /// in an `i32`.
+            pub fn log2<const FRAC: u32>(
+                self,
+            ) -> Result<I32<{ log2_bits(B, S, FRAC) }, { FRAC as i32 }>, RangeError> {
+                let () = Log2::<B, S, FRAC>::CHECK;
+                match self.raw() as u128 {
+                    0 => Err(RangeError::TooSmall),
+                    raw => Ok(unsafe { I32::new_unchecked(log2_raw(raw, S, FRAC) as i32) }),
+                }
+            }
+        }
+        impl<const B: u32, const S: i32> $Iname<B, S> {
+            /// Return two to the power of this number, with `FRAC` fraction bits.
+            /// The result is rounded to nearest, and is within one LSB of the true
+            /// value (and exact for integers).  Compilation will fail if the
+            /// result does not fit in a `u32`.
+            pub fn exp2<const FRAC: u32>(self) -> U32<{ exp2_bits(B, S, FRAC) }, { FRAC as i32 }> {
+                let () = Exp2::<B, S, FRAC>::CHECK;
+                let raw = exp2_raw(self.raw() as i128, S, FRAC);
+                let max = U32::<{ exp2_bits(B, S, FRAC) }, { FRAC as i32 }>::MAX.raw();
+                unsafe { U32::new_unchecked(raw.min(max)) }
+            }
+        }
+    };
+}
+
+fp_impl!(U8, I8);
+fp_impl!(U16, I16);
+fp_impl!(U32, I32);
+fp_impl!(U64, I64);
+fp_impl!(U128, I128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, Isize);
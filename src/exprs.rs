@@ -0,0 +1,278 @@
+use core::marker::PhantomData;
+
+use crate::Num;
+
+/// Rounding policy for [`rescale`](Num::rescale), [`fp_exprs!`](crate::fp_exprs) and
+/// [`from_ratio`](crate::from_ratio), applied when an exact intermediate result is
+/// shifted down to the output type.
+pub trait Rounding {
+    #[doc(hidden)]
+    const MODE: RoundingMode;
+}
+
+/// The rounding of a [`Rounding`] policy.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoundingMode {
+    Floor,
+    Ceil,
+    TowardZero,
+    AwayFromZero,
+    Nearest,
+    NearestEven,
+}
+
+impl RoundingMode {
+    /// Whether rounding may increase the value above the largest value of a
+    /// result truncated toward negative infinity, i.e. may carry into one more
+    /// integer bit.
+    pub const fn carries(self) -> bool {
+        matches!(
+            self,
+            RoundingMode::Ceil
+                | RoundingMode::AwayFromZero
+                | RoundingMode::Nearest
+                | RoundingMode::NearestEven
+        )
+    }
+}
+
+/// Round toward negative infinity, as `raw_shr` does.
+pub struct Floor;
+
+/// Round toward positive infinity.  This may carry into one more integer bit
+/// than [`Floor`].
+pub struct Ceil;
+
+/// Round toward zero, as integer division does.
+pub struct TowardZero;
+
+/// Round away from zero.  This may carry into one more integer bit than
+/// [`Floor`].
+pub struct AwayFromZero;
+
+/// Round to nearest, with ties away from zero.  This may carry into one more
+/// integer bit than [`Floor`].
+pub struct Nearest;
+
+/// Round to nearest, with ties to even (as IEEE 754 arithmetic does by default),
+/// which has no bias on average.  This may carry into one more integer bit than
+/// [`Floor`].
+pub struct NearestEven;
+
+impl Rounding for Floor {
+    const MODE: RoundingMode = RoundingMode::Floor;
+}
+
+impl Rounding for Ceil {
+    const MODE: RoundingMode = RoundingMode::Ceil;
+}
+
+impl Rounding for TowardZero {
+    const MODE: RoundingMode = RoundingMode::TowardZero;
+}
+
+impl Rounding for AwayFromZero {
+    const MODE: RoundingMode = RoundingMode::AwayFromZero;
+}
+
+impl Rounding for Nearest {
+    const MODE: RoundingMode = RoundingMode::Nearest;
+}
+
+impl Rounding for NearestEven {
+    const MODE: RoundingMode = RoundingMode::NearestEven;
+}
+
+/// Whether to round the magnitude `q` of a quotient up (to `q + 1`) under `mode`,
+/// given the sign of the quotient, whether the remainder is at least half an LSB
+/// (`half`), and whether any of it is below half an LSB (`sticky`).
+pub(crate) const fn round_up(
+    mode: RoundingMode,
+    negative: bool,
+    q: u128,
+    half: bool,
+    sticky: bool,
+) -> bool {
+    match mode {
+        RoundingMode::Floor => negative && (half || sticky),
+        RoundingMode::Ceil => !negative && (half || sticky),
+        RoundingMode::TowardZero => false,
+        RoundingMode::AwayFromZero => half || sticky,
+        RoundingMode::Nearest => half,
+        RoundingMode::NearestEven => half && (sticky || q & 1 == 1),
+    }
+}
+
+/// `x / 2^k`, rounded under `mode`.
+pub(crate) fn shr_rounded(x: i128, k: u32, mode: RoundingMode) -> i128 {
+    if k == 0 {
+        return x;
+    }
+    let (negative, m) = (x < 0, x.unsigned_abs());
+    // m <= 2^127, so for k >= 128 the quotient is zero
+    let (q, half, sticky) = match k {
+        1..=127 => (
+            m >> k,
+            (m >> (k - 1)) & 1 == 1,
+            m & ((1 << (k - 1)) - 1) != 0,
+        ),
+        128 => (0, m >> 127 == 1, m & (u128::MAX >> 1) != 0),
+        _ => (0, false, m != 0),
+    };
+    let m = q + round_up(mode, negative, q, half, sticky) as u128;
+    // k >= 1, so m <= 2^126
+    if negative {
+        -(m as i128)
+    } else {
+        m as i128
+    }
+}
+
+/// Compile-time checks for converting `T` to `Out` with rounding `R`.
+struct Fit<T, Out, R>(PhantomData<(T, Out, R)>);
+
+impl<T: Num, Out: Num, R: Rounding> Fit<T, Out, R> {
+    /// Number of bits the result can need: the bits of `T`, less those shifted
+    /// out (or plus those shifted in), plus one for a rounding carry and one for a
+    /// sign bit.
+    const BITS: i32 = {
+        let k = T::SHIFT - Out::SHIFT;
+        let bits = T::BITS as i32 - k;
+        let bits = if bits < 0 { 0 } else { bits };
+        bits + (R::MODE.carries() && k > 0) as i32 + (!T::SIGNED && Out::SIGNED) as i32
+    };
+    const SHIFTS: () = assert!(
+        T::SHIFT - Out::SHIFT < 128 && Out::SHIFT - T::SHIFT + (T::BITS as i32) < 128,
+        "shift between formats is too large"
+    );
+    const CHECK: () = {
+        let () = Self::SHIFTS;
+        assert!(
+            T::SIGNED == Out::SIGNED || Out::SIGNED,
+            "signed result cannot be stored in an unsigned type without saturation"
+        );
+        assert!(
+            Self::BITS <= Out::BITS as i32,
+            "result may overflow the output type"
+        );
+    };
+}
+
+/// Shift `x` to the shift of `Out` with rounding `R`, as an `i128`.
+fn rescale<T: Num, Out: Num, R: Rounding>(x: T) -> i128
+where
+    T::Raw: Into<i128>,
+{
+    let x: i128 = x.raw().into();
+    let k = T::SHIFT - Out::SHIFT;
+    if k <= 0 {
+        x << -k
+    } else {
+        shr_rounded(x, k as u32, R::MODE)
+    }
+}
+
+/// Convert the exact result of an expression to `Out`.  Used by [`fp_exprs!`].
+#[doc(hidden)]
+pub fn fit<T: Num, Out: Num, R: Rounding>(x: T) -> Out
+where
+    T::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = Fit::<T, Out, R>::CHECK;
+    let y = rescale::<T, Out, R>(x);
+    unsafe { Out::new_unchecked(y.try_into().ok().unwrap()) }
+}
+
+/// Convert the exact result of an expression to `Out`, saturating.  Used by
+/// [`fp_exprs!`].
+#[doc(hidden)]
+pub fn fit_saturating<T: Num, Out: Num, R: Rounding>(x: T) -> Out
+where
+    T::Raw: Into<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    let () = Fit::<T, Out, R>::SHIFTS;
+    let y = rescale::<T, Out, R>(x);
+    let (min, max) = (Out::MIN.raw().into(), Out::MAX.raw().into());
+    unsafe { Out::new_unchecked(y.clamp(min, max).try_into().ok().unwrap()) }
+}
+
+/// Evaluate fixed-point expressions and land each result on a declared type,
+/// inserting the rescaling step automatically.
+///
+/// Each statement `name: Type = expr;` binds `name` (as a `let` would) to the
+/// value of `expr` converted to `Type`.  The expression itself is evaluated with
+/// the ordinary operators, whose output types are exact, so the only bits that
+/// need to be shed are those of the final conversion: the value is shifted to the
+/// `SHIFT` of `Type`, rounded according to the rounding policy.  Compilation
+/// fails if the result could overflow `BITS` of `Type` (counting the possible
+/// carry from rounding), unless the statement is written `name: sat Type = expr;`,
+/// in which case the result is saturated instead.
+///
+/// The rounding policy is [`Floor`] by default, or may be chosen for the whole
+/// block with a leading `round: floor;`, `round: ceil;`, `round: toward_zero;`,
+/// `round: away_from_zero;`, `round: nearest;` (see [`Nearest`]) or
+/// `round: nearest_even;`.
+/// Later statements may use the names bound by earlier ones, which is how a long
+/// expression is split where bits should be shed part of the way through.
+///
+/// ```
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use fp::*;
+/// let a = I32::<10, 8>::from_f64(1.5).unwrap();
+/// let b = I32::<6, 4>::from_f64(-0.75).unwrap();
+/// let c = I32::<16, 12>::from_f64(0.25).unwrap();
+/// fp_exprs! {
+///     round: nearest;
+///     // a * b + c is an I32<17, 12>
+///     y: I32<14, 8> = a * b + c;
+///     z: sat I8<8, 4> = y * y;
+/// }
+/// assert_eq!(y.into_f64(), -0.875);
+/// assert_eq!(z.into_f64(), 0.75);
+/// ```
+///
+/// ```compile_fail
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use fp::*;
+/// let (a, b) = (I16::<10, 8>::new(0).unwrap(), I16::<6, 4>::new(0).unwrap());
+/// // a * b has 16 bits with 12 fractional bits, so 4 integer bits do not fit in 3
+/// fp_exprs! { y: I16<10, 7> = a * b; }
+/// ```
+#[macro_export]
+macro_rules! fp_exprs {
+    (round: floor; $($rest:tt)*) => {
+        $crate::fp_exprs!(@ $crate::Floor; $($rest)*);
+    };
+    (round: ceil; $($rest:tt)*) => {
+        $crate::fp_exprs!(@ $crate::Ceil; $($rest)*);
+    };
+    (round: toward_zero; $($rest:tt)*) => {
+        $crate::fp_exprs!(@ $crate::TowardZero; $($rest)*);
+    };
+    (round: away_from_zero; $($rest:tt)*) => {
+        $crate::fp_exprs!(@ $crate::AwayFromZero; $($rest)*);
+    };
+    (round: nearest; $($rest:tt)*) => {
+        $crate::fp_exprs!(@ $crate::Nearest; $($rest)*);
+    };
+    (round: nearest_even; $($rest:tt)*) => {
+        $crate::fp_exprs!(@ $crate::NearestEven; $($rest)*);
+    };
+    (@ $R:ty;) => {};
+    (@ $R:ty; $name:ident : sat $T:ty = $e:expr; $($rest:tt)*) => {
+        let $name: $T = $crate::fit_saturating::<_, $T, $R>($e);
+        $crate::fp_exprs!(@ $R; $($rest)*);
+    };
+    (@ $R:ty; $name:ident : $T:ty = $e:expr; $($rest:tt)*) => {
+        let $name: $T = $crate::fit::<_, $T, $R>($e);
+        $crate::fp_exprs!(@ $R; $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::fp_exprs!(@ $crate::Floor; $($rest)*);
+    };
+}
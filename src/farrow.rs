@@ -0,0 +1,74 @@
+use crate::{Num, U16};
+
+/// Fractional-delay interpolator, evaluating a cubic Lagrange polynomial
+/// through the four most recent input samples in Farrow form, so that the delay
+/// can change on every sample (e.g. under the control of a timing-recovery loop).
+///
+/// The total delay is `1 + mu` samples, for `mu` from 0 (inclusive) to 1
+/// (exclusive): that is, the output is interpolated between the previous input
+/// sample (at `mu == 0`, which is reproduced exactly) and the one before it.
+///
+/// Intermediate formats: samples are widened to `i64`.  The Farrow coefficients
+/// are formed exactly at six times their true value (so that they are
+/// integers), and each stage of the Horner evaluation in `mu` (in Q0.16) is
+/// truncated back to the LSB of `F`, six times over.  The final division by six is
+/// rounded to nearest, and since cubic interpolation can overshoot, the output is
+/// saturated to the range of `F`.
+#[derive(Clone, Debug)]
+pub struct FarrowDelay<F: Num> {
+    /// The four most recent inputs, oldest first.
+    history: [i64; 4],
+    _sample: core::marker::PhantomData<F>,
+}
+
+impl<F: Num> Default for FarrowDelay<F>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num> FarrowDelay<F>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+{
+    const CHECK: () = assert!(
+        F::BITS <= 32,
+        "samples wider than 32 bits are not supported"
+    );
+    /// Create an interpolator with all-zero history.
+    pub fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            history: [0; 4],
+            _sample: core::marker::PhantomData,
+        }
+    }
+    /// Feed one input sample.
+    pub fn push(&mut self, x: F) {
+        self.history.rotate_left(1);
+        self.history[3] = x.raw().into();
+    }
+    /// Return the input delayed by `1 + mu` samples, relative to the most
+    /// recent input.
+    pub fn interpolate(&self, mu: U16<16, 16>) -> F {
+        // s[t] is the sample delayed by 1 + t samples, for t = -1, 0, 1, 2.
+        let [s2, s1, s0, sm1] = self.history;
+        let c1 = -2 * sm1 - 3 * s0 + 6 * s1 - s2;
+        let c2 = 3 * (sm1 + s1) - 6 * s0;
+        let c3 = 3 * (s0 - s1) + s2 - sm1;
+        let mu = mu.raw() as i64;
+        let t = c2 + ((c3 * mu) >> 16);
+        let t = c1 + ((t * mu) >> 16);
+        let y = s0 + (t * mu + (3 << 16)).div_euclid(6 << 16);
+        let (min, max) = (F::MIN.raw().into(), F::MAX.raw().into());
+        unsafe { F::new_unchecked(y.clamp(min, max).try_into().ok().unwrap()) }
+    }
+    /// Feed one input sample, and return the input delayed by `1 + mu` samples.
+    pub fn process(&mut self, x: F, mu: U16<16, 16>) -> F {
+        self.push(x);
+        self.interpolate(mu)
+    }
+}
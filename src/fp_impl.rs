@@ -1,124 +1,247 @@
 use crate::*;
 
-// Because Rust does not provide suitable traits over the integer types,
-// we have to use a macro for the impls instead of writing one generic impl.
-macro_rules! fp_impl {
-    ($Name:ident, $T:ty) => {
-        /// Every integer is also a fixed-point number, considered to have
-        /// the maximum number of bits and zero shift.
-        impl Num for $T {
-            type Raw = $T;
-            type Output<const B: u32, const S: i32> = $Name<B, S>;
-            const BITS: u32 = <$T>::BITS;
-            const SHIFT: i32 = 0;
-            const MIN: $T = <$T>::MIN;
-            const MAX: $T = <$T>::MAX;
-            #[allow(unused_comparisons)]
-            const SIGNED: bool = <$T>::MIN < 0;
-            unsafe fn new_unchecked(val: $T) -> Self {
-                val
-            }
-            unsafe fn from_f32_unchecked(val: f32) -> Self {
-                val as $T
-            }
-            unsafe fn from_f64_unchecked(val: f64) -> Self {
-                val as $T
-            }
-            fn raw(self) -> $T {
-                self
-            }
-            fn into_f32(self) -> f32 {
-                self as f32
-            }
-            fn into_f64(self) -> f64 {
-                self as f64
-            }
-        }
+/// Exactly `2^n` as an `f32` (or zero or infinity if out of range).  Unlike `powi`,
+/// whose precision is unspecified and may vary between targets, this is exact.
+#[doc(hidden)]
+pub const fn pow2_f32(n: i32) -> f32 {
+    match n {
+        128.. => f32::INFINITY,
+        -126..=127 => f32::from_bits(((n + 127) as u32) << 23),
+        -149..=-127 => f32::from_bits(1 << (n + 149)),
+        _ => 0.,
+    }
+}
+
+/// Exactly `2^n` as an `f64` (or zero or infinity if out of range).  Unlike `powi`,
+/// whose precision is unspecified and may vary between targets, this is exact.
+#[doc(hidden)]
+pub const fn pow2_f64(n: i32) -> f64 {
+    match n {
+        1024.. => f64::INFINITY,
+        -1022..=1023 => f64::from_bits(((n + 1023) as u64) << 52),
+        -1074..=-1023 => f64::from_bits(1 << (n + 1074)),
+        _ => 0.,
+    }
+}
+
+/// An integer type which can be the raw type of a fixed-point number.
+///
+/// The crate implements this for the primitive integers.  A wider integer (e.g. a
+/// 256-bit integer from another crate) can implement it too, along with
+/// [`RawBits`] for every `BITS`, and then [`fp_struct!`](crate::fp_struct) defines
+/// a fixed-point type backed by it, which implements [`Num`].  (The arithmetic
+/// operators are implemented per raw type, so such a type must implement its own.)
+pub trait RawInt:
+    Copy + Eq + Ord + core::fmt::Debug + Shl<u32, Output = Self> + Shr<u32, Output = Self>
+{
+    /// The fixed-point type with this raw type.
+    type Fp<const B: u32, const S: i32>: Num<Raw = Self>;
+    /// The width of this type, in bits.
+    const BITS: u32;
+    /// The smallest value of this type.
+    const MIN: Self;
+    /// The largest value of this type.
+    const MAX: Self;
+    /// Whether this type is signed.
+    const SIGNED: bool;
+    /// Convert from `f32`, as `as` does: rounding toward zero, and saturating.
+    fn from_f32_lossy(val: f32) -> Self;
+    /// Convert from `f64`, as `as` does: rounding toward zero, and saturating.
+    fn from_f64_lossy(val: f64) -> Self;
+    /// Convert to `f32`, as `as` does: rounding to nearest.
+    fn into_f32_lossy(self) -> f32;
+    /// Convert to `f64`, as `as` does: rounding to nearest.
+    fn into_f64_lossy(self) -> f64;
+}
+
+/// The range of a [`RawInt`] when only its `BITS` least-significant bits may vary
+/// (and the rest are zero, or copies of the sign bit).  These are the `MIN` and
+/// `MAX` of a fixed-point type with `BITS` bits, which must be constants.
+pub trait RawBits<const BITS: u32>: RawInt {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+/// Every integer is also a fixed-point number, considered to have
+/// the maximum number of bits and zero shift.
+impl<T: RawInt> Num for T {
+    type Raw = T;
+    type Output<const B: u32, const S: i32> = T::Fp<B, S>;
+    const BITS: u32 = <T as RawInt>::BITS;
+    const SHIFT: i32 = 0;
+    const MIN: T = <T as RawInt>::MIN;
+    const MAX: T = <T as RawInt>::MAX;
+    const SIGNED: bool = <T as RawInt>::SIGNED;
+    unsafe fn new_unchecked(val: T) -> Self {
+        val
+    }
+    unsafe fn from_f32_unchecked(val: f32) -> Self {
+        T::from_f32_lossy(val)
+    }
+    unsafe fn from_f64_unchecked(val: f64) -> Self {
+        T::from_f64_lossy(val)
+    }
+    fn raw(self) -> T {
+        self
+    }
+    fn into_f32(self) -> f32 {
+        self.into_f32_lossy()
+    }
+    fn into_f64(self) -> f64 {
+        self.into_f64_lossy()
+    }
+}
 
+/// Define a fixed-point type `$Name<BITS, SHIFT>` (with optional attributes and
+/// visibility) whose raw type is `$T`, which
+/// must implement [`RawInt`] (with `Fp<B, S> = $Name<B, S>`) and [`RawBits`].
+/// The type implements [`Num`], conversion to and from `$T`, and `TryFrom` for
+/// floats, as the fixed-point types of this crate do (which are defined by this
+/// macro too).
+#[macro_export]
+macro_rules! fp_struct {
+    ($(#[$attr:meta])* $vis:vis $Name:ident, $T:ty) => {
+        $(#[$attr])*
         #[repr(transparent)]
         #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
-        /// [`#[repr(transparent)]`](https://doc.rust-lang.org/reference/type-layout.html#the-transparent-representation)
-        /// struct containing
-        #[doc = concat!("[`", stringify!($T), "`]")]
-        /// interpreted as a fixed-point number.
-        ///
-        /// Implements the trait [`fp::Num`](Num) for fixed-point manipulation.
-        pub struct $Name<const BITS: u32, const SHIFT: i32>($T);
-
-        impl<const BITS: u32, const SHIFT: i32> Num for $Name<BITS, SHIFT> {
+        $vis struct $Name<const BITS: u32, const SHIFT: i32>($T);
+
+        impl<const BITS: u32, const SHIFT: i32> $crate::Num for $Name<BITS, SHIFT> {
             type Raw = $T;
             type Output<const B: u32, const S: i32> = $Name<B, S>;
             const BITS: u32 = {
-                assert!(BITS <= <$T>::BITS, concat!("too many bits for ", stringify!($T)));
+                assert!(
+                    BITS <= <$T as $crate::RawInt>::BITS,
+                    concat!("too many bits for ", stringify!($T))
+                );
                 BITS
             };
             const SHIFT: i32 = SHIFT;
             const MIN: Self = Self({
-                if Self::BITS == 0 {
-                    0
-                } else {
-                    // n.b. shifting by >= T::BITS is undefined for integer types!
-                    <$T>::MIN >> (<$T>::BITS - Self::BITS)
-                }
+                let _ = <Self as $crate::Num>::BITS;
+                <$T as $crate::RawBits<BITS>>::MIN
             });
             const MAX: Self = Self({
-                if Self::BITS == 0 {
-                    0
-                } else {
-                    // n.b. shifting by >= T::BITS is undefined for integer types!
-                    <$T>::MAX >> (<$T>::BITS - Self::BITS)
-                }
+                let _ = <Self as $crate::Num>::BITS;
+                <$T as $crate::RawBits<BITS>>::MAX
             });
-            const SIGNED: bool = <$T>::SIGNED;
+            const SIGNED: bool = <$T as $crate::RawInt>::SIGNED;
             unsafe fn new_unchecked(val: $T) -> Self {
-                let _ = Self::BITS;  // force the compile-time check that T is wide enough for BITS
+                let _ = <Self as $crate::Num>::BITS; // force the compile-time check that T is wide enough for BITS
                 Self(val)
             }
             /// May cause a divide by zero error if `SHIFT` is extremely small.
             unsafe fn from_f32_unchecked(val: f32) -> Self {
-                unsafe { Self::new_unchecked((val * (2_f32).powi(SHIFT)) as $T) }
+                let raw = <$T as $crate::RawInt>::from_f32_lossy(val * $crate::pow2_f32(SHIFT));
+                unsafe { <Self as $crate::Num>::new_unchecked(raw) }
             }
             /// May cause a divide by zero error if `SHIFT` is extremely small.
             unsafe fn from_f64_unchecked(val: f64) -> Self {
-                unsafe { Self::new_unchecked((val * (2_f64).powi(SHIFT)) as $T) }
+                let raw = <$T as $crate::RawInt>::from_f64_lossy(val * $crate::pow2_f64(SHIFT));
+                unsafe { <Self as $crate::Num>::new_unchecked(raw) }
             }
             fn raw(self) -> $T {
                 self.0
             }
-            /// Panics when the logical value could exceed `f32::MAX`.
+            /// Compilation will fail if the logical value could exceed `f32::MAX`.
             fn into_f32(self) -> f32 {
-                assert!(
-                    BITS as i32 - SHIFT - Self::SIGNED as i32 <= f32::MAX_EXP as i32,
-                    "number could overflow f32"
-                );
-                self.0 as f32 / 2_f32.powi(SHIFT)
+                let () = Self::FITS_F32;
+                <$T as $crate::RawInt>::into_f32_lossy(self.0) * $crate::pow2_f32(-SHIFT)
             }
-            /// Panics when the logical value could exceed `f64::MAX`.
+            /// Compilation will fail if the logical value could exceed `f64::MAX`.
             fn into_f64(self) -> f64 {
-                assert!(
-                    BITS as i32 - SHIFT - Self::SIGNED as i32 <= f64::MAX_EXP as i32,
-                    "number could overflow f64"
-                );
-                self.0 as f64 / 2_f64.powi(SHIFT)
+                let () = Self::FITS_F64;
+                <$T as $crate::RawInt>::into_f64_lossy(self.0) * $crate::pow2_f64(-SHIFT)
             }
         }
 
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            const FITS_F32: () = assert!(
+                BITS as i32 - SHIFT - <Self as $crate::Num>::SIGNED as i32 <= f32::MAX_EXP,
+                "number could overflow f32"
+            );
+            const FITS_F64: () = assert!(
+                BITS as i32 - SHIFT - <Self as $crate::Num>::SIGNED as i32 <= f64::MAX_EXP,
+                "number could overflow f64"
+            );
+        }
+
         #[doc = concat!("`", stringify!($T), "` is the same as `", stringify!($Name), "<", stringify!($T) ,"::BITS, 0>`.")]
-        impl From<$T> for $Name<{ <$T>::BITS }, 0> {
+        impl From<$T> for $Name<{ <$T as $crate::RawInt>::BITS }, 0> {
             fn from(val: $T) -> Self {
-                unsafe { Self::new_unchecked(val) }
+                unsafe { <Self as $crate::Num>::new_unchecked(val) }
             }
         }
 
         #[doc = concat!("`", stringify!($T), "` is the same as `", stringify!($Name), "<", stringify!($T) ,"::BITS, 0>`.")]
-        impl From<$Name<{ <$T>::BITS }, 0>> for $T {
-            fn from(val: $Name<{ <$T>::BITS }, 0>) -> Self {
-                val.raw()
+        impl From<$Name<{ <$T as $crate::RawInt>::BITS }, 0>> for $T {
+            fn from(val: $Name<{ <$T as $crate::RawInt>::BITS }, 0>) -> Self {
+                $crate::Num::raw(val)
+            }
+        }
+
+        /// Same as [`Num::from_f32`]($crate::Num::from_f32).
+        impl<const BITS: u32, const SHIFT: i32> TryFrom<f32> for $Name<BITS, SHIFT> {
+            type Error = $crate::RangeError;
+            fn try_from(val: f32) -> Result<Self, $crate::RangeError> {
+                <Self as $crate::Num>::from_f32(val)
+            }
+        }
+
+        /// Same as [`Num::from_f64`]($crate::Num::from_f64).
+        impl<const BITS: u32, const SHIFT: i32> TryFrom<f64> for $Name<BITS, SHIFT> {
+            type Error = $crate::RangeError;
+            fn try_from(val: f64) -> Result<Self, $crate::RangeError> {
+                <Self as $crate::Num>::from_f64(val)
             }
         }
     };
 }
 
+// Because Rust does not provide suitable traits over the integer types,
+// we have to use a macro for the impls instead of writing one generic impl.
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        impl RawInt for $T {
+            type Fp<const B: u32, const S: i32> = $Name<B, S>;
+            const BITS: u32 = <$T>::BITS;
+            const MIN: $T = <$T>::MIN;
+            const MAX: $T = <$T>::MAX;
+            #[allow(unused_comparisons)]
+            const SIGNED: bool = <$T>::MIN < 0;
+            fn from_f32_lossy(val: f32) -> Self {
+                val as $T
+            }
+            fn from_f64_lossy(val: f64) -> Self {
+                val as $T
+            }
+            fn into_f32_lossy(self) -> f32 {
+                self as f32
+            }
+            fn into_f64_lossy(self) -> f64 {
+                self as f64
+            }
+        }
+
+        impl<const BITS: u32> RawBits<BITS> for $T {
+            // n.b. shifting by >= T::BITS is undefined for integer types!
+            const MIN: $T = if BITS == 0 { 0 } else { <$T>::MIN >> (<$T>::BITS - BITS) };
+            const MAX: $T = if BITS == 0 { 0 } else { <$T>::MAX >> (<$T>::BITS - BITS) };
+        }
+
+        fp_struct!(
+            /// [`#[repr(transparent)]`](https://doc.rust-lang.org/reference/type-layout.html#the-transparent-representation)
+            /// struct containing
+            #[doc = concat!("[`", stringify!($T), "`]")]
+            /// interpreted as a fixed-point number.
+            ///
+            /// Implements the trait [`fp::Num`](Num) for fixed-point manipulation.
+            pub $Name,
+            $T
+        );
+    };
+}
+
 fp_impl!(I8, i8);
 fp_impl!(U8, u8);
 fp_impl!(I16, i16);
@@ -129,11 +252,58 @@ fp_impl!(I64, i64);
 fp_impl!(U64, u64);
 fp_impl!(I128, i128);
 fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
 fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
 fp_impl!(Usize, usize);
 
+/// Conversion of an unsigned number to the signed type with one more bit, for code
+/// which is generic over `Num`.  (Each unsigned type also has an inherent
+/// `into_signed`, which this calls.)
+pub trait IntoSigned: Num {
+    type Signed: Num;
+    /// Convert to the signed type with one more bit.  The value is unchanged.
+    fn into_signed(self) -> Self::Signed;
+}
+
+/// Conversion of a signed number to the unsigned type with one fewer bit, for code
+/// which is generic over `Num`, as [`IntoSigned`] does.
+pub trait IntoUnsigned: Num {
+    type Unsigned: Num;
+    /// Convert to the unsigned type with one fewer bit, or return `None` if `self`
+    /// is negative.
+    fn into_unsigned(self) -> Option<Self::Unsigned>;
+    /// Convert to the unsigned type with one fewer bit.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `self` is not negative.
+    unsafe fn into_unsigned_unchecked(self) -> Self::Unsigned;
+}
+
 macro_rules! fp_signed_unsigned_impl {
     ($Uname:ident, $Iname:ident) => {
+        impl<const B: u32, const S: i32> IntoSigned for $Uname<B, S>
+        where
+            [(); (B + 1) as usize]:,
+        {
+            type Signed = $Iname<{ B + 1 }, S>;
+            fn into_signed(self) -> Self::Signed {
+                <$Uname<B, S>>::into_signed(self)
+            }
+        }
+        impl<const B: u32, const S: i32> IntoUnsigned for $Iname<B, S>
+        where
+            [(); (B - 1) as usize]:,
+        {
+            type Unsigned = $Uname<{ B - 1 }, S>;
+            fn into_unsigned(self) -> Option<Self::Unsigned> {
+                <$Iname<B, S>>::into_unsigned(self)
+            }
+            unsafe fn into_unsigned_unchecked(self) -> Self::Unsigned {
+                unsafe { <$Iname<B, S>>::into_unsigned_unchecked(self) }
+            }
+        }
         impl<const B: u32, const S: i32> $Uname<B, S> {
             pub fn into_signed(self) -> $Iname<{ B + 1 }, S>
             where
@@ -143,6 +313,11 @@ macro_rules! fp_signed_unsigned_impl {
             }
         }
         impl<const B: u32, const S: i32> $Iname<B, S> {
+            /// Convert to the unsigned type with one fewer bit.
+            ///
+            /// # Safety
+            ///
+            /// The caller must ensure that `self` is not negative.
             pub unsafe fn into_unsigned_unchecked(self) -> $Uname<{ B - 1 }, S>
             where
                 [(); (B - 1) as usize]:,
@@ -159,6 +334,12 @@ macro_rules! fp_signed_unsigned_impl {
                     None
                 }
             }
+            /// Return the absolute value, as the unsigned type with the same number of
+            /// bits.  Like `Neg`, this cannot overflow: `-MIN` is one more than `MAX`,
+            /// but it fits in `B` unsigned bits.
+            pub fn abs(self) -> $Uname<B, S> {
+                unsafe { $Uname::new_unchecked(self.raw().unsigned_abs()) }
+            }
         }
     };
 }
@@ -168,4 +349,5 @@ fp_signed_unsigned_impl!(U16, I16);
 fp_signed_unsigned_impl!(U32, I32);
 fp_signed_unsigned_impl!(U64, I64);
 fp_signed_unsigned_impl!(U128, I128);
+#[cfg(not(feature = "deterministic"))]
 fp_signed_unsigned_impl!(Usize, Isize);
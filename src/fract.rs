@@ -0,0 +1,84 @@
+use crate::{rescale_bits, Floor, Num, Rounding, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
+#[cfg(not(feature = "deterministic"))]
+use crate::{Isize, Usize};
+
+/// Returns the bit width of the return type from `fract`: the number of fraction
+/// bits, or zero for a number with no fraction bits.
+pub const fn fract_bits(shift: i32) -> u32 {
+    if shift > 0 {
+        shift as u32
+    } else {
+        0
+    }
+}
+
+struct Split<F, const INT: u32, const FRACT: u32>(core::marker::PhantomData<F>);
+
+impl<F: Num, const INT: u32, const FRACT: u32> Split<F, INT, FRACT> {
+    const FRACT: () = assert!(
+        FRACT <= <F::Raw as Num>::BITS,
+        "fraction has too many bits for its raw type"
+    );
+    const INT: () = assert!(
+        INT <= <F::Raw as Num>::BITS,
+        "integer part has too many bits for its raw type"
+    );
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty, $Uname:ident, $U:ty) => {
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Return the fractional part, `self - self.floor()`, which is never
+            /// negative, as an unsigned number with only fraction bits.
+            pub fn fract(self) -> $Uname<{ fract_bits(S) }, S> {
+                let () = Split::<Self, 0, { fract_bits(S) }>::FRACT;
+                let bits = fract_bits(S);
+                let fract = if bits == 0 {
+                    0
+                } else {
+                    self.raw() as $U & (<$U>::MAX >> (<$U>::BITS - bits))
+                };
+                unsafe { $Uname::new_unchecked(fract) }
+            }
+            /// Return the integer part, as `floor` does, and the fractional part, as
+            /// `fract` does, which recombine exactly: the raw value is the integer
+            /// part shifted left by `S`, plus the fractional part.  (So an
+            /// interpolating table lookup can take its index from the one and its
+            /// weight from the other.)
+            pub fn split_int_frac(
+                self,
+            ) -> (
+                $Name<{ rescale_bits(B, Self::SIGNED, S, Floor::MODE) }, 0>,
+                $Uname<{ fract_bits(S) }, S>,
+            ) {
+                let () = Split::<Self, { rescale_bits(B, Self::SIGNED, S, Floor::MODE) }, 0>::INT;
+                let raw = self.raw();
+                let int = if S <= 0 {
+                    raw << -S
+                } else if S < <$T>::BITS as i32 {
+                    raw >> S
+                } else if self.is_negative() {
+                    !(0 as $T)
+                } else {
+                    0 as $T
+                };
+                (unsafe { $Name::new_unchecked(int) }, self.fract())
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8, U8, u8);
+fp_impl!(U8, u8, U8, u8);
+fp_impl!(I16, i16, U16, u16);
+fp_impl!(U16, u16, U16, u16);
+fp_impl!(I32, i32, U32, u32);
+fp_impl!(U32, u32, U32, u32);
+fp_impl!(I64, i64, U64, u64);
+fp_impl!(U64, u64, U64, u64);
+fp_impl!(I128, i128, U128, u128);
+fp_impl!(U128, u128, U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize, Usize, usize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize, Usize, usize);
@@ -0,0 +1,198 @@
+use crate::{Matrix, Num, RangeError, I32};
+
+/// Requantization multiplier: a positive real scale factor represented as
+/// `multiplier * 2^(shift - 31)`, with `multiplier` in Q31 from 1/2 (inclusive)
+/// to 1 (exclusive), as in TFLite and CMSIS-NN.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Requantize {
+    multiplier: i32,
+    shift: i32,
+}
+
+impl Requantize {
+    /// Create a multiplier from its parts, or return a `RangeError` if
+    /// `multiplier` is less than 1/2 (or negative), or if `shift` is not between
+    /// -62 and 31.
+    pub fn new(multiplier: I32<32, 31>, shift: i32) -> Result<Self, RangeError> {
+        if multiplier.raw() < 1 << 30 || shift < -62 {
+            return Err(RangeError::TooSmall);
+        } else if shift > 31 {
+            return Err(RangeError::TooLarge);
+        }
+        Ok(Self {
+            multiplier: multiplier.raw(),
+            shift,
+        })
+    }
+    /// Compute the multiplier closest to `scale` (typically the input scale times
+    /// the weight scale, divided by the output scale), or return `None` unless
+    /// `scale` is between `2^-63` and `2^31`, or if it is so close to `2^31` that
+    /// it rounds up to that value.
+    pub const fn from_scale(scale: f64) -> Option<Self> {
+        if !(scale >= 1. / (1u64 << 63) as f64 && scale < (1u64 << 31) as f64) {
+            return None;
+        }
+        let (mut fraction, mut shift) = (scale, 0);
+        while fraction >= 1. {
+            fraction /= 2.;
+            shift += 1;
+        }
+        while fraction < 0.5 {
+            fraction *= 2.;
+            shift -= 1;
+        }
+        let mut multiplier = (fraction * (1u64 << 31) as f64 + 0.5) as i64;
+        if multiplier == 1 << 31 {
+            multiplier = 1 << 30;
+            shift += 1;
+            if shift > 31 {
+                return None;
+            }
+        }
+        Some(Self {
+            multiplier: multiplier as i32,
+            shift,
+        })
+    }
+    /// Return the multiplier, in Q31.
+    pub fn multiplier(&self) -> I32<32, 31> {
+        unsafe { I32::new_unchecked(self.multiplier) }
+    }
+    /// Return the power-of-two shift.
+    pub fn shift(&self) -> i32 {
+        self.shift
+    }
+    /// Scale `x`, rounding to nearest with ties toward positive infinity (the
+    /// single-rounding mode of CMSIS-NN).  The product is formed exactly.
+    pub fn apply(&self, x: i64) -> i64 {
+        let product = x as i128 * self.multiplier as i128;
+        let k = 31 - self.shift;
+        let y = if k > 0 {
+            (product + (1 << (k - 1))) >> k
+        } else {
+            product << -k
+        };
+        y.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+/// Quantized matrix multiplication over `i8`, with the semantics of the
+/// TFLite/CMSIS-NN fully connected and convolution kernels: each quantized value
+/// `q` stands for the real value `scale * (q - zero_point)`, and each output is
+///
+/// ```text
+/// out[i][j] = clamp(requantize(acc[i][j]) + out_zero_point, out_min, out_max)
+/// acc[i][j] = bias[j] + sum over k of (a[i][k] - a_zero_point) * (b[k][j] - b_zero_point)
+/// ```
+///
+/// The products `a * b` are accumulated in `i32`, and the zero points are then
+/// corrected for with the row sums of `a` and the column sums of `b`, which is
+/// exact (and cheaper than offsetting every element) as long as the inner dimension
+/// `K` is at most `2^15` (which is checked at compile time).  The bias and
+/// correction terms are added in `i64`, so the accumulator cannot overflow.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuantGemm {
+    pub a_zero_point: i8,
+    pub b_zero_point: i8,
+    pub out_zero_point: i8,
+    pub requantize: Requantize,
+    out_min: i8,
+    out_max: i8,
+}
+
+impl QuantGemm {
+    /// Create a kernel with the given requantization and zero points, and no
+    /// output clamping beyond the range of `i8`.
+    pub fn new(
+        requantize: Requantize,
+        a_zero_point: i8,
+        b_zero_point: i8,
+        out_zero_point: i8,
+    ) -> Self {
+        Self {
+            a_zero_point,
+            b_zero_point,
+            out_zero_point,
+            requantize,
+            out_min: i8::MIN,
+            out_max: i8::MAX,
+        }
+    }
+    /// Clamp the output to `min..=max` (e.g. `out_zero_point..=i8::MAX` for a
+    /// fused ReLU), or return `None` if `min > max`.
+    pub fn with_output_range(self, min: i8, max: i8) -> Option<Self> {
+        if min > max {
+            return None;
+        }
+        Some(Self {
+            out_min: min,
+            out_max: max,
+            ..self
+        })
+    }
+    /// Return the lower and upper limits of the output.
+    pub fn output_range(&self) -> (i8, i8) {
+        (self.out_min, self.out_max)
+    }
+    /// Return the accumulator `acc[i][j]` (see above), before requantization.
+    pub fn accumulate<const M: usize, const K: usize, const N: usize>(
+        &self,
+        a: &Matrix<i8, M, K>,
+        b: &Matrix<i8, K, N>,
+        bias: &[i32; N],
+    ) -> Matrix<i64, M, N> {
+        let () = GemmDims::<K>::CHECK;
+        let (za, zb) = (self.a_zero_point as i64, self.b_zero_point as i64);
+        let (a, b) = (a.rows(), b.rows());
+        let mut col_sums = [0i64; N];
+        for row in b {
+            for (sum, &x) in col_sums.iter_mut().zip(row) {
+                *sum += x as i64;
+            }
+        }
+        let mut out = [[0i64; N]; M];
+        for (out, a) in out.iter_mut().zip(a) {
+            let row_sum: i64 = a.iter().map(|&x| x as i64).sum();
+            let mut dot = [0i32; N];
+            for (&x, b) in a.iter().zip(b) {
+                for (dot, &y) in dot.iter_mut().zip(b) {
+                    *dot += x as i32 * y as i32;
+                }
+            }
+            for j in 0..N {
+                out[j] = bias[j] as i64 + dot[j] as i64 - zb * row_sum - za * col_sums[j]
+                    + K as i64 * za * zb;
+            }
+        }
+        Matrix::new(out)
+    }
+    /// Multiply `a` (with `M` rows of `K` activations) by `b` (with `K` rows of
+    /// `N` weights), add `bias`, and requantize to the output format.
+    pub fn run<const M: usize, const K: usize, const N: usize>(
+        &self,
+        a: &Matrix<i8, M, K>,
+        b: &Matrix<i8, K, N>,
+        bias: &[i32; N],
+    ) -> Matrix<i8, M, N> {
+        let acc = self.accumulate(a, b, bias);
+        let (min, max) = (self.out_min as i64, self.out_max as i64);
+        Matrix::new(acc.rows().map(|row| {
+            row.map(|acc| {
+                let y = self
+                    .requantize
+                    .apply(acc)
+                    .saturating_add(self.out_zero_point as i64);
+                y.clamp(min, max) as i8
+            })
+        }))
+    }
+}
+
+struct GemmDims<const K: usize>;
+
+impl<const K: usize> GemmDims<K> {
+    const CHECK: () = assert!(
+        K <= 1 << 15,
+        "inner dimension too large for i32 accumulators"
+    );
+}
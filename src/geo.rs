@@ -0,0 +1,129 @@
+//! Geographic coordinates in the integer format of GNSS receivers and autopilots
+//! (e.g. u-blox and ArduPilot): degrees scaled by `1e7`, in an `i32`, for a
+//! resolution of about 1.1 cm.
+
+use crate::{cos_lut, scale_by_ratio, Num, RangeError, I32, I64, U32};
+
+/// `pi`, as `PI_NUM / PI_DEN`, which is within `1e-16` of `pi` (the nearest
+/// convergent of its continued fraction with a denominator below `1e8`).
+const PI_NUM: i64 = 245_850_922;
+const PI_DEN: u64 = 78_256_779;
+
+/// Units of `1e-7` degrees per degree.
+const SCALE: u64 = 10_000_000;
+
+/// The mean radius of the Earth, in units of `0.1` m.
+const EARTH_RADIUS_DM: u64 = 63_710_088;
+
+/// Latitude, in units of `1e-7` degrees, from `-90` to `90` degrees.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Latitude(I32<31, 0>);
+
+/// Longitude, in units of `1e-7` degrees, from `-180` to `180` degrees.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Longitude(I32<32, 0>);
+
+macro_rules! coordinate_impl {
+    ($Name:ident, $Raw:ty, $limit:expr) => {
+        impl $Name {
+            /// The largest magnitude, in units of `1e-7` degrees.
+            pub const LIMIT: i32 = $limit;
+            /// Create a coordinate from its value in units of `1e-7` degrees, or
+            /// return a `RangeError` if it is out of range.
+            pub fn new(raw: i32) -> Result<Self, RangeError> {
+                if raw < -Self::LIMIT {
+                    Err(RangeError::TooSmall)
+                } else if raw > Self::LIMIT {
+                    Err(RangeError::TooLarge)
+                } else {
+                    // Safety: the limit is within the range of the raw format.
+                    Ok(Self(unsafe { <$Raw>::new_unchecked(raw) }))
+                }
+            }
+            /// Create a coordinate from its value in degrees, rounded to the
+            /// nearest `1e-7` degrees, or return a `RangeError` if it is out of
+            /// range.
+            pub fn from_degrees(degrees: f64) -> Result<Self, RangeError> {
+                let scaled = degrees * SCALE as f64;
+                let rounded = if scaled < 0. {
+                    scaled - 0.5
+                } else {
+                    scaled + 0.5
+                };
+                if rounded > -Self::LIMIT as f64 - 1. && rounded < Self::LIMIT as f64 + 1. {
+                    Self::new(rounded as i32)
+                } else if rounded < 0. {
+                    Err(RangeError::TooSmall)
+                } else {
+                    Err(RangeError::TooLarge)
+                }
+            }
+            /// Return the value in units of `1e-7` degrees.
+            pub fn raw(self) -> i32 {
+                self.0.raw()
+            }
+            /// Return the value in degrees.
+            pub fn degrees(self) -> f64 {
+                self.0.raw() as f64 / SCALE as f64
+            }
+            /// Return the value in radians, rounded to the nearest `2^-29`.
+            pub fn to_radians(self) -> I32<32, 29> {
+                scale_by_ratio::<$Raw, I32<32, 29>, PI_NUM, { PI_DEN * 180 * SCALE }>(self.0)
+            }
+            /// Return the value in turns, modulo one turn (i.e. as a phase for
+            /// [`sin_lut`](crate::sin_lut) and [`cos_lut`]), rounded to the
+            /// nearest `2^-32`.
+            pub fn to_turns(self) -> U32<32, 32> {
+                let turns: I64<33, 32> =
+                    scale_by_ratio::<$Raw, I64<33, 32>, 1, { 360 * SCALE }>(self.0);
+                turns.wrapping_cast()
+            }
+        }
+    };
+}
+
+coordinate_impl!(Latitude, I32<31, 0>, 900_000_000);
+coordinate_impl!(Longitude, I32<32, 0>, 1_800_000_000);
+
+/// A position on the surface of the Earth.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GeoPoint {
+    pub lat: Latitude,
+    pub lon: Longitude,
+}
+
+impl GeoPoint {
+    /// Return the distance to `other` in meters, by the equirectangular
+    /// approximation on a sphere of the Earth's mean radius.
+    ///
+    /// The approximation is accurate to within 0.1% for points up to about 100 km
+    /// apart (away from the poles), which covers the distances between waypoints
+    /// and geofence checks that navigation code needs; the arithmetic adds an
+    /// error of a few LSBs.  The east-west difference takes the short way around
+    /// the antimeridian.  Every intermediate is widened, so no input overflows.
+    pub fn distance(self, other: GeoPoint) -> U32<32, 6> {
+        let dlat = (other.lat.raw() - self.lat.raw()) as i64;
+        let dlon = other.lon.raw() as i64 - self.lon.raw() as i64;
+        let full = 360 * SCALE as i64;
+        let dlon = if dlon > full / 2 {
+            dlon - full
+        } else if dlon < -full / 2 {
+            dlon + full
+        } else {
+            dlon
+        };
+        // the mean latitude is in range, since both latitudes are
+        let mean = (self.lat.raw() as i64 + other.lat.raw() as i64) / 2;
+        let mean = Latitude(unsafe { I32::new_unchecked(mean as i32) });
+        let cos: i64 = cos_lut(mean.to_turns()).raw().into();
+        // `dx` and `dy` are in units of 1e-7 degrees, with 30 more fractional bits
+        let dx = (dlon * cos) as i128;
+        let dy = (dlat as i128) << 30;
+        let d = (dx * dx + dy * dy).unsigned_abs().isqrt();
+        // meters, with 6 fractional bits, are d * 2^(6 - 30) * (pi / 180 / SCALE) * R
+        let num = d * PI_NUM as u128 * EARTH_RADIUS_DM as u128;
+        let den = (PI_DEN as u128 * 180 * SCALE as u128 * 10) << 24;
+        let meters = (num + den / 2) / den;
+        unsafe { U32::new_unchecked(meters.min(u32::MAX as u128) as u32) }
+    }
+}
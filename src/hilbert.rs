@@ -0,0 +1,92 @@
+use core::f64::consts::{PI, TAU};
+
+use crate::{const_math, Complex, Num, I32};
+
+/// FIR Hilbert transformer of odd length `N`, producing the analytic signal of
+/// its input as complex (I/Q) samples: the real part is the input delayed by
+/// `(N - 1) / 2` samples, and the imaginary part is that delayed input shifted
+/// in phase by -90 degrees.  The magnitude of the output estimates the envelope
+/// of the input.
+///
+/// The taps are those of the ideal transformer, `2 / (pi n)` for odd `n` (and
+/// zero for even `n`), tapered by a Blackman window; they are computed and rounded
+/// to Q1.30 at compile time.  Longer filters are accurate over a wider band: the
+/// passband ripple is below 1% from about `3.5 / N` to `0.5 - 3.5 / N` times the
+/// sample rate.  The filter is accumulated exactly in an `i128` and rounded to
+/// nearest; since the gain of the filter slightly exceeds 1 near the band edges,
+/// the imaginary part is saturated to the range of `F`.
+#[derive(Clone, Debug)]
+pub struct Hilbert<F: Num, const N: usize> {
+    /// The `N` most recent inputs, oldest first.
+    history: [i64; N],
+    _sample: core::marker::PhantomData<F>,
+}
+
+impl<F: Num, const N: usize> Default for Hilbert<F, N>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num, const N: usize> Hilbert<F, N>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+{
+    /// The delay of the filter, in samples.
+    pub const DELAY: usize = (N - 1) / 2;
+    /// Filter taps in Q1.30, with `TAPS[DELAY + n]` the tap for `n` samples of lag
+    /// beyond the center.
+    const TAPS: [i32; N] = {
+        assert!(N % 2 == 1 && N >= 3, "length must be odd and at least 3");
+        let mut taps = [0; N];
+        let mut i = 0;
+        while i < N {
+            let n = i as i64 - Self::DELAY as i64;
+            if n % 2 != 0 {
+                // the window is stretched so that its zero ends fall outside the filter
+                let x = (i + 1) as f64 / (N + 1) as f64;
+                let window =
+                    0.42 - 0.5 * const_math::cos(TAU * x) + 0.08 * const_math::cos(2. * TAU * x);
+                let tap = 2. / (PI * n as f64) * window * (1u64 << 30) as f64;
+                taps[i] = (if tap < 0. { tap - 0.5 } else { tap + 0.5 }) as i32;
+            }
+            i += 1;
+        }
+        taps
+    };
+    /// Create a transformer with all-zero history.
+    pub fn new() -> Self {
+        let _ = Self::TAPS; // force the compile-time checks
+        Self {
+            history: [0; N],
+            _sample: core::marker::PhantomData,
+        }
+    }
+    /// Return the quantized filter taps, in order of increasing lag.
+    pub fn taps() -> [I32<32, 30>; N] {
+        Self::TAPS.map(|t| unsafe { I32::new_unchecked(t) })
+    }
+    /// Filter one sample.
+    pub fn process(&mut self, x: F) -> Complex<F> {
+        self.history.rotate_left(1);
+        self.history[N - 1] = x.raw().into();
+        // history[N - 1 - k] has lag k, and is multiplied by TAPS[k].
+        // Only the taps at odd offsets from the center are nonzero.
+        let sum: i128 = ((Self::DELAY + 1) % 2..N)
+            .step_by(2)
+            .map(|k| self.history[N - 1 - k] as i128 * Self::TAPS[k] as i128)
+            .sum();
+        let (min, max): (i64, i64) = (F::MIN.raw().into(), F::MAX.raw().into());
+        let im = ((sum + (1 << 29)) >> 30).clamp(min as i128, max as i128) as i64;
+        let re = self.history[N - 1 - Self::DELAY];
+        unsafe {
+            Complex::new(
+                F::new_unchecked(re.try_into().ok().unwrap()),
+                F::new_unchecked(im.try_into().ok().unwrap()),
+            )
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{Num, U32};
+
+/// `floor(t * 2^k / h)`, for `t <= h < 2^49` and `k < 128`, without overflow.
+fn scale_ratio(t: u128, h: u128, k: u32) -> u128 {
+    // shift by at most 79 bits at a time, so that neither t nor a remainder
+    // (both less than 2^49) overflows
+    let s = k.min(79);
+    let (q, r) = ((t << s) / h, (t << s) % h);
+    if s == k {
+        q
+    } else {
+        (q << (k - s)) + (r << (k - s)) / h
+    }
+}
+
+/// Histogram of fixed-point values, with `BINS` equal-width bins spanning the
+/// entire range of `F` (from `F::MIN` to `F::MAX`).  `BINS` must be a power of
+/// two no greater than `2^F::BITS`, so that the bin edges fall exactly on
+/// representable values: each bin covers `2^(F::BITS - log2(BINS))` raw values,
+/// and the bin of a value is given by its `log2(BINS)` most-significant bits.
+#[derive(Clone, Debug)]
+pub struct Histogram<F: Num, const BINS: usize> {
+    counts: [u32; BINS],
+    total: u64,
+    _value: core::marker::PhantomData<F>,
+}
+
+impl<F: Num, const BINS: usize> Default for Histogram<F, BINS>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num, const BINS: usize> Histogram<F, BINS>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// Number of raw bits which select a value within a bin.
+    const BIN_SHIFT: u32 = {
+        assert!(
+            BINS.is_power_of_two(),
+            "number of bins must be a power of two"
+        );
+        assert!(BINS.trailing_zeros() <= F::BITS, "more bins than values");
+        assert!(F::BITS < i128::BITS, "too many bits");
+        F::BITS - BINS.trailing_zeros()
+    };
+    /// Create an empty histogram.
+    pub fn new() -> Self {
+        let _ = Self::BIN_SHIFT;
+        Self {
+            counts: [0; BINS],
+            total: 0,
+            _value: core::marker::PhantomData,
+        }
+    }
+    /// Return the index of the bin containing `x`.
+    pub fn bin(x: F) -> usize {
+        ((x.raw().into() - F::MIN.raw().into()) >> Self::BIN_SHIFT) as usize
+    }
+    /// Return the smallest and largest values which fall in bin `i`, or `None`
+    /// if there is no such bin.
+    pub fn bin_range(i: usize) -> Option<(F, F)> {
+        if i >= BINS {
+            return None;
+        }
+        let lo = F::MIN.raw().into() + ((i as i128) << Self::BIN_SHIFT);
+        let hi = lo + ((1u128 << Self::BIN_SHIFT) - 1) as i128;
+        unsafe {
+            Some((
+                F::new_unchecked(lo.try_into().ok().unwrap()),
+                F::new_unchecked(hi.try_into().ok().unwrap()),
+            ))
+        }
+    }
+    /// Record one occurrence of `x`.  Saturates if a bin's count would
+    /// exceed `u32::MAX`.
+    pub fn add(&mut self, x: F) {
+        let count = &mut self.counts[Self::bin(x)];
+        if *count < u32::MAX {
+            *count += 1;
+            self.total += 1;
+        }
+    }
+    /// Return the number of values recorded in bin `i`, or `None` if there is no
+    /// such bin.
+    pub fn count(&self, i: usize) -> Option<u32> {
+        self.counts.get(i).copied()
+    }
+    /// Return the counts of all bins.
+    pub fn counts(&self) -> &[u32; BINS] {
+        &self.counts
+    }
+    /// Return the total number of values recorded.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+    /// Discard all recorded values.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+    /// Estimate the `q`-quantile of the recorded values (e.g. `q = 0.5` for the
+    /// median), for `q` between 0 and 1.  The estimate is interpolated linearly
+    /// within the bin containing the quantile, so it is exact to within one bin
+    /// width.  Returns `None` if the histogram is empty or `q > 1`.
+    pub fn quantile(&self, q: U32<17, 16>) -> Option<F> {
+        if self.total == 0 || q.raw() > 1 << 16 {
+            return None;
+        }
+        // rank of the quantile, in units of 2^-16 values
+        let target = self.total as u128 * q.raw() as u128;
+        let mut below = 0u128;
+        for (i, &count) in self.counts.iter().enumerate() {
+            let here = (count as u128) << 16;
+            if count > 0 && below + here >= target {
+                let (lo, _) = Self::bin_range(i)?;
+                let offset = scale_ratio(target - below, here, Self::BIN_SHIFT);
+                let max = (1u128 << Self::BIN_SHIFT) - 1;
+                let raw = lo.raw().into() + offset.min(max) as i128;
+                return Some(unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) });
+            }
+            below += here;
+        }
+        None
+    }
+}
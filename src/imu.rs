@@ -0,0 +1,169 @@
+use crate::{Num, I32, U32};
+
+/// Three-dimensional vector of fixed-point numbers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Vector3<F: Num> {
+    pub x: F,
+    pub y: F,
+    pub z: F,
+}
+
+impl<F: Num> Vector3<F> {
+    pub fn new(x: F, y: F, z: F) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// Unit quaternion representing an attitude, with components in Q2.30.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Quaternion {
+    pub w: I32<32, 30>,
+    pub x: I32<32, 30>,
+    pub y: I32<32, 30>,
+    pub z: I32<32, 30>,
+}
+
+impl Quaternion {
+    /// The identity rotation.
+    pub fn identity() -> Self {
+        Self::from_raw([1 << 30, 0, 0, 0])
+    }
+    fn from_raw(q: [i32; 4]) -> Self {
+        // Safety: every i32 is a valid I32<32, 30>.
+        unsafe {
+            Self {
+                w: I32::new_unchecked(q[0]),
+                x: I32::new_unchecked(q[1]),
+                y: I32::new_unchecked(q[2]),
+                z: I32::new_unchecked(q[3]),
+            }
+        }
+    }
+    fn raw(self) -> [i64; 4] {
+        [self.w, self.x, self.y, self.z].map(|c| c.raw() as i64)
+    }
+    /// Return this quaternion scaled to unit length.  Returns the identity
+    /// rotation if all components are zero.
+    pub fn normalize(self) -> Self {
+        match normalize(self.raw()) {
+            Some(q) => Self::from_raw(q.map(|c| c as i32)),
+            None => Self::identity(),
+        }
+    }
+}
+
+/// `1 / sqrt(x)` for `x` in Q30 between 0.25 and 4, by Newton-Raphson iteration.
+/// The result is in Q30 and is accurate to within a few LSBs.
+fn inv_sqrt_q30(x: i64) -> i64 {
+    debug_assert!((1 << 28..1 << 32).contains(&x));
+    // Newton's method converges for any initial guess below sqrt(3 / x).
+    let mut y: i64 = if x < 1 << 30 { 3 << 29 } else { 3 << 28 };
+    for _ in 0..6 {
+        let xy2 = (x * ((y * y) >> 30)) >> 30;
+        y = (y * ((3 << 30) - xy2)) >> 31;
+    }
+    y
+}
+
+/// Scale a vector of `N` components to unit length in Q30, or return `None`
+/// if all components are zero.  The components may be in any common format.
+fn normalize<const N: usize>(v: [i64; N]) -> Option<[i64; N]> {
+    let max = v.iter().map(|c| c.unsigned_abs()).max()?;
+    if max == 0 {
+        return None;
+    }
+    // Scaling does not change the direction, so first bring the largest
+    // component to between 2^29 and 2^30 (keeping the squared length in range).
+    let shift = 34 - max.leading_zeros() as i32;
+    let v = v.map(|c| if shift >= 0 { c >> shift } else { c << -shift });
+    let len2 = v.iter().map(|c| c * c).sum::<i64>() >> 30;
+    let inv = inv_sqrt_q30(len2);
+    Some(v.map(|c| (c * inv) >> 30))
+}
+
+/// Multiply Q-format numbers `a` and `b`, shifting the product right by `shift`.
+fn mul(a: i64, b: i64, shift: u32) -> i64 {
+    ((a as i128 * b as i128) >> shift) as i64
+}
+
+/// Mahony complementary filter for attitude estimation from a 3-axis gyroscope
+/// and a 3-axis accelerometer.
+///
+/// Formats:
+/// * gyroscope rates: `I32<32, 16>` in radians per second;
+/// * accelerometer: `I32<32, 16>` in any unit (only the direction is used);
+/// * gains `kp` (proportional, in 1/s) and `ki` (integral, in 1/s^2): `U32<32, 16>`;
+/// * sample period: `U32<32, 32>` in seconds;
+/// * attitude: [`Quaternion`], with Q2.30 components;
+/// * internal state (the integral of the attitude error, in rad/s) and all
+///   intermediate vectors: Q30 in `i64`, with products formed in `i128`.
+///
+/// The accelerometer corrects drift in roll and pitch only; heading is
+/// integrated from the gyroscope alone.
+#[derive(Clone, Debug)]
+pub struct Mahony {
+    kp: i64,
+    ki: i64,
+    dt: i64,
+    q: [i64; 4],
+    integral: [i64; 3],
+}
+
+impl Mahony {
+    /// Create a filter starting at the identity attitude.
+    pub fn new(kp: U32<32, 16>, ki: U32<32, 16>, dt: U32<32, 32>) -> Self {
+        Self {
+            kp: kp.raw() as i64,
+            ki: ki.raw() as i64,
+            dt: dt.raw() as i64,
+            q: Quaternion::identity().raw(),
+            integral: [0; 3],
+        }
+    }
+    /// Return the current attitude estimate, which rotates vectors from the
+    /// sensor frame to the reference frame.
+    pub fn attitude(&self) -> Quaternion {
+        Quaternion::from_raw(self.q.map(|c| c as i32))
+    }
+    /// Set the current attitude estimate.
+    pub fn set_attitude(&mut self, q: Quaternion) {
+        self.q = q.normalize().raw();
+    }
+    /// Update the attitude estimate with one sample from each sensor.
+    pub fn update(
+        &mut self,
+        gyro: Vector3<I32<32, 16>>,
+        accel: Vector3<I32<32, 16>>,
+    ) -> Quaternion {
+        let [q0, q1, q2, q3] = self.q;
+        // gyroscope rates in Q30
+        let mut g = [gyro.x, gyro.y, gyro.z].map(|c| (c.raw() as i64) << 14);
+        if let Some([ax, ay, az]) = normalize([accel.x, accel.y, accel.z].map(|c| c.raw() as i64)) {
+            // direction of gravity predicted by the current attitude
+            let vx = 2 * (mul(q1, q3, 30) - mul(q0, q2, 30));
+            let vy = 2 * (mul(q0, q1, 30) + mul(q2, q3, 30));
+            let vz = mul(q0, q0, 30) - mul(q1, q1, 30) - mul(q2, q2, 30) + mul(q3, q3, 30);
+            // the error is the cross product of measured and predicted gravity
+            let e = [
+                mul(ay, vz, 30) - mul(az, vy, 30),
+                mul(az, vx, 30) - mul(ax, vz, 30),
+                mul(ax, vy, 30) - mul(ay, vx, 30),
+            ];
+            for i in 0..3 {
+                self.integral[i] += mul(mul(self.ki, e[i], 16), self.dt, 32);
+                g[i] += mul(self.kp, e[i], 16) + self.integral[i];
+            }
+        }
+        // half of the rotation angle over this sample
+        let [hx, hy, hz] = g.map(|c| mul(c, self.dt, 33));
+        // q += q * (0, h)
+        let q = [
+            q0 - mul(q1, hx, 30) - mul(q2, hy, 30) - mul(q3, hz, 30),
+            q1 + mul(q0, hx, 30) + mul(q2, hz, 30) - mul(q3, hy, 30),
+            q2 + mul(q0, hy, 30) - mul(q1, hz, 30) + mul(q3, hx, 30),
+            q3 + mul(q0, hz, 30) + mul(q1, hy, 30) - mul(q2, hx, 30),
+        ];
+        self.q = normalize(q).unwrap_or(Quaternion::identity().raw());
+        self.attitude()
+    }
+}
@@ -0,0 +1,388 @@
+use core::ops::{Add, Div, Mul, Neg, Shl, Shr, Sub};
+
+use crate::{
+    add_sub::max, checked::mul_u128, fp_struct, pow2_f32, pow2_f64, Fits, Num, RangeError, RawBits,
+    RawInt,
+};
+
+macro_rules! int256_impl {
+    ($Raw:ident, $Hi:ty, $Name:ident, $signed:expr, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub struct $Raw {
+            // (the high limb comes first, so that the derived order is numeric order)
+            hi: $Hi,
+            lo: u128,
+        }
+
+        impl $Raw {
+            const ZERO: Self = Self { hi: 0, lo: 0 };
+            /// Return the integer `hi * 2^128 + lo`.
+            pub const fn from_limbs(hi: $Hi, lo: u128) -> Self {
+                Self { hi, lo }
+            }
+            /// Return the high and low 128-bit limbs, as `from_limbs` takes them.
+            pub const fn limbs(self) -> ($Hi, u128) {
+                (self.hi, self.lo)
+            }
+            #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+            const fn is_negative(self) -> bool {
+                self.hi < 0
+            }
+            const fn cast_signed(self) -> Int256 {
+                Int256 {
+                    hi: self.hi as i128,
+                    lo: self.lo,
+                }
+            }
+            const fn unsigned_abs(self) -> Uint256 {
+                let m = if self.is_negative() {
+                    self.wrapping_neg()
+                } else {
+                    self
+                };
+                Uint256 {
+                    hi: m.hi as u128,
+                    lo: m.lo,
+                }
+            }
+            const fn from_magnitude(negative: bool, m: Uint256) -> Self {
+                let x = Self {
+                    hi: m.hi as $Hi,
+                    lo: m.lo,
+                };
+                if negative {
+                    x.wrapping_neg()
+                } else {
+                    x
+                }
+            }
+            const fn wrapping_add(self, other: Self) -> Self {
+                let (lo, carry) = self.lo.overflowing_add(other.lo);
+                Self {
+                    hi: self.hi.wrapping_add(other.hi).wrapping_add(carry as $Hi),
+                    lo,
+                }
+            }
+            const fn wrapping_sub(self, other: Self) -> Self {
+                let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+                Self {
+                    hi: self.hi.wrapping_sub(other.hi).wrapping_sub(borrow as $Hi),
+                    lo,
+                }
+            }
+            const fn wrapping_neg(self) -> Self {
+                Self::ZERO.wrapping_sub(self)
+            }
+            fn wrapping_mul(self, other: Self) -> Self {
+                let (hi, lo) = mul_u128(self.lo, other.lo);
+                let cross = (self.lo.wrapping_mul(other.hi as u128))
+                    .wrapping_add((self.hi as u128).wrapping_mul(other.lo));
+                Self {
+                    hi: hi.wrapping_add(cross) as $Hi,
+                    lo,
+                }
+            }
+            /// Divide, rounding toward zero, as integer division does.
+            fn wrapping_div(self, other: Self) -> Self {
+                let q = div_magnitude(self.unsigned_abs(), other.unsigned_abs());
+                Self::from_magnitude(self.is_negative() != other.is_negative(), q)
+            }
+            /// Shift left by `n` bits, for `n < 256`.
+            const fn shl_const(self, n: u32) -> Self {
+                match n {
+                    0 => self,
+                    1..=127 => Self {
+                        hi: (self.hi << n) | (self.lo >> (128 - n)) as $Hi,
+                        lo: self.lo << n,
+                    },
+                    _ => Self {
+                        hi: (self.lo << (n - 128)) as $Hi,
+                        lo: 0,
+                    },
+                }
+            }
+            /// Shift right by `n` bits, for `n < 256`, as an arithmetic shift for a
+            /// signed type.
+            const fn shr_const(self, n: u32) -> Self {
+                match n {
+                    0 => self,
+                    1..=127 => Self {
+                        hi: self.hi >> n,
+                        lo: (self.lo >> n) | ((self.hi as u128) << (128 - n)),
+                    },
+                    _ => Self {
+                        hi: if self.is_negative() { !0 } else { 0 },
+                        lo: (self.hi >> (n - 128)) as u128,
+                    },
+                }
+            }
+        }
+
+        impl Shl<u32> for $Raw {
+            type Output = Self;
+            fn shl(self, n: u32) -> Self {
+                self.shl_const(n)
+            }
+        }
+
+        impl Shr<u32> for $Raw {
+            type Output = Self;
+            fn shr(self, n: u32) -> Self {
+                self.shr_const(n)
+            }
+        }
+
+        impl RawInt for $Raw {
+            type Fp<const B: u32, const S: i32> = $Name<B, S>;
+            const BITS: u32 = 256;
+            const MIN: Self = if $signed {
+                Self {
+                    hi: <$Hi>::MIN,
+                    lo: 0,
+                }
+            } else {
+                Self::ZERO
+            };
+            const MAX: Self = Self {
+                hi: <$Hi>::MAX,
+                lo: u128::MAX,
+            };
+            const SIGNED: bool = $signed;
+            fn from_f32_lossy(val: f32) -> Self {
+                Self::from_f64_lossy(val as f64)
+            }
+            fn from_f64_lossy(val: f64) -> Self {
+                let negative = val < 0.;
+                if val.is_nan() || (negative && !$signed) {
+                    Self::ZERO
+                } else if val.abs() >= pow2_f64(256 - $signed as i32) {
+                    if negative {
+                        <Self as RawInt>::MIN
+                    } else {
+                        <Self as RawInt>::MAX
+                    }
+                } else {
+                    Self::from_magnitude(negative, magnitude_from_f64(val.abs()))
+                }
+            }
+            fn into_f32_lossy(self) -> f32 {
+                let m = magnitude_into_f32(self.unsigned_abs());
+                if self.is_negative() {
+                    -m
+                } else {
+                    m
+                }
+            }
+            fn into_f64_lossy(self) -> f64 {
+                let m = magnitude_into_f64(self.unsigned_abs());
+                if self.is_negative() {
+                    -m
+                } else {
+                    m
+                }
+            }
+        }
+
+        impl<const BITS: u32> RawBits<BITS> for $Raw {
+            const MIN: Self = if BITS == 0 {
+                Self::ZERO
+            } else {
+                <Self as RawInt>::MIN.shr_const(256 - BITS)
+            };
+            const MAX: Self = if BITS == 0 {
+                Self::ZERO
+            } else {
+                <Self as RawInt>::MAX.shr_const(256 - BITS)
+            };
+        }
+    };
+}
+
+int256_impl!(
+    Int256,
+    i128,
+    I256,
+    true,
+    "A 256-bit two's complement integer, as two 128-bit limbs: the raw type of [`I256`]."
+);
+int256_impl!(
+    Uint256,
+    u128,
+    U256,
+    false,
+    "A 256-bit unsigned integer, as two 128-bit limbs: the raw type of [`U256`]."
+);
+
+impl Uint256 {
+    const fn leading_zeros(self) -> u32 {
+        if self.hi == 0 {
+            128 + self.lo.leading_zeros()
+        } else {
+            self.hi.leading_zeros()
+        }
+    }
+}
+
+/// `n / d`, rounded toward zero, by binary long division.  Panics if `d` is zero,
+/// as integer division does.
+fn div_magnitude(n: Uint256, d: Uint256) -> Uint256 {
+    assert!(d != Uint256::ZERO, "attempt to divide by zero");
+    let (mut q, mut r) = (Uint256::ZERO, Uint256::ZERO);
+    for i in (0..256 - n.leading_zeros()).rev() {
+        // r < d before the shift, so the bit shifted out of it (if any) makes it
+        // larger than d, and the difference fits
+        let carry = r.hi >> 127 != 0;
+        r = r.shl_const(1);
+        r.lo |= n.shr_const(i).lo & 1;
+        q = q.shl_const(1);
+        if carry || r >= d {
+            r = r.wrapping_sub(d);
+            q.lo |= 1;
+        }
+    }
+    q
+}
+
+/// `m` as a `Uint256`, rounded toward zero, for `0 <= m < 2^256`.
+fn magnitude_from_f64(m: f64) -> Uint256 {
+    // (both limbs are exact: the high limb has at most 53 significant bits, so the
+    // difference is formed exactly)
+    let hi = (m * pow2_f64(-128)) as u128;
+    let lo = (m - hi as f64 * pow2_f64(128)) as u128;
+    Uint256 { hi, lo }
+}
+
+/// The 128 high bits of `m`, with any nonzero bits below them folded into the
+/// least-significant bit (so that a conversion of them rounds as a conversion of
+/// `m` would), and the number of bits below them.
+fn magnitude_high_bits(m: Uint256) -> (u128, u32) {
+    if m.hi == 0 {
+        return (m.lo, 0);
+    }
+    let k = 128 - m.hi.leading_zeros();
+    let sticky = m.lo << (128 - k) != 0;
+    (m.shr_const(k).lo | sticky as u128, k)
+}
+
+fn magnitude_into_f32(m: Uint256) -> f32 {
+    let (bits, k) = magnitude_high_bits(m);
+    bits as f32 * pow2_f32(k as i32)
+}
+
+fn magnitude_into_f64(m: Uint256) -> f64 {
+    let (bits, k) = magnitude_high_bits(m);
+    bits as f64 * pow2_f64(k as i32)
+}
+
+macro_rules! from_impl {
+    ($Raw:ident, $($T:ty),*) => {
+        $(
+            impl From<$T> for $Raw {
+                fn from(val: $T) -> Self {
+                    #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                    let hi = if val < 0 { !0 } else { 0 };
+                    Self { hi, lo: val as u128 }
+                }
+            }
+
+            impl TryFrom<$Raw> for $T {
+                type Error = RangeError;
+                fn try_from(val: $Raw) -> Result<Self, RangeError> {
+                    if val < <$Raw>::from(<$T>::MIN) {
+                        Err(RangeError::TooSmall)
+                    } else if val > <$Raw>::from(<$T>::MAX) {
+                        Err(RangeError::TooLarge)
+                    } else {
+                        Ok(val.lo as $T)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_impl!(Int256, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+from_impl!(Uint256, u8, u16, u32, u64, u128);
+
+fp_struct!(
+    /// A fixed-point number with a 256-bit raw value, [`Int256`], e.g. for
+    /// products of 128-bit token amounts.  Implements [`Num`], and `Add`, `Sub`,
+    /// `Neg`, `Mul` and `Div` with the output types of the other fixed-point types.
+    pub I256,
+    Int256
+);
+fp_struct!(
+    /// A fixed-point number with a 256-bit unsigned raw value, [`Uint256`], as
+    /// [`I256`] is signed.
+    pub U256,
+    Uint256
+);
+
+// The operators follow those of the other fixed-point types (in `add_sub` and
+// `mul_div`), with the raw arithmetic done on the limbs.
+macro_rules! fp_impl {
+    ($Name:ident) => {
+        impl<const B0: u32, const B1: u32, const S: i32> Add<$Name<B1, S>> for $Name<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            type Output = $Name<{ max(B0, B1) + 1 }, S>;
+            fn add(self, other: $Name<B1, S>) -> Self::Output {
+                let () = Fits::<Self::Output, { max(B0, B1) + 1 }>::SUM;
+                unsafe { Self::Output::new_unchecked(self.raw().wrapping_add(other.raw())) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> Sub<$Name<B1, S>> for $Name<B0, S>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+        {
+            // Subtraction output is always signed, even for unsigned inputs.
+            type Output = I256<{ max(B0, B1) + 1 }, S>;
+            fn sub(self, other: $Name<B1, S>) -> Self::Output {
+                let () = Fits::<Self::Output, { max(B0, B1) + 1 }>::SUM;
+                let raw = self.raw().wrapping_sub(other.raw());
+                unsafe { Self::Output::new_unchecked(raw.cast_signed()) }
+            }
+        }
+        impl<const B: u32, const S: i32> Neg for $Name<B, S>
+        where
+            [(); (B + 1) as usize]:,
+        {
+            // Negation output is always signed, and has one more bit.
+            type Output = I256<{ B + 1 }, S>;
+            fn neg(self) -> Self::Output {
+                let () = Fits::<Self::Output, { B + 1 }>::SUM;
+                unsafe { Self::Output::new_unchecked(self.raw().cast_signed().wrapping_neg()) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Mul<$Name<B1, S1>>
+            for $Name<B0, S0>
+        where
+            [(); (B0 + B1) as usize]:,
+            [(); (S0 + S1) as usize]:,
+        {
+            type Output = $Name<{ B0 + B1 }, { S0 + S1 }>;
+            fn mul(self, other: $Name<B1, S1>) -> Self::Output {
+                #[allow(clippy::suspicious_arithmetic_impl)] // (adding bits, not values)
+                let () = Fits::<Self::Output, { B0 + B1 }>::PRODUCT;
+                unsafe { Self::Output::new_unchecked(self.raw().wrapping_mul(other.raw())) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Div<$Name<B1, S1>>
+            for $Name<B0, S0>
+        where
+            [(); (B0 + Self::SIGNED as u32) as usize]:,
+            [(); (S0 - S1) as usize]:,
+        {
+            // As for the other types, a signed quotient (of MIN / -1) needs one more bit.
+            type Output = $Name<{ B0 + Self::SIGNED as u32 }, { S0 - S1 }>;
+            fn div(self, other: $Name<B1, S1>) -> Self::Output {
+                unsafe { Self::Output::new_unchecked(self.raw().wrapping_div(other.raw())) }
+            }
+        }
+    };
+}
+
+fp_impl!(I256);
+fp_impl!(U256);
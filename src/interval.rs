@@ -0,0 +1,161 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::Num;
+
+/// A closed interval `[lo, hi]` of fixed-point values, which receives the same
+/// operations as the values it bounds.  Intended for simulation: run a signal
+/// chain on intervals (or record the samples seen with `include`) to measure
+/// the range each stage actually reaches, which may be far narrower than the
+/// worst case implied by its type, and then choose a tighter `BITS`.
+///
+/// Each operation returns the smallest interval of the output type which
+/// contains the result of the operation on every pair of values in the
+/// operands, so the bounds are guaranteed but may be pessimistic when the same
+/// value appears twice (e.g. `x - x` is not `[0, 0]`).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Interval<F: Num> {
+    lo: F,
+    hi: F,
+}
+
+impl<F: Num> Interval<F> {
+    /// Create the interval between `a` and `b`, in either order.
+    pub fn new(a: F, b: F) -> Self {
+        Self {
+            lo: a.min(b),
+            hi: a.max(b),
+        }
+    }
+    /// Create the interval containing only `x`.
+    pub fn point(x: F) -> Self {
+        Self { lo: x, hi: x }
+    }
+    /// Create the interval containing every value of `F`.
+    pub fn full() -> Self {
+        Self {
+            lo: F::MIN,
+            hi: F::MAX,
+        }
+    }
+    /// Return the lower bound.
+    pub fn lo(self) -> F {
+        self.lo
+    }
+    /// Return the upper bound.
+    pub fn hi(self) -> F {
+        self.hi
+    }
+    /// Return true if `x` is in the interval.
+    pub fn contains(self, x: F) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+    /// Return true if zero is in the interval.
+    pub fn contains_zero(self) -> bool {
+        !self.lo.is_positive() && !self.hi.is_negative()
+    }
+    /// Return the smallest interval containing both `self` and `other`.
+    pub fn hull(self, other: Self) -> Self {
+        Self {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+    /// Widen the interval, if necessary, to contain `x`.
+    pub fn include(&mut self, x: F) {
+        *self = self.hull(Self::point(x));
+    }
+    /// Return the width of the interval, `hi - lo`, as `f64`.
+    pub fn width_f64(self) -> f64 {
+        self.hi.into_f64() - self.lo.into_f64()
+    }
+}
+
+impl<F: Num> Interval<F>
+where
+    F::Raw: Into<i128>,
+{
+    /// Return the smallest `BITS` which holds every value of the interval at the
+    /// signedness and `SHIFT` of `F`.
+    pub fn bits_needed(self) -> u32 {
+        let bits = |x: F| {
+            let v: i128 = x.raw().into();
+            if v < 0 {
+                i128::BITS + 1 - v.leading_ones()
+            } else if v == 0 {
+                0
+            } else {
+                i128::BITS + F::SIGNED as u32 - v.leading_zeros()
+            }
+        };
+        bits(self.lo).max(bits(self.hi))
+    }
+}
+
+impl<A: Num + Add<B, Output = C>, B: Num, C: Num> Add<Interval<B>> for Interval<A> {
+    type Output = Interval<C>;
+    fn add(self, other: Interval<B>) -> Self::Output {
+        Interval {
+            lo: self.lo + other.lo,
+            hi: self.hi + other.hi,
+        }
+    }
+}
+
+impl<A: Num + Sub<B, Output = C>, B: Num, C: Num> Sub<Interval<B>> for Interval<A> {
+    type Output = Interval<C>;
+    fn sub(self, other: Interval<B>) -> Self::Output {
+        Interval {
+            lo: self.lo - other.hi,
+            hi: self.hi - other.lo,
+        }
+    }
+}
+
+impl<A: Num + Mul<B, Output = C>, B: Num, C: Num> Mul<Interval<B>> for Interval<A> {
+    type Output = Interval<C>;
+    fn mul(self, other: Interval<B>) -> Self::Output {
+        let products = [
+            self.lo * other.lo,
+            self.lo * other.hi,
+            self.hi * other.lo,
+            self.hi * other.hi,
+        ];
+        Interval {
+            lo: products.into_iter().min().unwrap(),
+            hi: products.into_iter().max().unwrap(),
+        }
+    }
+}
+
+impl<A: Num + Div<B, Output = C>, B: Num, C: Num> Div<Interval<B>> for Interval<A> {
+    type Output = Interval<C>;
+    /// Quotient of the intervals.  If the divisor contains zero, the quotient is
+    /// unbounded, and the result is the whole range of `C`.
+    fn div(self, other: Interval<B>) -> Self::Output {
+        if other.contains_zero() {
+            return Interval::full();
+        }
+        // Truncating division is monotonic in each operand while the sign of the
+        // divisor is fixed, so the extremes are at the corners.
+        let quotients = [
+            self.lo / other.lo,
+            self.lo / other.hi,
+            self.hi / other.lo,
+            self.hi / other.hi,
+        ];
+        Interval {
+            lo: quotients.into_iter().min().unwrap(),
+            hi: quotients.into_iter().max().unwrap(),
+        }
+    }
+}
+
+impl<A: Num + Neg<Output = C>, C: Num> Neg for Interval<A> {
+    type Output = Interval<C>;
+    fn neg(self) -> Self::Output {
+        Interval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Num, RangeError};
+
+/// Error returned by a kernel whose slice arguments have different lengths.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LengthMismatch;
+
+/// Error returned by a slice routine which can fail either because its slice
+/// arguments have the wrong lengths, or because a result is out of range.
+#[derive(Debug)]
+pub enum SliceError {
+    LengthMismatch,
+    Range(RangeError),
+}
+
+impl From<LengthMismatch> for SliceError {
+    fn from(_: LengthMismatch) -> Self {
+        SliceError::LengthMismatch
+    }
+}
+
+impl From<RangeError> for SliceError {
+    fn from(e: RangeError) -> Self {
+        SliceError::Range(e)
+    }
+}
+
+/// Compile-time check that a product with `SA + SX` fractional bits can be
+/// truncated to `SHIFT` fractional bits.
+struct ProductShift<const SA: i32, const SX: i32, const SHIFT: i32>;
+
+impl<const SA: i32, const SX: i32, const SHIFT: i32> ProductShift<SA, SX, SHIFT> {
+    const CHECK: () = assert!(
+        SA + SX >= SHIFT,
+        "product has fewer fractional bits than output"
+    );
+}
+
+/// Compile-time check that values of `BITS` bits, scaled by a constant to need
+/// `NEEDED` bits before the shift by `VAL_SHIFT`, fit in `BITS` bits, and that
+/// the shift is narrower than the wide intermediate type of `WIDE` bits.
+struct ScaleFits<const BITS: u32, const VAL_SHIFT: u32, const NEEDED: u32, const WIDE: u32>;
+
+impl<const BITS: u32, const VAL_SHIFT: u32, const NEEDED: u32, const WIDE: u32>
+    ScaleFits<BITS, VAL_SHIFT, NEEDED, WIDE>
+{
+    const CHECK: () = {
+        assert!(
+            NEEDED <= BITS + VAL_SHIFT,
+            "scaled values may not fit in BITS bits"
+        );
+        assert!(
+            VAL_SHIFT < WIDE,
+            "VAL_SHIFT is too large for the intermediate type"
+        );
+    };
+}
+
+// In-place kernels over slices.  The output of every element must have the same
+// type as the input, so the products are formed in a wider raw type `$W` and
+// shifted back down before being stored.
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty, $W:ty) => {
+        use crate::$Name;
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// Multiply every element of `xs` in place by the constant `VAL / 2^VAL_SHIFT`,
+            /// truncating the result (as `raw_shr` does).  Compilation will fail unless
+            /// the scaled values are guaranteed to fit in `BITS` bits, i.e. unless
+            /// `Self::mul_const_bits(VAL) <= BITS + VAL_SHIFT`.
+            pub fn scale_slice_const<const VAL: $T, const VAL_SHIFT: u32>(xs: &mut [Self])
+            where
+                [(); Self::mul_const_bits(VAL) as usize]:,
+            {
+                let () =
+                    ScaleFits::<BITS, VAL_SHIFT, { Self::mul_const_bits(VAL) }, { <$W>::BITS }>::CHECK;
+                for x in xs.iter_mut() {
+                    let scaled = (x.raw() as $W * VAL as $W) >> VAL_SHIFT;
+                    *x = unsafe { Self::new_unchecked(scaled as $T) };
+                }
+            }
+            /// Compute `y[i] = y[i] + a * x[i]` in place for every element, saturating
+            /// each result into `Self`.  The product `a * x[i]` is formed at full
+            /// precision and truncated to `SHIFT` before the addition.
+            ///
+            /// Returns `LengthMismatch` (leaving `y` unchanged) if `y` and `x` have
+            /// different lengths.  Compilation will fail if the product has fewer
+            /// fractional bits than `Self` (i.e. `SA + SX < SHIFT`), or if it does not
+            /// fit in the wide intermediate type.
+            pub fn axpy<const BA: u32, const SA: i32, const BX: u32, const SX: i32>(
+                y: &mut [Self],
+                a: $Name<BA, SA>,
+                x: &[$Name<BX, SX>],
+            ) -> Result<(), LengthMismatch>
+            where
+                [(); (<$W>::BITS - 1 - BA - BX) as usize]:,
+            {
+                let () = ProductShift::<SA, SX, SHIFT>::CHECK;
+                if y.len() != x.len() {
+                    return Err(LengthMismatch);
+                }
+                let shift = (SA + SX - SHIFT) as u32;
+                let (min, max) = (Self::MIN.raw() as $W, Self::MAX.raw() as $W);
+                for (y, x) in y.iter_mut().zip(x) {
+                    let sum = y.raw() as $W + ((a.raw() as $W * x.raw() as $W) >> shift);
+                    *y = unsafe { Self::new_unchecked(sum.clamp(min, max) as $T) };
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8, i16);
+fp_impl!(U8, u8, u16);
+fp_impl!(I16, i16, i32);
+fp_impl!(U16, u16, u32);
+fp_impl!(I32, i32, i64);
+fp_impl!(U32, u32, u64);
+fp_impl!(I64, i64, i128);
+fp_impl!(U64, u64, u128);
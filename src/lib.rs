@@ -32,9 +32,68 @@
 //! It is necessary to use nightly Rust in order to enable the unstable
 //! `generic_const_exprs` feature.  Otherwise it would not be possible to specify
 //! the correct return type from most operations.
+//!
+//! # Rounding and determinism
+//!
+//! Every operation is fully specified, down to the last bit:
+//!
+//! * `Add`, `Sub`, `Neg`, `Mul`, and `mul_const` are exact (the output type is always
+//!   wide enough to hold the result).
+//! * `Div` and `div_const` round toward zero, as integer division does, and `Rem`
+//!   is the matching remainder, with the sign of the dividend.
+//! * `raw_shr` rounds toward negative infinity, as an arithmetic right shift does.
+//! * `rescale` rounds as its [`Rounding`] parameter specifies, and `floor`, `ceil`,
+//!   `round` (with ties away from zero) and `trunc` round as their names say.
+//! * `sqrt` rounds toward zero, as an integer square root does.
+//! * `recip` rounds to nearest, with ties away from zero.
+//! * `from_f32` and `from_f64` round toward zero; `into_f32` and `into_f64` round to
+//!   nearest (ties to even), per IEEE 754.  Scaling by `2^SHIFT` is exact.
+//! * Operations elsewhere in the crate document their own rounding.
+//!
+//! All integer operations, and all float operations on targets with IEEE 754
+//! arithmetic, therefore give identical results on every target, except for
+//! operations on `Isize` and `Usize`, whose width varies between targets.  The
+//! `deterministic` feature removes those two types, and refuses to compile for
+//! targets whose floating-point arithmetic is not IEEE 754 compliant (32-bit x86
+//! without SSE2, whose x87 unit rounds to extended precision).
+//!
+//! # Panics
+//!
+//! No safe function or operator in this crate panics at runtime, with one exception:
+//! `Div` and `Rem` panic on division by zero, as integer division does.
+//!
+//! * Requirements on types (e.g. that an accumulator has the right shift, or that
+//!   a format fits in `f32`) are checked at compile time, so a program which
+//!   violates one does not compile.  E.g. an operator whose result would have more
+//!   bits than its raw type fails to compile, naming the operator:
+//!
+//!   ```compile_fail
+//!   # #![allow(incomplete_features)]
+//!   # #![feature(generic_const_exprs)]
+//!   use fp::*;
+//!   let x = I32::<20, 0>::MAX;
+//!   let y = x * x; // product has too many bits for its raw type
+//!   ```
+//! * Requirements on values (e.g. that a value is in range, or that two slices have
+//!   the same length) are reported by returning `Result` or `Option`.
+//! * The `const fn` design helpers (such as [`BiquadDesign`]'s constructors, and
+//!   the tables and coefficients built by [`fp_table!`] and [`fp_coeffs!`]) reject
+//!   invalid parameters with a panic, and are meant to be evaluated in const
+//!   context, where that panic is a compile error.
+//!
+//! Panics caused by the caller's own `.unwrap()`, and undefined behavior caused by
+//! the caller's misuse of an `unsafe` function, are of course excluded.
 
+#![allow(incomplete_features)]
 #![feature(generic_const_exprs)]
 
+#[cfg(all(
+    feature = "deterministic",
+    target_arch = "x86",
+    not(target_feature = "sse2")
+))]
+compile_error!("the `deterministic` feature requires SSE2 floating point on x86");
+
 use core::ops::{Shl, Shr};
 
 #[derive(Debug)]
@@ -73,7 +132,10 @@ pub trait Num: Clone + Copy + Eq + Ord + PartialEq + PartialOrd + Sized {
     /// Whether this type is signed. (If false, it's unsigned.)
     const SIGNED: bool;
     /// Interpret the provided raw value as a fixed-point number of type `Self`.
-    /// Unsafe: no bounds checking is performed; the caller must ensure that the
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that the
     /// result lies between `Self::MIN` and `Self::MAX`. It is almost always better
     /// to use `.new().unwrap()` instead of this function, so that an out-of-bounds
     /// value panics with a reasonable message instead of propagating undefined
@@ -93,6 +155,23 @@ pub trait Num: Clone + Copy + Eq + Ord + PartialEq + PartialOrd + Sized {
     }
     /// Return the raw value which internally represents this fixed-point number.
     fn raw(self) -> Self::Raw;
+    /// Return whether this number is less than zero (never, for unsigned types).
+    fn is_negative(self) -> bool {
+        self.raw() < raw_zero()
+    }
+    /// Return whether this number is greater than zero.
+    fn is_positive(self) -> bool {
+        self.raw() > raw_zero()
+    }
+    /// Return whether this number is zero.
+    fn is_zero(self) -> bool {
+        self.raw() == raw_zero()
+    }
+    /// Return `-1`, `0` or `1`, as this number is negative, zero, or positive.
+    fn signum(self) -> I8<2, 0> {
+        let sign = self.is_positive() as i8 - self.is_negative() as i8;
+        unsafe { I8::new_unchecked(sign) }
+    }
     /// Return the fixed-point number of type `Self` which has a logical value of `val`,
     /// or return a RangeError if `val` is too small or too large to be represented
     /// by `Self`.
@@ -105,6 +184,12 @@ pub trait Num: Clone + Copy + Eq + Ord + PartialEq + PartialOrd + Sized {
             Ok(unsafe { Self::from_f32_unchecked(val) })
         }
     }
+    /// Return the fixed-point number of type `Self` which has a logical value of `val`.
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that `val` lies
+    /// between `Self::MIN` and `Self::MAX`.
     unsafe fn from_f32_unchecked(val: f32) -> Self;
     /// Return the fixed-point number of type `Self` which has a logical value of `val`,
     /// or return a RangeError if `val` is too small or too large to be represented
@@ -118,29 +203,88 @@ pub trait Num: Clone + Copy + Eq + Ord + PartialEq + PartialOrd + Sized {
             Ok(unsafe { Self::from_f64_unchecked(val) })
         }
     }
+    /// Return the fixed-point number of type `Self` which has a logical value of `val`.
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that `val` lies
+    /// between `Self::MIN` and `Self::MAX`.
     unsafe fn from_f64_unchecked(val: f64) -> Self;
+    /// Return the fixed-point number of type `Self` nearest to `val / 2^S`, i.e. to the
+    /// value of a fixed-point number whose raw value is `val` and whose shift is `S`
+    /// (e.g. a register read with a known format).  Low bits beyond `Self::SHIFT` are
+    /// truncated (rounding toward negative infinity, as `raw_shr` does), and values
+    /// out of range saturate to `Self::MIN` or `Self::MAX`.
+    fn from_i32_saturating<const S: i32>(val: i32) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        saturate_from_i128(val as i128, S)
+    }
+    /// Like [`from_i32_saturating`](Self::from_i32_saturating), for an `i64`.
+    fn from_i64_saturating<const S: i32>(val: i64) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        saturate_from_i128(val as i128, S)
+    }
+    /// Like [`from_i32_saturating`](Self::from_i32_saturating), for an `i128`.
+    fn from_i128_saturating<const S: i32>(val: i128) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        saturate_from_i128(val, S)
+    }
+    /// Like [`from_i32_saturating`](Self::from_i32_saturating), for an `u32`.
+    fn from_u32_saturating<const S: i32>(val: u32) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        saturate_from_i128(val as i128, S)
+    }
+    /// Like [`from_i32_saturating`](Self::from_i32_saturating), for an `u64`.
+    fn from_u64_saturating<const S: i32>(val: u64) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        saturate_from_i128(val as i128, S)
+    }
+    /// Like [`from_i32_saturating`](Self::from_i32_saturating), for an `u128`.
+    fn from_u128_saturating<const S: i32>(val: u128) -> Self
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+    {
+        match i128::try_from(val) {
+            Ok(val) => saturate_from_i128(val, S),
+            // floor(val / 2^k) == floor((val / 2) / 2^(k - 1)), and val / 2 fits
+            Err(_) => saturate_from_i128((val >> 1) as i128, S - 1),
+        }
+    }
     /// Return the logical value of `Self` as `f32`. Truncation is possible.
+    /// Compilation will fail if the logical value could exceed `f32::MAX`.
     fn into_f32(self) -> f32;
     /// Return the logical value of `Self` as `f64`. Truncation is possible.
+    /// Compilation will fail if the logical value could exceed `f64::MAX`.
     fn into_f64(self) -> f64;
     /// Return the fixed-point number of type `Self` which has the same logical value as `val`.
-    /// `F` and `Self` must have the same shift and signedness. `Self` must have at least as
-    /// many bits as `F`.
-    fn from_fp<T: Num, F: Num<Raw = T>>(val: F) -> Self
+    /// `F` and `Self` must have the same shift and signedness, and `Self` must have at least
+    /// as many bits as `F`, or compilation will fail.
+    fn from_fp<T, F>(val: F) -> Self
     where
+        T: Num,
+        F: Num<Raw = T>,
         Self::Raw: TryFrom<T>,
     {
-        assert!(Self::SHIFT == F::SHIFT);
-        assert!(Self::BITS >= F::BITS);
-        assert!(Self::SIGNED == F::SIGNED);
+        let () = Convert::<F, Self>::WIDENING;
         unsafe { Self::new_unchecked(val.raw().try_into().ok().unwrap()) }
     }
     /// Return the fixed-point number of type `F` which has the same logical value as `self`.
-    /// `F` and `Self` must have the same shift and signedness. `F` must have at least as
-    /// many bits as `Self`.
-    fn into_fp<T: Num, F: Num<Raw = T>>(self) -> F
+    /// `F` and `Self` must have the same shift and signedness, and `F` must have at least
+    /// as many bits as `Self`, or compilation will fail.
+    fn into_fp<T, F>(self) -> F
     where
-        T: TryFrom<Self::Raw>,
+        T: Num + TryFrom<Self::Raw>,
+        F: Num<Raw = T>,
     {
         F::from_fp(self)
     }
@@ -159,8 +303,11 @@ pub trait Num: Clone + Copy + Eq + Ord + PartialEq + PartialOrd + Sized {
     fn set_bits<const N: u32>(self) -> Result<Self::Output<N, { Self::SHIFT }>, RangeError> {
         Self::Output::new(self.raw())
     }
-    /// Set the number of bits used to represent this value.  Unsafe: no bounds checking
-    /// is performed; the caller must ensure that the value fits within
+    /// Set the number of bits used to represent this value.
+    ///
+    /// # Safety
+    ///
+    /// No bounds checking is performed; the caller must ensure that the value fits within
     /// the new number of bits.  It is almost always better to call `.set_bits().unwrap()`
     /// instead, so that an out-of-bounds
     /// value panics with a reasonable message instead of propagating undefined
@@ -177,6 +324,85 @@ pub trait Num: Clone + Copy + Eq + Ord + PartialEq + PartialOrd + Sized {
             Ok(val) => val,
         }
     }
+    /// Set the number of bits used to represent this value, wrapping in case of
+    /// overflow (as two's-complement arithmetic does): the raw value is reduced
+    /// modulo `2^N`, by keeping its `N` least-significant bits and, for signed types,
+    /// sign-extending from bit `N - 1`.
+    fn wrapping_set_bits<const N: u32>(self) -> Self::Output<N, { Self::SHIFT }> {
+        unsafe { Self::Output::new_unchecked(wrap_raw(self.raw(), N)) }
+    }
+    /// Return the fixed-point number of type `F` which has the same logical value
+    /// as `self`, modulo `2^(F::BITS - F::SHIFT)`: that is, like `into_fp`, but
+    /// wrapping (as [`wrapping_set_bits`](Self::wrapping_set_bits) does) rather than
+    /// requiring `F` to have at least as many bits as `Self`.  `F` and `Self` must
+    /// have the same shift and signedness, or compilation will fail.
+    fn wrap_into_fp<T, F>(self) -> F
+    where
+        T: Num + TryFrom<Self::Raw>,
+        F: Num<Raw = T>,
+    {
+        let () = Convert::<Self, F>::SAME_SCALE;
+        let raw = wrap_raw(self.raw(), F::BITS.min(<Self::Raw as Num>::BITS));
+        unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) }
+    }
+    /// Return the fixed-point number of type `F` which is nearest to `self`: that
+    /// is, like `into_fp`, but clamping to `F::MIN` or `F::MAX` rather than
+    /// requiring `F` to have at least as many bits as `Self` (e.g. to bring an
+    /// `I64<40, S>` accumulator back to an `I16<15, S>` output).  `F` and `Self`
+    /// must have the same shift and signedness, or compilation will fail; use
+    /// [`saturating_cast`](Self::saturating_cast) to change either.
+    fn saturating_into_fp<F: Num>(self) -> F
+    where
+        Self::Raw: Into<i128>,
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let () = Convert::<Self, F>::SAME_SCALE;
+        saturate_from_i128(self.raw().into(), Self::SHIFT)
+    }
+    /// Convert to any fixed-point or primitive integer type `F` (which may differ
+    /// in shift, signedness and bits), rounding toward negative infinity at the
+    /// shift of `F`, or return `None` if the result is out of the range of `F`.
+    fn checked_cast<F: Num>(self) -> Option<F>
+    where
+        Self::Raw: Into<i128>,
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let (raw, exact) = shift_i128(self.raw().into(), Self::SHIFT, F::SHIFT);
+        let raw = exact.then_some(raw)?;
+        let (min, max): (i128, i128) = (F::MIN.raw().into(), F::MAX.raw().into());
+        (min..=max)
+            .contains(&raw)
+            .then(|| unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) })
+    }
+    /// Convert to any fixed-point or primitive integer type `F`, as
+    /// [`checked_cast`](Self::checked_cast) does, but saturating to `F::MIN` or
+    /// `F::MAX` if the result is out of range.
+    fn saturating_cast<F: Num>(self) -> F
+    where
+        Self::Raw: Into<i128>,
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        saturate_from_i128(self.raw().into(), Self::SHIFT)
+    }
+    /// Convert to any fixed-point or primitive integer type `F`, as
+    /// [`checked_cast`](Self::checked_cast) does, but wrapping if the result is out
+    /// of range: the raw result is reduced modulo `2^F::BITS`, as
+    /// [`wrapping_set_bits`](Self::wrapping_set_bits) does.
+    fn wrapping_cast<F: Num>(self) -> F
+    where
+        Self::Raw: Into<i128>,
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let (raw, _) = shift_i128(self.raw().into(), Self::SHIFT, F::SHIFT);
+        let raw = wrap_raw(raw, F::BITS);
+        let raw = if !F::SIGNED && raw < 0 && F::BITS > 0 {
+            // (the unsigned reduction of a negative value; F::BITS < 128 here)
+            raw + (1 << F::BITS)
+        } else {
+            raw
+        };
+        unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) }
+    }
     /// Shift the logical value of this number left by N bits. (N may be negative
     /// for a right shift).  This is a type system operation only; the raw value
     /// is unchanged.  The logical value is multiplied by 2^N.
@@ -213,9 +439,366 @@ pub trait Num: Clone + Copy + Eq + Ord + PartialEq + PartialOrd + Sized {
     {
         unsafe { Self::Output::new_unchecked(self.raw() >> N) }
     }
+    /// Shift the raw value of this number right by N bits, as `raw_shr` does, but
+    /// round to nearest (with ties away from zero) rather than truncating, so that
+    /// a chain of shifts does not drift toward negative infinity.  The output type
+    /// is that of `raw_shr`, so a result which would round up past its `MAX` is
+    /// `MAX`.
+    fn round_lsb<const N: u32>(
+        self,
+    ) -> Self::Output<{ Self::BITS - N }, { Self::SHIFT - N as i32 }>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); (Self::BITS - N) as usize]:,
+        [(); (Self::SHIFT - N as i32) as usize]:,
+    {
+        round_lsb_raw::<Self, N>(self, Nearest::MODE)
+    }
+    /// Shift the raw value of this number right by N bits, as `round_lsb` does,
+    /// but with ties to even, so that the rounding of ties is unbiased too.
+    fn round_lsb_even<const N: u32>(
+        self,
+    ) -> Self::Output<{ Self::BITS - N }, { Self::SHIFT - N as i32 }>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); (Self::BITS - N) as usize]:,
+        [(); (Self::SHIFT - N as i32) as usize]:,
+    {
+        round_lsb_raw::<Self, N>(self, NearestEven::MODE)
+    }
+    /// Change the shift of this number to `S`, rounding as `R` specifies (e.g.
+    /// [`NearestEven`], [`TowardZero`] or [`AwayFromZero`]) when bits are shifted
+    /// out.  The output has as many bits as the result can need: more when `S`
+    /// is larger than `SHIFT`, fewer when it is smaller, plus one when `R` can
+    /// round up into another integer bit.  Compilation will fail if the result
+    /// would have too many bits for the raw type.
+    fn rescale<const S: i32, R: Rounding>(
+        self,
+    ) -> Self::Output<{ rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT - S, R::MODE) }, S>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT - S, R::MODE) as usize]:,
+    {
+        let raw = rescale_raw(self.raw().into(), Self::SHIFT - S, R::MODE);
+        unsafe { Self::Output::new_unchecked(raw.try_into().ok().unwrap()) }
+    }
+    /// Return the largest integer which is not greater than this number, as a
+    /// number with a shift of zero (so its raw value is the integer), e.g. the
+    /// index of the pixel which contains a position.
+    fn floor(
+        self,
+    ) -> Self::Output<{ rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, Floor::MODE) }, 0>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, Floor::MODE) as usize]:,
+    {
+        let raw = rescale_raw(self.raw().into(), Self::SHIFT, Floor::MODE);
+        unsafe { Self::Output::new_unchecked(raw.try_into().ok().unwrap()) }
+    }
+    /// Return the smallest integer which is not less than this number, as
+    /// `floor` does.  The output may have one more bit than that of `floor`.
+    fn ceil(
+        self,
+    ) -> Self::Output<{ rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, Ceil::MODE) }, 0>
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, Ceil::MODE) as usize]:,
+    {
+        let raw = rescale_raw(self.raw().into(), Self::SHIFT, Ceil::MODE);
+        unsafe { Self::Output::new_unchecked(raw.try_into().ok().unwrap()) }
+    }
+    /// Return the nearest integer to this number, with ties away from zero, as
+    /// `floor` does.  The output may have one more bit than that of `floor`.
+    fn round(
+        self,
+    ) -> Self::Output<
+        { rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, Nearest::MODE) },
+        0,
+    >
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, Nearest::MODE) as usize]:,
+    {
+        let raw = rescale_raw(self.raw().into(), Self::SHIFT, Nearest::MODE);
+        unsafe { Self::Output::new_unchecked(raw.try_into().ok().unwrap()) }
+    }
+    /// Return the integer part of this number, rounding toward zero, as `floor`
+    /// does.
+    fn trunc(
+        self,
+    ) -> Self::Output<
+        { rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, TowardZero::MODE) },
+        0,
+    >
+    where
+        Self::Raw: Into<i128> + TryFrom<i128>,
+        [(); rescale_bits(Self::BITS, Self::SIGNED, Self::SHIFT, TowardZero::MODE)
+            as usize]:,
+    {
+        let raw = rescale_raw(self.raw().into(), Self::SHIFT, TowardZero::MODE);
+        unsafe { Self::Output::new_unchecked(raw.try_into().ok().unwrap()) }
+    }
+}
+
+/// The raw value `raw` shifted right by `k` bits (or left by `-k` bits), rounded
+/// under `mode`.
+fn rescale_raw(raw: i128, k: i32, mode: RoundingMode) -> i128 {
+    if k <= 0 {
+        raw << -k
+    } else {
+        exprs::shr_rounded(raw, k as u32, mode)
+    }
+}
+
+/// `raw_shr` of `x` by `N` bits, rounded under `mode` and limited to the `MAX` of
+/// the output.
+fn round_lsb_raw<F: Num, const N: u32>(
+    x: F,
+    mode: RoundingMode,
+) -> F::Output<{ F::BITS - N }, { F::SHIFT - N as i32 }>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+    [(); (F::BITS - N) as usize]:,
+    [(); (F::SHIFT - N as i32) as usize]:,
+{
+    let max = F::Output::<{ F::BITS - N }, { F::SHIFT - N as i32 }>::MAX.raw().into();
+    let raw = rescale_raw(x.raw().into(), N as i32, mode).min(max);
+    unsafe { F::Output::new_unchecked(raw.try_into().ok().unwrap()) }
+}
+
+/// Returns the bit width of the return type from [`rescale`](Num::rescale), for a
+/// number of `bits` bits whose raw value is shifted right by `k` bits (or left by
+/// `-k` bits) with rounding `mode`.
+#[doc(hidden)]
+pub const fn rescale_bits(bits: u32, signed: bool, k: i32, mode: RoundingMode) -> u32 {
+    if k <= 0 {
+        return bits + k.unsigned_abs();
+    }
+    // a signed number keeps at least a sign bit, since -1 >> k is -1
+    let kept = if bits as i64 > k as i64 {
+        bits - k as u32
+    } else if signed && bits > 0 {
+        1
+    } else {
+        0
+    };
+    kept + mode.carries() as u32
+}
+
+/// Zero, as a raw value.  (The `MIN` of every raw type is either zero or just
+/// its sign bit.)
+fn raw_zero<R: Num<Raw = R> + Shl<u32, Output = R>>() -> R {
+    R::MIN << 1
+}
+
+/// Compile-time checks that the result of an operator, of type `F` with `B` bits,
+/// fits in the raw type of `F`.  (The same requirement is checked by `F::BITS`,
+/// but only where `F` is used, with a message which does not name the operator.)
+pub(crate) struct Fits<F, const B: u32>(core::marker::PhantomData<F>);
+
+impl<F: Num, const B: u32> Fits<F, B> {
+    pub(crate) const SUM: () = assert!(
+        B <= <F::Raw as Num>::BITS,
+        "sum has too many bits for its raw type"
+    );
+    pub(crate) const PRODUCT: () = assert!(
+        B <= <F::Raw as Num>::BITS,
+        "product has too many bits for its raw type; see `widening_mul` and `Promote`"
+    );
+    pub(crate) const QUOTIENT: () = assert!(
+        B <= <F::Raw as Num>::BITS,
+        "quotient has too many bits for its raw type"
+    );
+}
+
+/// Compile-time checks on a pair of formats, for conversions from `A` to `B`.
+pub(crate) struct Convert<A, B>(core::marker::PhantomData<(A, B)>);
+
+impl<A: Num, B: Num> Convert<A, B> {
+    /// `A` and `B` have the same shift and signedness.
+    pub(crate) const SAME_SCALE: () = {
+        assert!(A::SHIFT == B::SHIFT, "formats have different shifts");
+        assert!(A::SIGNED == B::SIGNED, "formats have different signedness");
+    };
+    /// As `SAME_SCALE`, and `B` has at least as many bits as `A`.
+    pub(crate) const WIDENING: () = {
+        let () = Self::SAME_SCALE;
+        assert!(B::BITS >= A::BITS, "conversion would lose bits");
+    };
+}
+
+/// Convert `val / 2^shift` to `F`, truncating and saturating.
+fn saturate_from_i128<F: Num>(val: i128, shift: i32) -> F
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    let (min, max): (i128, i128) = (F::MIN.raw().into(), F::MAX.raw().into());
+    let k = shift as i64 - F::SHIFT as i64;
+    let raw = if k >= 0 {
+        val >> k.min(127)
+    } else if val == 0 {
+        0
+    } else if -k < val.unsigned_abs().leading_zeros() as i64 - 1 {
+        val << -k
+    } else if val < 0 {
+        min
+    } else {
+        max
+    };
+    unsafe { F::new_unchecked(raw.clamp(min, max).try_into().ok().unwrap()) }
+}
+
+/// Shift the raw value `val` from shift `from` to shift `to`, rounding toward
+/// negative infinity, and return the result modulo `2^128`, along with whether it
+/// is exact (i.e. did not overflow an `i128`).
+fn shift_i128(val: i128, from: i32, to: i32) -> (i128, bool) {
+    let k = to as i64 - from as i64;
+    if k <= 0 {
+        (val >> (-k).min(127), true)
+    } else if k >= 128 {
+        (0, val == 0)
+    } else {
+        let shifted = val << k;
+        (shifted, shifted >> k == val)
+    }
+}
+
+/// Reduce `raw` modulo `2^n` into the range of an `n`-bit number of its type.
+fn wrap_raw<R: Num<Raw = R> + Shl<u32, Output = R> + Shr<u32, Output = R>>(raw: R, n: u32) -> R {
+    if n == 0 {
+        // (shifting by the full width at once would overflow)
+        raw << (R::BITS - 1) << 1
+    } else {
+        raw << (R::BITS - n) >> (R::BITS - n)
+    }
 }
 
 mod fp_impl;
 pub use fp_impl::*;
 mod add_sub;
 mod mul_div;
+mod shadow;
+pub use shadow::*;
+mod sum;
+pub use sum::*;
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parallel")]
+pub use parallel::*;
+mod kernels;
+pub use kernels::{LengthMismatch, SliceError};
+mod noise;
+pub use noise::*;
+mod waveform;
+pub use waveform::*;
+mod resample;
+pub use resample::*;
+mod dc_block;
+pub use dc_block::*;
+mod median;
+pub use median::*;
+mod sliding;
+pub use sliding::*;
+mod histogram;
+pub use histogram::*;
+pub mod color;
+pub mod const_math;
+mod perlin;
+pub use perlin::*;
+mod imu;
+pub use imu::*;
+mod biquad;
+pub use biquad::*;
+mod soft_clip;
+pub use soft_clip::*;
+mod meter;
+pub use meter::*;
+mod table;
+pub use table::*;
+mod promote;
+pub use promote::*;
+mod packed;
+pub use packed::*;
+pub mod telemetry;
+mod bfp;
+pub use bfp::*;
+mod sigma_delta;
+pub use sigma_delta::*;
+mod farrow;
+pub use farrow::*;
+mod complex;
+pub use complex::*;
+mod hilbert;
+pub use hilbert::*;
+mod convolve;
+pub use convolve::*;
+mod matrix;
+pub use matrix::*;
+mod state_space;
+pub use state_space::*;
+mod encoder;
+pub use encoder::*;
+mod svm;
+pub use svm::*;
+mod gemm;
+pub use gemm::*;
+mod activation;
+pub use activation::*;
+mod welford;
+pub use welford::*;
+mod exprs;
+pub use exprs::*;
+mod align;
+pub use align::*;
+mod decimal;
+pub use decimal::*;
+mod posit;
+pub use posit::*;
+mod buffer;
+pub use buffer::*;
+mod schema;
+mod ratio;
+pub use ratio::*;
+mod rational;
+pub use rational::scale_by_ratio;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wasm")]
+pub use wasm::*;
+mod pipeline;
+pub use pipeline::*;
+mod cholesky;
+pub use cholesky::*;
+mod endian;
+pub use endian::*;
+mod sort_key;
+mod coeffs;
+pub use coeffs::*;
+pub mod selftest;
+mod delay_line;
+pub use delay_line::*;
+mod geo;
+pub use geo::*;
+mod checked;
+mod wrapping;
+pub use wrapping::*;
+mod sqrt;
+pub use sqrt::{sqrt_bits, sqrt_shift};
+pub mod trig;
+mod exp_log;
+pub use exp_log::{exp2_bits, log2_bits};
+mod recip;
+pub use recip::recip_bits;
+mod fract;
+pub use fract::fract_bits;
+pub mod dither;
+mod int256;
+pub use int256::*;
+mod dyn_fp;
+pub use dyn_fp::*;
+mod interval;
+pub use interval::*;
+mod error_bound;
+pub use error_bound::*;
+#[cfg(feature = "ufmt")]
+pub mod ufmt;
@@ -0,0 +1,44 @@
+use crate::{Num, RangeError};
+
+/// Small dense matrix of `R` rows and `C` columns, stored row by row, whose
+/// elements all have the fixed-point type `F`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Matrix<F: Num, const R: usize, const C: usize> {
+    rows: [[F; C]; R],
+}
+
+impl<F: Num, const R: usize, const C: usize> Matrix<F, R, C> {
+    /// Create a matrix from its rows.
+    pub fn new(rows: [[F; C]; R]) -> Self {
+        Self { rows }
+    }
+    /// Return the matrix whose elements have the logical values `rows`, or a
+    /// `RangeError` if any of them is out of the range of `F`.
+    pub fn from_f64(rows: [[f64; C]; R]) -> Result<Self, RangeError> {
+        let mut out = [[F::MIN; C]; R];
+        for (out, row) in out.iter_mut().zip(rows) {
+            for (out, x) in out.iter_mut().zip(row) {
+                *out = F::from_f64(x)?;
+            }
+        }
+        Ok(Self { rows: out })
+    }
+    /// Return the elements, row by row.
+    pub fn rows(&self) -> &[[F; C]; R] {
+        &self.rows
+    }
+    /// Return the element in row `i` and column `j`, or `None` if out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> Option<F> {
+        self.rows.get(i)?.get(j).copied()
+    }
+    /// Return the transpose of the matrix.
+    pub fn transpose(&self) -> Matrix<F, C, R> {
+        let mut out = [[F::MIN; R]; C];
+        for (i, row) in self.rows.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                out[j][i] = x;
+            }
+        }
+        Matrix { rows: out }
+    }
+}
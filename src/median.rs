@@ -0,0 +1,55 @@
+use crate::Num;
+
+/// Fixed-window median filter over the most recent `N` samples, for rejecting
+/// impulsive noise ("spikes").  `N` should be odd, so that the median is a
+/// single sample; the output is always one of the input samples, so it has
+/// the same type as the input.
+///
+/// The window is kept both in arrival order (to know which sample to evict) and
+/// in sorted order, which is updated by insertion in `O(N)` per sample without
+/// allocation.  Before `N` samples have arrived, the window is padded with the
+/// initial value given to `new`.
+#[derive(Clone, Debug)]
+pub struct MedianFilter<F: Num, const N: usize> {
+    /// Samples in arrival order (a ring buffer; `next` is the oldest).
+    ring: [F; N],
+    next: usize,
+    /// The same samples, in ascending order.
+    sorted: [F; N],
+}
+
+impl<F: Num, const N: usize> MedianFilter<F, N> {
+    const CHECK: () = assert!(N % 2 == 1, "median filter window length must be odd");
+    /// Create a median filter whose window initially contains `N` copies of `init`.
+    pub fn new(init: F) -> Self {
+        let () = Self::CHECK;
+        Self {
+            ring: [init; N],
+            next: 0,
+            sorted: [init; N],
+        }
+    }
+    /// Insert a sample into the window (evicting the oldest sample)
+    /// and return the median of the window.
+    pub fn process(&mut self, x: F) -> F {
+        let old = core::mem::replace(&mut self.ring[self.next], x);
+        self.next = if self.next + 1 == N { 0 } else { self.next + 1 };
+        // Remove `old` from the sorted window, then shift elements to make
+        // room for `x` in its sorted position.
+        let mut i = self.sorted.iter().position(|&s| s == old).unwrap();
+        while i > 0 && self.sorted[i - 1] > x {
+            self.sorted[i] = self.sorted[i - 1];
+            i -= 1;
+        }
+        while i + 1 < N && self.sorted[i + 1] < x {
+            self.sorted[i] = self.sorted[i + 1];
+            i += 1;
+        }
+        self.sorted[i] = x;
+        self.median()
+    }
+    /// Return the median of the window.
+    pub fn median(&self) -> F {
+        self.sorted[N / 2]
+    }
+}
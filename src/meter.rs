@@ -0,0 +1,153 @@
+use core::marker::PhantomData;
+
+use crate::{clog2, Num, I32};
+
+/// `20 * log10(2)` (decibels per doubling of amplitude), in Q16.
+const DB_PER_OCTAVE: i64 = 394_566;
+
+/// Base-2 logarithm of `v > 0` in Q16, truncated, by repeated squaring of the
+/// normalized mantissa.
+fn log2_q16(v: u128) -> i64 {
+    let msb = 127 - v.leading_zeros();
+    // mantissa in [1, 2), in Q31
+    let mut m = if msb >= 31 {
+        (v >> (msb - 31)) as u64
+    } else {
+        (v << (31 - msb)) as u64
+    };
+    let mut y = (msb as i64) << 16;
+    for i in (0..16).rev() {
+        m = (m * m) >> 31;
+        if m >= 1 << 32 {
+            m >>= 1;
+            y += 1 << i;
+        }
+    }
+    y
+}
+
+/// Peak and RMS level meter, measuring over consecutive blocks of `N` samples.
+///
+/// After every `N` samples, the meter reports the peak (largest absolute value)
+/// and RMS (root mean square, rounded to the nearest LSB) of that block.  It
+/// also holds the largest peak seen since the last call to
+/// [`reset_hold`](Self::reset_hold).  Squares are accumulated exactly in a
+/// `u128`, so `2 * BITS + clog2(N)` must not exceed 128.
+///
+/// Levels have the same raw type and shift as the input, and one more bit (to
+/// hold the absolute value of `F::MIN`).  They can also be read in decibels
+/// relative to full scale, where full scale is `2^(BITS - 1)` raw for signed
+/// `F` and `2^BITS` raw for unsigned `F` (so a full-scale square wave reads
+/// 0 dBFS and a full-scale sine wave reads -3.01 dBFS).
+#[derive(Clone, Debug)]
+pub struct LevelMeter<F: Num, const N: usize> {
+    sum_squares: u128,
+    block_peak: u128,
+    count: usize,
+    peak: u128,
+    rms: u128,
+    hold: u128,
+    _sample: PhantomData<F>,
+}
+
+impl<F: Num, const N: usize> Default for LevelMeter<F, N>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+    [(); (F::BITS + 1) as usize]:,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num, const N: usize> LevelMeter<F, N>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+    [(); (F::BITS + 1) as usize]:,
+{
+    const CHECK: () = {
+        assert!(N > 0, "block length must be nonzero");
+        assert!(
+            2 * F::BITS + clog2(N) <= u128::BITS,
+            "sum of squares too wide for u128"
+        );
+    };
+    /// Create a meter with all levels at zero.
+    pub fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            sum_squares: 0,
+            block_peak: 0,
+            count: 0,
+            peak: 0,
+            rms: 0,
+            hold: 0,
+            _sample: PhantomData,
+        }
+    }
+    /// Measure one sample.  Returns true if this sample completed a block, i.e.
+    /// if the peak and RMS levels have just been updated.
+    pub fn process(&mut self, x: F) -> bool {
+        let x = x.raw().into().unsigned_abs();
+        self.sum_squares += x * x;
+        self.block_peak = self.block_peak.max(x);
+        self.count += 1;
+        if self.count < N {
+            return false;
+        }
+        let mean = self.sum_squares / N as u128;
+        let root = mean.isqrt();
+        // round to nearest: sqrt(mean) >= root + 0.5 iff mean > root * (root + 1)
+        self.rms = if mean > root * (root + 1) {
+            root + 1
+        } else {
+            root
+        };
+        self.peak = self.block_peak;
+        self.hold = self.hold.max(self.peak);
+        (self.sum_squares, self.block_peak, self.count) = (0, 0, 0);
+        true
+    }
+    fn level(raw: u128) -> F::Output<{ F::BITS + 1 }, { F::SHIFT }> {
+        unsafe { F::Output::new_unchecked((raw as i128).try_into().ok().unwrap()) }
+    }
+    /// Convert a raw level to dBFS in Q16, or `None` for a level of zero.
+    fn dbfs(raw: u128) -> Option<I32<32, 16>> {
+        if raw == 0 {
+            return None;
+        }
+        let full_scale = if F::SIGNED { F::BITS - 1 } else { F::BITS } as i64;
+        let octaves = log2_q16(raw) - (full_scale << 16);
+        let db = (octaves * DB_PER_OCTAVE + (1 << 15)) >> 16;
+        Some(unsafe { I32::new_unchecked(db as i32) })
+    }
+    /// Peak level of the most recent complete block.
+    pub fn peak(&self) -> F::Output<{ F::BITS + 1 }, { F::SHIFT }> {
+        Self::level(self.peak)
+    }
+    /// RMS level of the most recent complete block.
+    pub fn rms(&self) -> F::Output<{ F::BITS + 1 }, { F::SHIFT }> {
+        Self::level(self.rms)
+    }
+    /// Largest peak level of any complete block since the last call to
+    /// [`reset_hold`](Self::reset_hold).
+    pub fn peak_hold(&self) -> F::Output<{ F::BITS + 1 }, { F::SHIFT }> {
+        Self::level(self.hold)
+    }
+    /// Reset the held peak level to zero.
+    pub fn reset_hold(&mut self) {
+        self.hold = 0;
+    }
+    /// Peak level in dBFS, accurate to within 0.001 dB, or `None` if the level is zero.
+    pub fn peak_dbfs(&self) -> Option<I32<32, 16>> {
+        Self::dbfs(self.peak)
+    }
+    /// RMS level in dBFS; see [`peak_dbfs`](Self::peak_dbfs).
+    pub fn rms_dbfs(&self) -> Option<I32<32, 16>> {
+        Self::dbfs(self.rms)
+    }
+    /// Held peak level in dBFS; see [`peak_dbfs`](Self::peak_dbfs).
+    pub fn peak_hold_dbfs(&self) -> Option<I32<32, 16>> {
+        Self::dbfs(self.hold)
+    }
+}
@@ -1,6 +1,23 @@
-use core::ops::{Div, Mul};
+use core::ops::{Div, Mul, Rem};
 
-use crate::Num;
+use crate::{add_sub::max, Fits, Num};
+
+/// Needed for const-generic support, because the standard
+/// ways to compute minimum of two values are not const.
+pub const fn min(a: u32, b: u32) -> u32 {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Compile-time check on the exponent of `pow_const`.
+struct Exponent<const N: u32>;
+
+impl<const N: u32> Exponent<N> {
+    const CHECK: () = assert!(N > 0, "exponent must be positive");
+}
 
 macro_rules! fp_impl {
     ($Name:ident, $T:ty) => {
@@ -19,8 +36,37 @@ macro_rules! fp_impl {
                     BITS + <$T>::BITS - (val - 1).leading_zeros()
                 }
             }
+            /// If `val` is plus or minus a power of two, `2^k`, return `k`.
+            const fn pow2_exponent(val: $T) -> Option<u32> {
+                let magnitude = val.abs_diff(0);
+                if magnitude.is_power_of_two() {
+                    Some(magnitude.trailing_zeros())
+                } else {
+                    None
+                }
+            }
+            /// Multiply by the constant `VAL`.  A power of two (or its negative)
+            /// compiles to a shift (and a negation), on every target.
             pub fn mul_const<const VAL: $T>(self) -> $Name<{ Self::mul_const_bits(VAL) }, SHIFT> {
-                unsafe { $Name::new_unchecked(self.raw() * VAL) }
+                let raw = self.raw();
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let product = match Self::pow2_exponent(VAL) {
+                    // (the product fits in the output, so wrapping gives it exactly)
+                    Some(k) if VAL < 0 => (raw << k).wrapping_neg(),
+                    Some(k) => raw << k,
+                    None => raw * VAL,
+                };
+                unsafe { $Name::new_unchecked(product) }
+            }
+            /// Raise to the constant power `N`, which must be positive.  The result
+            /// has `N` times the bits and `N` times the shift, as `N - 1` applications
+            /// of `Mul` would give.  Compilation will fail if `N` is zero, or if the
+            /// result has too many bits for the raw type.
+            pub fn pow_const<const N: u32>(self) -> $Name<{ BITS * N }, { SHIFT * N as i32 }> {
+                let () = Exponent::<N>::CHECK;
+                let () = Fits::<Self, { BITS * N }>::PRODUCT;
+                // (the power fits in the output, so wrapping gives it exactly)
+                unsafe { $Name::new_unchecked(self.raw().wrapping_pow(N)) }
             }
             /// Returns the bit width of the return type from `div_const`.
             pub const fn div_const_bits(val: $T) -> u32 {
@@ -35,8 +81,134 @@ macro_rules! fp_impl {
                     1 + BITS - (<$T>::BITS - val.leading_zeros())
                 }
             }
+            /// Divide by the constant `VAL`, rounding toward zero.  A power of two (or
+            /// its negative) compiles to a shift, with a correction for negative
+            /// dividends, rather than a division, on every target (including those
+            /// with no divide instruction).
             pub fn div_const<const VAL: $T>(self) -> $Name<{ Self::div_const_bits(VAL) }, SHIFT> {
-                unsafe { $Name::new_unchecked(self.raw() / VAL) }
+                let raw = self.raw();
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let quotient = match Self::pow2_exponent(VAL) {
+                    Some(k) => {
+                        // an arithmetic shift rounds down, so round negative values up
+                        let truncated = if raw < 0 {
+                            (raw + ((1 as $T) << k).wrapping_sub(1)) >> k
+                        } else {
+                            raw >> k
+                        };
+                        if VAL < 0 {
+                            truncated.wrapping_neg()
+                        } else {
+                            truncated
+                        }
+                    }
+                    None => raw / VAL,
+                };
+                unsafe { $Name::new_unchecked(quotient) }
+            }
+            /// Returns the bit width of the return type from `div_euclid`, which is
+            /// the same as that from `Div`.
+            pub const fn div_euclid_bits() -> u32 {
+                BITS + (<$T>::MIN != 0) as u32
+            }
+            /// Divide as `Div` does, but round the quotient toward negative infinity
+            /// if the divisor is positive, and toward positive infinity if it is
+            /// negative, so that `rem_euclid` is never negative.
+            pub fn div_euclid<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> $Name<{ Self::div_euclid_bits() }, { SHIFT - S1 }> {
+                unsafe { $Name::new_unchecked(self.raw().wrapping_div_euclid(other.raw())) }
+            }
+            /// Divide as `Div` does, but round the quotient to nearest, with ties away
+            /// from zero, as `(a + b / 2) / b` does for positive values (but without
+            /// overflow).  The output type is that of `Div`.
+            pub fn div_round<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> $Name<{ BITS + Self::SIGNED as u32 }, { SHIFT - S1 }> {
+                let (n, d) = (self.raw(), other.raw());
+                let (q, r) = (n / d, n.wrapping_rem(d));
+                // the remainder is smaller than both the dividend and the divisor
+                let (_, r) = unsafe { Self::new_unchecked(r) }.sign_magnitude();
+                let (_, d_magnitude) = other.sign_magnitude();
+                let q = if r < d_magnitude - r {
+                    q
+                } else if self.is_negative() != other.is_negative() {
+                    q.wrapping_sub(1 as $T)
+                } else {
+                    q.wrapping_add(1 as $T)
+                };
+                unsafe { $Name::new_unchecked(q) }
+            }
+            /// Divide as `Div` does, but round the quotient toward positive infinity,
+            /// as `(a + b - 1) / b` does for positive values (but without overflow).
+            /// The output type is that of `Div`.
+            pub fn div_ceil<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> $Name<{ BITS + Self::SIGNED as u32 }, { SHIFT - S1 }> {
+                let (n, d) = (self.raw(), other.raw());
+                let (q, r) = (n / d, n.wrapping_rem(d));
+                // truncation already rounds a negative quotient up
+                let q = if r != 0 as $T && self.is_negative() == other.is_negative() {
+                    q.wrapping_add(1 as $T)
+                } else {
+                    q
+                };
+                unsafe { $Name::new_unchecked(q) }
+            }
+            /// Returns the bit width of the return type from `rem_euclid`, for a
+            /// divisor of `divisor_bits` bits.  The remainder is less than the
+            /// magnitude of the divisor, and for unsigned types it is at most the
+            /// dividend.
+            pub const fn rem_euclid_bits(divisor_bits: u32) -> u32 {
+                if <$T>::MIN != 0 || divisor_bits < BITS {
+                    divisor_bits
+                } else {
+                    BITS
+                }
+            }
+            /// Return the remainder matching `div_euclid`, which has the shift of
+            /// `self`, and is in the range `[0, |other|)`: e.g. a phase wrapped into
+            /// one period.
+            pub fn rem_euclid<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> $Name<{ Self::rem_euclid_bits(B1) }, SHIFT> {
+                // (wrapping_rem_euclid gives MIN % -1 == 0 rather than overflowing)
+                unsafe { $Name::new_unchecked(self.raw().wrapping_rem_euclid(other.raw())) }
+            }
+            /// Return the quotient and remainder of dividing by `other`, as `Div` and
+            /// `Rem` do, with the same output types, in one division: the two always
+            /// reconstruct the dividend exactly, as `q * other + r == self`.
+            pub fn div_rem<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> (
+                $Name<{ BITS + Self::SIGNED as u32 }, { SHIFT - S1 }>,
+                $Name<{ min(BITS, B1) }, SHIFT>,
+            ) {
+                let (n, d) = (self.raw(), other.raw());
+                unsafe {
+                    (
+                        $Name::new_unchecked(n / d),
+                        $Name::new_unchecked(n.wrapping_rem(d)),
+                    )
+                }
+            }
+            /// Return `self * b + c`, where `c` has the shift of the product, in one
+            /// expression which maps onto a multiply-accumulate instruction.  The
+            /// output has the shift of the product, and one more bit than the wider
+            /// of the product and `c` (as `self * b + c` would), without naming the
+            /// type of the product.
+            pub fn mul_add<const B1: u32, const S1: i32, const B2: u32>(
+                self,
+                b: $Name<B1, S1>,
+                c: $Name<B2, { SHIFT + S1 }>,
+            ) -> $Name<{ max(BITS + B1, B2) + 1 }, { SHIFT + S1 }> {
+                let () = Fits::<Self, { max(BITS + B1, B2) + 1 }>::SUM;
+                unsafe { $Name::new_unchecked(self.raw() * b.raw() + c.raw()) }
             }
         }
         impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Mul<$Name<B1, S1>>
@@ -47,6 +219,8 @@ macro_rules! fp_impl {
         {
             type Output = $Name<{ B0 + B1 }, { S0 + S1 }>;
             fn mul(self: $Name<B0, S0>, other: $Name<B1, S1>) -> Self::Output {
+                #[allow(clippy::suspicious_arithmetic_impl)] // (adding bits, not values)
+                let () = Fits::<Self::Output, { B0 + B1 }>::PRODUCT;
                 unsafe { Self::Output::new_unchecked(self.raw() * other.raw()) }
             }
         }
@@ -64,6 +238,20 @@ macro_rules! fp_impl {
                 unsafe { Self::Output::new_unchecked(self.raw() / other.raw()) }
             }
         }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Rem<$Name<B1, S1>>
+            for $Name<B0, S0>
+        where
+            [(); min(B0, B1) as usize]:,
+        {
+            // The remainder is consistent with `Div`: `a == (a / b) * b + a % b`, so it has
+            // the sign of the dividend, and the shift of the dividend.  Its magnitude is
+            // less than that of the divisor, and at most that of the dividend.
+            type Output = $Name<{ min(B0, B1) }, S0>;
+            fn rem(self: $Name<B0, S0>, other: $Name<B1, S1>) -> Self::Output {
+                // (wrapping_rem gives MIN % -1 == 0 rather than overflowing)
+                unsafe { Self::Output::new_unchecked(self.raw().wrapping_rem(other.raw())) }
+            }
+        }
     };
 }
 
@@ -77,11 +265,46 @@ fp_impl!(I64, i64);
 fp_impl!(U64, u64);
 fp_impl!(I128, i128);
 fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
 fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
 fp_impl!(Usize, usize);
 
 macro_rules! fp_signed_unsigned_impl {
     ($Uname:ident, $Iname:ident) => {
+        impl<const B: u32, const S: i32> $Uname<B, S> {
+            /// Return the square, which has twice the bits and twice the shift.
+            pub fn square(self) -> $Uname<{ 2 * B }, { 2 * S }> {
+                let () = Fits::<Self, { 2 * B }>::PRODUCT;
+                unsafe { $Uname::new_unchecked(self.raw().wrapping_mul(self.raw())) }
+            }
+            /// Return the cube, which has three times the bits and three times the
+            /// shift.
+            pub fn cube(self) -> $Uname<{ 3 * B }, { 3 * S }> {
+                let () = Fits::<Self, { 3 * B }>::PRODUCT;
+                let raw = self.raw();
+                unsafe { $Uname::new_unchecked(raw.wrapping_mul(raw).wrapping_mul(raw)) }
+            }
+        }
+        impl<const B: u32, const S: i32> $Iname<B, S> {
+            /// Return the square, which has twice the shift.  A square is never
+            /// negative, so it is unsigned, and the largest is `MIN * MIN`, which
+            /// is `2^(2 * B - 2)`, so it has `2 * B - 1` bits (one fewer than `Mul`
+            /// gives).
+            pub fn square(self) -> $Uname<{ (2 * B).saturating_sub(1) }, { 2 * S }> {
+                let () = Fits::<Self, { (2 * B).saturating_sub(1) }>::PRODUCT;
+                let magnitude = self.raw().unsigned_abs();
+                unsafe { $Uname::new_unchecked(magnitude.wrapping_mul(magnitude)) }
+            }
+            /// Return the cube, which has three times the shift.  The cube of `MIN`
+            /// is `-2^(3 * B - 3)`, so it has `3 * B - 2` bits (two fewer than `Mul`
+            /// gives).
+            pub fn cube(self) -> $Iname<{ (3 * B).saturating_sub(2) }, { 3 * S }> {
+                let () = Fits::<Self, { (3 * B).saturating_sub(2) }>::PRODUCT;
+                let raw = self.raw();
+                unsafe { $Iname::new_unchecked(raw.wrapping_mul(raw).wrapping_mul(raw)) }
+            }
+        }
         impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Mul<$Uname<B1, S1>>
             for $Iname<B0, S0>
         where
@@ -90,8 +313,12 @@ macro_rules! fp_signed_unsigned_impl {
         {
             type Output = $Iname<{ B0 + B1 }, { S0 + S1 }>;
             fn mul(self: $Iname<B0, S0>, other: $Uname<B1, S1>) -> Self::Output {
+                #[allow(clippy::suspicious_arithmetic_impl)] // (adding bits, not values)
+                let () = Fits::<Self::Output, { B0 + B1 }>::PRODUCT;
                 unsafe {
-                    Self::Output::new_unchecked(self.raw() * other.raw() as <Self::Output as Num>::Raw)
+                    Self::Output::new_unchecked(
+                        self.raw() * other.raw() as <Self::Output as Num>::Raw,
+                    )
                 }
             }
         }
@@ -103,8 +330,12 @@ macro_rules! fp_signed_unsigned_impl {
         {
             type Output = $Iname<{ B0 + B1 }, { S0 + S1 }>;
             fn mul(self: $Uname<B0, S0>, other: $Iname<B1, S1>) -> Self::Output {
+                #[allow(clippy::suspicious_arithmetic_impl)] // (adding bits, not values)
+                let () = Fits::<Self::Output, { B0 + B1 }>::PRODUCT;
                 unsafe {
-                    Self::Output::new_unchecked(self.raw() as <Self::Output as Num>::Raw * other.raw())
+                    Self::Output::new_unchecked(
+                        self.raw() as <Self::Output as Num>::Raw * other.raw(),
+                    )
                 }
             }
         }
@@ -116,7 +347,9 @@ macro_rules! fp_signed_unsigned_impl {
             type Output = $Iname<B0, { S0 - S1 }>;
             fn div(self: $Iname<B0, S0>, other: $Uname<B1, S1>) -> Self::Output {
                 unsafe {
-                    Self::Output::new_unchecked(self.raw() / other.raw() as <Self::Output as Num>::Raw)
+                    Self::Output::new_unchecked(
+                        self.raw() / other.raw() as <Self::Output as Num>::Raw,
+                    )
                 }
             }
         }
@@ -129,10 +362,35 @@ macro_rules! fp_signed_unsigned_impl {
             type Output = $Iname<{ B0 + 1 }, { S0 - S1 }>;
             fn div(self: $Uname<B0, S0>, other: $Iname<B1, S1>) -> Self::Output {
                 unsafe {
-                    Self::Output::new_unchecked(self.raw() as <Self::Output as Num>::Raw / other.raw())
+                    Self::Output::new_unchecked(
+                        self.raw() as <Self::Output as Num>::Raw / other.raw(),
+                    )
                 }
             }
         }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Rem<$Uname<B1, S1>>
+            for $Iname<B0, S0>
+        where
+            [(); min(B0, B1 + 1) as usize]:,
+        {
+            type Output = $Iname<{ min(B0, B1 + 1) }, S0>;
+            fn rem(self: $Iname<B0, S0>, other: $Uname<B1, S1>) -> Self::Output {
+                let r = (self.raw().unsigned_abs() % other.raw()) as <Self::Output as Num>::Raw;
+                let r = if self.raw() < 0 { r.wrapping_neg() } else { r };
+                unsafe { Self::Output::new_unchecked(r) }
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32> Rem<$Iname<B1, S1>>
+            for $Uname<B0, S0>
+        where
+            [(); min(B0, B1.saturating_sub(1)) as usize]:,
+        {
+            // The remainder of an unsigned dividend is never negative.
+            type Output = $Uname<{ min(B0, B1.saturating_sub(1)) }, S0>;
+            fn rem(self: $Uname<B0, S0>, other: $Iname<B1, S1>) -> Self::Output {
+                unsafe { Self::Output::new_unchecked(self.raw() % other.raw().unsigned_abs()) }
+            }
+        }
     };
 }
 
@@ -141,4 +399,110 @@ fp_signed_unsigned_impl!(U16, I16);
 fp_signed_unsigned_impl!(U32, I32);
 fp_signed_unsigned_impl!(U64, I64);
 fp_signed_unsigned_impl!(U128, I128);
+#[cfg(not(feature = "deterministic"))]
 fp_signed_unsigned_impl!(Usize, Isize);
+
+macro_rules! fp_widening_impl {
+    ($Name:ident, $T:ty, $Wide:ident, $W:ty) => {
+        impl<const B0: u32, const S0: i32> $Name<B0, S0> {
+            /// Multiply as `Mul` does, but return the product in the raw type of
+            /// twice the width, so that it may have more bits than this raw type
+            /// (e.g. `I32<20, 8> * I32<20, 8>` as an `I64<40, 16>`).  To promote
+            /// only when the product needs it, use [`Promote`](crate::Promote).
+            pub fn widening_mul<const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> crate::$Wide<{ B0 + B1 }, { S0 + S1 }> {
+                unsafe { crate::$Wide::new_unchecked(self.raw() as $W * other.raw() as $W) }
+            }
+            /// Return `self * b / c`, with the product formed exactly in the raw type of
+            /// twice the width, and then divided, rounding toward zero (as `Div`
+            /// does).  The quotient is no larger than the product, so it has the bits
+            /// of the product, and the shift of the product less that of `c`; narrow
+            /// it with `checked_cast` or `saturating_cast` where `c` is known to be
+            /// large enough (e.g. sensor counts scaled by a calibration ratio).
+            pub fn mul_div<const B1: u32, const S1: i32, const B2: u32, const S2: i32>(
+                self,
+                b: $Name<B1, S1>,
+                c: $Name<B2, S2>,
+            ) -> crate::$Wide<{ B0 + B1 }, { S0 + S1 - S2 }> {
+                let product = self.raw() as $W * b.raw() as $W;
+                // (the product is never MIN, so this cannot overflow)
+                unsafe { crate::$Wide::new_unchecked(product / c.raw() as $W) }
+            }
+            /// Divide as `Div` does, rounding toward zero, but with the dividend first
+            /// shifted left by `EXTRA` bits in the raw type of twice the width, so that
+            /// the quotient has `EXTRA` more fraction bits (e.g. `U32<12, 0>` divided
+            /// by `U32<12, 0>` with `EXTRA == 16` is a `U64<28, 16>`, rather than the
+            /// `U32<12, 0>` that `Div` gives).  Compilation will fail if the quotient
+            /// does not fit in the wide raw type.
+            pub fn div_precise<const EXTRA: u32, const B1: u32, const S1: i32>(
+                self,
+                other: $Name<B1, S1>,
+            ) -> crate::$Wide<{ B0 + EXTRA + Self::SIGNED as u32 }, { S0 - S1 + EXTRA as i32 }>
+            {
+                let () = Fits::<crate::$Wide<0, 0>, { B0 + EXTRA + Self::SIGNED as u32 }>::QUOTIENT;
+                let quotient = ((self.raw() as $W) << EXTRA) / other.raw() as $W;
+                unsafe { crate::$Wide::new_unchecked(quotient) }
+            }
+            /// Returns the number of bits needed to hold `x`, as a value of this
+            /// signedness.
+            const fn bits_needed(x: $W) -> u32 {
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                if x == 0 {
+                    0
+                } else if <$T>::MIN == 0 {
+                    <$W>::BITS - x.leading_zeros()
+                } else if x < 0 {
+                    <$W>::BITS + 1 - (!x).leading_zeros()
+                } else {
+                    <$W>::BITS + 1 - x.leading_zeros()
+                }
+            }
+            /// Returns the bit width of the return type from `mul_ratio`.
+            pub const fn mul_ratio_bits(num: $T, den: $T) -> u32 {
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let den_negative = den < 0;
+                assert!(!den_negative && den != 0, "denominator must be positive");
+                if B0 == 0 {
+                    return 0;
+                }
+                // the result is monotonic in x, so the extremes come from MIN and MAX
+                let shift = <$W>::BITS - B0;
+                let (min, max) = (<$W>::MIN >> shift, <$W>::MAX >> shift);
+                let (num, den) = (num as $W, den as $W);
+                let (a, b) = (
+                    Self::bits_needed(min * num / den),
+                    Self::bits_needed(max * num / den),
+                );
+                if a > b {
+                    a
+                } else {
+                    b
+                }
+            }
+            /// Multiply by the constant `NUM / DEN`, with the product formed exactly
+            /// in the raw type of twice the width, and then divided, rounding toward
+            /// zero (as `div_const` does).  The output has exactly as many bits as the
+            /// extremes of `Self` need after scaling: e.g. ADC counts to millivolts,
+            /// for a 12-bit ADC with a 3300 mV reference, is `mul_ratio::<3300, 4096>()`,
+            /// which has 12 bits.  Compilation will fail if `DEN` is not positive.
+            /// (To round to nearest, use `mul_rational_const`.)
+            pub fn mul_ratio<const NUM: $T, const DEN: $T>(
+                self,
+            ) -> $Name<{ Self::mul_ratio_bits(NUM, DEN) }, S0> {
+                let scaled = self.raw() as $W * NUM as $W / DEN as $W;
+                unsafe { $Name::new_unchecked(scaled as $T) }
+            }
+        }
+    };
+}
+
+fp_widening_impl!(I8, i8, I16, i16);
+fp_widening_impl!(U8, u8, U16, u16);
+fp_widening_impl!(I16, i16, I32, i32);
+fp_widening_impl!(U16, u16, U32, u32);
+fp_widening_impl!(I32, i32, I64, i64);
+fp_widening_impl!(U32, u32, U64, u64);
+fp_widening_impl!(I64, i64, I128, i128);
+fp_widening_impl!(U64, u64, U128, u128);
@@ -0,0 +1,114 @@
+use crate::Num;
+
+/// A small, fast, deterministic pseudo-random number generator
+/// (Vigna's xorshift64*), for generating noise and dither without
+/// an external dependency.  Not suitable for cryptographic use.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    /// Create a generator from `seed`.  The same seed always produces the
+    /// same sequence on every platform.  (A zero seed, which would otherwise
+    /// produce all zeros, is replaced by a fixed nonzero seed.)
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed },
+        }
+    }
+    /// Return the next 64 pseudo-random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl Default for Xorshift64 {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Draw a uniformly-distributed value from the full range of `Self`
+            /// (white noise), i.e. uniform between `Self::MIN` and `Self::MAX`.
+            pub fn random(rng: &mut Xorshift64) -> Self {
+                // Use the high bits of the generator output, which are the
+                // strongest bits of xorshift64*.  The shift sign-extends signed types.
+                let bits = if <$T>::BITS <= 64 {
+                    (rng.next_u64() >> (64 - <$T>::BITS)) as $T
+                } else {
+                    ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) as $T
+                };
+                if Self::BITS == 0 {
+                    Self::MIN
+                } else {
+                    // n.b. shifting by >= T::BITS is undefined for integer types!
+                    unsafe { Self::new_unchecked(bits >> (<$T>::BITS - Self::BITS)) }
+                }
+            }
+            /// Draw a value with a triangular probability distribution, by summing two
+            /// independent uniform draws from `Self`.  The result has one more bit.
+            /// For signed types this is the standard TPDF dither distribution, centered on zero.
+            pub fn random_tpdf(rng: &mut Xorshift64) -> $Name<{ B + 1 }, S>
+            where
+                [(); (B + 1) as usize]:,
+            {
+                let (a, b) = (Self::random(rng), Self::random(rng));
+                // use wrapping_add to ensure we don't do overflow checks
+                // (overflow safety is guaranteed by the type system)
+                unsafe { $Name::new_unchecked(a.raw().wrapping_add(b.raw())) }
+            }
+            /// Shift the raw value right by N bits, as `raw_shr` does, but round up
+            /// with probability proportional to the discarded bits (and down
+            /// otherwise), so that the result is unbiased on average.  The output
+            /// type is that of `raw_shr`, so a result which would round up past its
+            /// `MAX` is `MAX`.
+            pub fn raw_shr_stochastic<const N: u32>(
+                self,
+                rng: &mut Xorshift64,
+            ) -> $Name<{ B - N }, { S - N as i32 }>
+            where
+                [(); (B - N) as usize]:,
+                [(); (S - N as i32) as usize]:,
+            {
+                let raw = self.raw();
+                let down = raw >> N;
+                if N == 0 {
+                    return unsafe { $Name::new_unchecked(down) };
+                }
+                // compare the discarded bits with N uniformly-distributed bits
+                let discarded = raw as u128 & (u128::MAX >> (128 - N));
+                let threshold = if N <= 64 {
+                    (rng.next_u64() >> (64 - N)) as u128
+                } else {
+                    ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) >> (128 - N)
+                };
+                let max = $Name::<{ B - N }, { S - N as i32 }>::MAX.raw();
+                let up = threshold < discarded && down < max;
+                unsafe { $Name::new_unchecked(if up { down + 1 } else { down }) }
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+fp_impl!(I128, i128);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
@@ -0,0 +1,229 @@
+use core::marker::PhantomData;
+
+use crate::Num;
+
+/// Number of bytes needed to store `n` values of `bits` bits each.
+pub const fn packed_bytes(n: usize, bits: u32) -> usize {
+    (n * bits as usize).div_ceil(8)
+}
+
+/// Read the `width`-bit field starting at bit `bit` of `bytes` (in little-endian
+/// bit order).
+fn read_bits(bytes: &[u8], bit: usize, width: u32) -> u128 {
+    let (mut value, mut done) = (0, 0);
+    while done < width {
+        let pos = bit + done as usize;
+        let offset = (pos % 8) as u32;
+        let take = (8 - offset).min(width - done);
+        let field = (bytes[pos / 8] >> offset) as u128 & ((1 << take) - 1);
+        value |= field << done;
+        done += take;
+    }
+    value
+}
+
+/// Overwrite the `width`-bit field starting at bit `bit` of `bytes` with the low
+/// `width` bits of `value`.
+fn write_bits(bytes: &mut [u8], bit: usize, width: u32, value: u128) {
+    let mut done = 0;
+    while done < width {
+        let pos = bit + done as usize;
+        let offset = (pos % 8) as u32;
+        let take = (8 - offset).min(width - done);
+        let mask = (((1u16 << take) - 1) << offset) as u8;
+        let field = (((value >> done) as u8) << offset) & mask;
+        bytes[pos / 8] = (bytes[pos / 8] & !mask) | field;
+        done += take;
+    }
+}
+
+/// The low `F::BITS` bits of the raw value of `x`.
+fn pack<F: Num>(x: F) -> u128
+where
+    F::Raw: Into<i128>,
+{
+    let mask = if F::BITS == 0 {
+        0
+    } else {
+        u128::MAX >> (u128::BITS - F::BITS)
+    };
+    x.raw().into() as u128 & mask
+}
+
+/// The value whose low `F::BITS` raw bits are `bits`.
+fn unpack<F: Num>(bits: u128) -> F
+where
+    F::Raw: TryFrom<i128>,
+{
+    let raw = if F::SIGNED && F::BITS > 0 {
+        // sign-extend from the top stored bit
+        let unused = u128::BITS - F::BITS;
+        (bits << unused) as i128 >> unused
+    } else {
+        bits as i128
+    };
+    unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) }
+}
+
+/// Growable vector of fixed-point numbers, each stored in exactly `F::BITS` bits
+/// (so, e.g., two 12-bit samples occupy three bytes, rather than the four bytes
+/// of two `i16`).  Elements are packed in little-endian bit order with no padding
+/// between them.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PackedVec<F: Num> {
+    bytes: Vec<u8>,
+    len: usize,
+    _value: PhantomData<F>,
+}
+
+impl<F: Num> PackedVec<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// Create an empty vector.
+    pub fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            len: 0,
+            _value: PhantomData,
+        }
+    }
+    /// Create an empty vector with space for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(packed_bytes(capacity, F::BITS)),
+            len: 0,
+            _value: PhantomData,
+        }
+    }
+    /// Return the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Return true if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Return the packed storage (with any unused bits of the final byte zeroed).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+    /// Return element `i`, or `None` if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> Option<F> {
+        (i < self.len).then(|| unpack(read_bits(&self.bytes, i * F::BITS as usize, F::BITS)))
+    }
+    /// Replace element `i` with `x`, returning the previous value, or return
+    /// `None` (leaving the vector unchanged) if `i` is out of bounds.
+    pub fn set(&mut self, i: usize, x: F) -> Option<F> {
+        let old = self.get(i)?;
+        write_bits(&mut self.bytes, i * F::BITS as usize, F::BITS, pack(x));
+        Some(old)
+    }
+    /// Append `x` to the end of the vector.
+    pub fn push(&mut self, x: F) {
+        self.len += 1;
+        self.bytes.resize(packed_bytes(self.len, F::BITS), 0);
+        write_bits(
+            &mut self.bytes,
+            (self.len - 1) * F::BITS as usize,
+            F::BITS,
+            pack(x),
+        );
+    }
+    /// Remove and return the last element, or `None` if the vector is empty.
+    pub fn pop(&mut self) -> Option<F> {
+        let last = self.get(self.len.checked_sub(1)?)?;
+        write_bits(
+            &mut self.bytes,
+            (self.len - 1) * F::BITS as usize,
+            F::BITS,
+            0,
+        );
+        self.len -= 1;
+        self.bytes.truncate(packed_bytes(self.len, F::BITS));
+        Some(last)
+    }
+    /// Iterate over the elements.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = F> + '_ {
+        (0..self.len).map(|i| unpack(read_bits(&self.bytes, i * F::BITS as usize, F::BITS)))
+    }
+}
+
+impl<F: Num> FromIterator<F> for PackedVec<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        let mut v = Self::new();
+        v.extend(iter);
+        v
+    }
+}
+
+impl<F: Num> Extend<F> for PackedVec<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    fn extend<I: IntoIterator<Item = F>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Fixed-length array of `N` fixed-point numbers, each stored in exactly
+/// `F::BITS` bits; see [`PackedVec`].  The storage is inline, so this is
+/// suitable for static buffers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackedArray<F: Num, const N: usize>
+where
+    [(); packed_bytes(N, F::BITS)]:,
+{
+    bytes: [u8; packed_bytes(N, F::BITS)],
+    _value: PhantomData<F>,
+}
+
+impl<F: Num, const N: usize> PackedArray<F, N>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+    [(); packed_bytes(N, F::BITS)]:,
+{
+    /// Create an array with every element set to `x`.
+    pub fn new(x: F) -> Self {
+        let mut array = Self {
+            bytes: [0; packed_bytes(N, F::BITS)],
+            _value: PhantomData,
+        };
+        for i in 0..N {
+            array.set(i, x);
+        }
+        array
+    }
+    /// Return the number of elements, `N`.
+    pub const fn len(&self) -> usize {
+        N
+    }
+    /// Return true if `N` is zero.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+    /// Return the packed storage (with any unused bits of the final byte zeroed).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+    /// Return element `i`, or `None` if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> Option<F> {
+        (i < N).then(|| unpack(read_bits(&self.bytes, i * F::BITS as usize, F::BITS)))
+    }
+    /// Replace element `i` with `x`, returning the previous value, or return
+    /// `None` (leaving the array unchanged) if `i` is out of bounds.
+    pub fn set(&mut self, i: usize, x: F) -> Option<F> {
+        let old = self.get(i)?;
+        write_bits(&mut self.bytes, i * F::BITS as usize, F::BITS, pack(x));
+        Some(old)
+    }
+    /// Iterate over the elements.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = F> + '_ {
+        (0..N).map(|i| unpack(read_bits(&self.bytes, i * F::BITS as usize, F::BITS)))
+    }
+}
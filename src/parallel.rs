@@ -0,0 +1,191 @@
+use core::ops::{Add, Range};
+
+use crate::{
+    clog2,
+    convolve::{check_acc, convolve_raw, convolve_with},
+    sum::tree_sum_raw,
+    Convert, ConvolveMode, Num, RangeError, SliceError, Welford,
+};
+
+/// Ranges shorter than this are not worth splitting across threads.
+const MIN_CHUNK_LEN: usize = 4096;
+
+/// Split `0..len` into one contiguous range per available thread (but none
+/// shorter than `MIN_CHUNK_LEN`), apply `f` to each range on its own thread, and
+/// return the per-range results combined in order with `combine`, or `None` if
+/// `len` is zero.
+fn par_ranges<R: Send>(
+    len: usize,
+    f: impl Fn(Range<usize>) -> R + Sync,
+    combine: impl Fn(R, R) -> R,
+) -> Option<R> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_len = len.div_ceil(threads).max(MIN_CHUNK_LEN);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..len)
+            .step_by(chunk_len)
+            .map(|start| {
+                let f = &f;
+                scope.spawn(move || f(start..len.min(start + chunk_len)))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .reduce(combine)
+    })
+}
+
+/// As `par_ranges`, over contiguous chunks of `xs`.
+fn par_chunks<T: Sync, R: Send>(
+    xs: &[T],
+    f: impl Fn(&[T]) -> R + Sync,
+    combine: impl Fn(R, R) -> R,
+) -> Option<R> {
+    par_ranges(xs.len(), |range| f(&xs[range]), combine)
+}
+
+/// Concatenate two vectors, in order.
+fn concat<T>(mut a: Vec<T>, b: Vec<T>) -> Vec<T> {
+    a.extend(b);
+    a
+}
+
+/// Multi-threaded version of [`tree_sum_slice`](crate::tree_sum_slice).
+/// The slice is split into one contiguous chunk per thread; each chunk is
+/// summed by pairwise reduction and the partial sums are then added together.
+/// The overflow guarantee and the result are identical to `tree_sum_slice`.
+pub fn par_tree_sum_slice<F, Acc>(xs: &[F]) -> Result<Acc, RangeError>
+where
+    F: Num + Sync,
+    F::Raw: Default + Send + Add<Output = F::Raw>,
+    Acc: Num<Raw = F::Raw>,
+{
+    let () = Convert::<F, Acc>::SAME_SCALE;
+    if F::BITS + clog2(xs.len()) > Acc::BITS {
+        return Err(RangeError::TooLarge);
+    }
+    let sum = par_chunks(xs, tree_sum_raw, |a, b| a + b).unwrap_or_default();
+    Ok(unsafe { Acc::new_unchecked(sum) })
+}
+
+/// Convert every element of `xs` to `f64`, as `into_f64` does, on multiple
+/// threads.
+pub fn par_into_f64_slice<F: Num + Sync>(xs: &[F]) -> Vec<f64> {
+    par_chunks(xs, |c| c.iter().map(|x| x.into_f64()).collect(), concat).unwrap_or_default()
+}
+
+/// Convert every element of `xs` to `F`, as `from_f64` does, on multiple
+/// threads, or return the `RangeError` of the first element which is out of
+/// the range of `F`.
+pub fn par_from_f64_slice<F: Num + Send>(xs: &[f64]) -> Result<Vec<F>, RangeError> {
+    par_chunks(
+        xs,
+        |c| c.iter().map(|&x| F::from_f64(x)).collect(),
+        |a, b| Ok(concat(a?, b?)),
+    )
+    .unwrap_or(Ok(Vec::new()))
+}
+
+/// Dot product of two slices, accumulated exactly on multiple threads into the
+/// caller's choice of accumulator type `Acc`, as [`dot`](crate::dot) does for
+/// arrays.  `Acc` must have shift `A::SHIFT + B::SHIFT`, and must be signed if
+/// either input is signed (or compilation will fail).  Returns
+/// `SliceError::LengthMismatch` if the slices have different lengths, or
+/// `SliceError::Range` if `Acc` is not guaranteed to hold the sum (i.e. if
+/// `A::BITS + B::BITS + clog2(a.len())` exceeds `Acc::BITS`, or 127).
+pub fn par_dot<A, B, Acc: Num>(a: &[A], b: &[B]) -> Result<Acc, SliceError>
+where
+    A: Num + Sync,
+    B: Num + Sync,
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+    Acc::Raw: TryFrom<i128>,
+{
+    if a.len() != b.len() {
+        return Err(SliceError::LengthMismatch);
+    }
+    check_acc::<A, B, Acc>(a.len())?;
+    // the products and their partial sums fit in `Acc`, so this cannot overflow
+    let sum = par_ranges(
+        a.len(),
+        |range| {
+            let (a, b) = (&a[range.clone()], &b[range]);
+            a.iter()
+                .zip(b)
+                .map(|(x, y)| x.raw().into() * y.raw().into())
+                .sum()
+        },
+        |x: i128, y| x + y,
+    )
+    .unwrap_or(0);
+    Ok(unsafe { Acc::new_unchecked(sum.try_into().ok().unwrap()) })
+}
+
+/// Multi-threaded version of [`convolve`](crate::convolve), with the same
+/// accumulator requirements and results.  The outputs are split into one
+/// contiguous range per thread.
+pub fn par_convolve<A, B, Acc>(a: &[A], b: &[B], mode: ConvolveMode) -> Result<Vec<Acc>, RangeError>
+where
+    A: Num,
+    B: Num,
+    Acc: Num + Send,
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+    Acc::Raw: TryFrom<i128>,
+{
+    convolve_with(a, b, mode, false, par_convolve_raw)
+}
+
+/// Multi-threaded version of [`correlate`](crate::correlate), with the same
+/// accumulator requirements and results.
+pub fn par_correlate<A, B, Acc>(
+    a: &[A],
+    b: &[B],
+    mode: ConvolveMode,
+) -> Result<Vec<Acc>, RangeError>
+where
+    A: Num,
+    B: Num,
+    Acc: Num + Send,
+    A::Raw: Into<i128>,
+    B::Raw: Into<i128>,
+    Acc::Raw: TryFrom<i128>,
+{
+    convolve_with(a, b, mode, true, par_convolve_raw)
+}
+
+fn par_convolve_raw<Acc: Num + Send>(a: &[i128], b: &[i128], range: Range<usize>) -> Vec<Acc>
+where
+    Acc::Raw: TryFrom<i128>,
+{
+    let start = range.start;
+    par_ranges(
+        range.len(),
+        |r| convolve_raw(a, b, start + r.start..start + r.end),
+        concat,
+    )
+    .unwrap_or_default()
+}
+
+/// Accumulate the samples of `xs` into a [`Welford`] accumulator on multiple
+/// threads, merging the accumulators of contiguous chunks.  Every statistic of
+/// the result is identical to that of pushing the samples in order.
+pub fn par_welford<F: Num + Send + Sync>(xs: &[F]) -> Welford<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    par_chunks(
+        xs,
+        |c| {
+            let mut w = Welford::new();
+            c.iter().for_each(|&x| w.push(x));
+            w
+        },
+        |mut a, b| {
+            a.merge(&b);
+            a
+        },
+    )
+    .unwrap_or_default()
+}
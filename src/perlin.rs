@@ -0,0 +1,84 @@
+use crate::{Num, I32};
+
+/// Perlin gradient noise in one and two dimensions, for procedural generation.
+///
+/// Coordinates are in Q16.16 (`I32<32, 16>`), with the noise lattice at integer
+/// coordinates; output is in `I32<18, 16>` and lies between -1 and 1.  Only
+/// integer arithmetic with fully specified rounding is used, so the output is
+/// bit-identical on every platform for a given seed, as needed for lockstep
+/// simulation and replays.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Perlin {
+    seed: u32,
+}
+
+/// Hash lattice point `(x, y)` with `seed`, with the finalizer from MurmurHash3.
+fn hash(seed: u32, x: i32, y: i32) -> u32 {
+    let mut h = seed ^ (x as u32).wrapping_mul(0x27d4_eb2d) ^ (y as u32).wrapping_mul(0x1656_67b1);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2_ae35);
+    h ^ (h >> 16)
+}
+
+/// Perlin's quintic fade curve `6t^5 - 15t^4 + 10t^3`, in Q16 for `0 <= t < 1`.
+fn fade(t: i64) -> i64 {
+    let t2 = (t * t) >> 16;
+    let t3 = (t2 * t) >> 16;
+    // t^3 (t (6t - 15) + 10)
+    let inner = ((t * ((6 * t) - (15 << 16))) >> 16) + (10 << 16);
+    (t3 * inner) >> 16
+}
+
+/// Linear interpolation `a + t (b - a)`, in Q16.
+fn lerp(t: i64, a: i64, b: i64) -> i64 {
+    a + (((b - a) * t) >> 16)
+}
+
+/// Split a Q16.16 coordinate into its integer cell and its Q16 offset within the cell.
+fn split(x: I32<32, 16>) -> (i32, i64) {
+    (x.raw() >> 16, (x.raw() & 0xffff) as i64)
+}
+
+impl Perlin {
+    /// Create a noise generator.  Different seeds give uncorrelated noise fields.
+    pub const fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+    /// One-dimensional noise at `x`.
+    pub fn noise1(&self, x: I32<32, 16>) -> I32<18, 16> {
+        let (i, t) = split(x);
+        // gradients uniform in [-1, 1), in Q16
+        let grad = |i| (hash(self.seed, i, 0) as i32 >> 15) as i64;
+        let a = (grad(i) * t) >> 16;
+        let b = (grad(i.wrapping_add(1)) * (t - (1 << 16))) >> 16;
+        // the magnitude before scaling is at most 1/2
+        Self::output(2 * lerp(fade(t), a, b))
+    }
+    /// Two-dimensional noise at `(x, y)`.
+    pub fn noise2(&self, x: I32<32, 16>, y: I32<32, 16>) -> I32<18, 16> {
+        let ((i, tx), (j, ty)) = (split(x), split(y));
+        // the four diagonal and four axis-aligned gradients
+        let dot = |i, j, dx: i64, dy: i64| match hash(self.seed, i, j) & 7 {
+            0 => dx + dy,
+            1 => dx - dy,
+            2 => -dx + dy,
+            3 => -dx - dy,
+            4 => dx,
+            5 => -dx,
+            6 => dy,
+            _ => -dy,
+        };
+        let (i1, j1) = (i.wrapping_add(1), j.wrapping_add(1));
+        let (tx1, ty1) = (tx - (1 << 16), ty - (1 << 16));
+        let (u, v) = (fade(tx), fade(ty));
+        let lower = lerp(u, dot(i, j, tx, ty), dot(i1, j, tx1, ty));
+        let upper = lerp(u, dot(i, j1, tx, ty1), dot(i1, j1, tx1, ty1));
+        Self::output(lerp(v, lower, upper))
+    }
+    fn output(raw: i64) -> I32<18, 16> {
+        // Rounding in the interpolation could in principle step just outside [-1, 1].
+        I32::new(raw.clamp(-1 << 16, 1 << 16) as i32).unwrap()
+    }
+}
@@ -0,0 +1,161 @@
+//! Composition of signal-processing stages into a single pipeline, with the
+//! format of each stage's output checked against its successor's input at
+//! compile time.
+
+use crate::{
+    Complex, DcBlocker, DelayLine, Hilbert, LevelMeter, MedianFilter, Num, SigmaDelta,
+    SlidingMinMax, SoftClip, StateSpace, U8,
+};
+
+/// A stage of a signal-processing pipeline, which maps each input sample of type
+/// `In` to an output sample of type [`Output`](Self::Output).
+///
+/// Stages are chained with [`then`](Self::then), which only accepts a next stage
+/// whose input type is exactly this stage's output type, so a mismatch in bits or
+/// shift between stages is a compile error rather than a bug.  Where the formats
+/// differ by design, an explicit conversion stage (a closure in a [`FnBlock`],
+/// e.g. `FnBlock(|x: I16<13, 8>| x.saturate::<12>())`) makes the change visible:
+///
+/// ```
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use fp::*;
+/// let mut pipeline = DcBlocker::<I16<12, 8>, 8>::new()
+///     .then(FnBlock(|x: I16<13, 8>| x.saturate::<12>()))
+///     .then(MedianFilter::<I16<12, 8>, 3>::new(I16::new(0).unwrap()));
+/// let y: I16<12, 8> = pipeline.process(I16::new(100).unwrap());
+/// assert_eq!(y.raw(), 0); // the median of 0, 0 and 100
+/// ```
+pub trait Block<In> {
+    type Output;
+    /// Process one sample.
+    fn process(&mut self, x: In) -> Self::Output;
+    /// Return a stage which feeds the output of `self` into `next`.
+    fn then<B: Block<Self::Output>>(self, next: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+    {
+        Chain {
+            first: self,
+            second: next,
+        }
+    }
+}
+
+/// Two stages in series, as returned by [`Block::then`].
+#[derive(Clone, Debug)]
+pub struct Chain<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<In, A: Block<In>, B: Block<A::Output>> Block<In> for Chain<A, B> {
+    type Output = B::Output;
+    fn process(&mut self, x: In) -> B::Output {
+        self.second.process(self.first.process(x))
+    }
+}
+
+/// A stage that applies a function (or closure) to each sample, e.g. to convert
+/// between the formats of two other stages.
+#[derive(Clone, Debug)]
+pub struct FnBlock<F>(pub F);
+
+impl<In, Out, F: FnMut(In) -> Out> Block<In> for FnBlock<F> {
+    type Output = Out;
+    fn process(&mut self, x: In) -> Out {
+        (self.0)(x)
+    }
+}
+
+impl<F: Num, const K: u32> Block<F> for DcBlocker<F, K>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+    [(); (F::BITS + 1) as usize]:,
+    [(); F::SHIFT as usize]:,
+{
+    type Output = F::Output<{ F::BITS + 1 }, { F::SHIFT }>;
+    fn process(&mut self, x: F) -> Self::Output {
+        DcBlocker::process(self, x)
+    }
+}
+
+/// A delay of `N` samples.
+impl<F: Num, const N: usize> Block<F> for DelayLine<F, N> {
+    type Output = F;
+    fn process(&mut self, x: F) -> F {
+        self.push(x)
+    }
+}
+
+impl<F: Num, const N: usize> Block<F> for Hilbert<F, N>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+{
+    type Output = Complex<F>;
+    fn process(&mut self, x: F) -> Complex<F> {
+        Hilbert::process(self, x)
+    }
+}
+
+impl<F: Num, const N: usize> Block<F> for LevelMeter<F, N>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+    [(); (F::BITS + 1) as usize]:,
+{
+    type Output = bool;
+    fn process(&mut self, x: F) -> bool {
+        LevelMeter::process(self, x)
+    }
+}
+
+impl<F: Num, const N: usize> Block<F> for MedianFilter<F, N> {
+    type Output = F;
+    fn process(&mut self, x: F) -> F {
+        MedianFilter::process(self, x)
+    }
+}
+
+impl<F: Num, const OUT_BITS: u32> Block<F> for SigmaDelta<F, OUT_BITS>
+where
+    F::Raw: Into<i64>,
+{
+    type Output = U8<OUT_BITS, 0>;
+    fn process(&mut self, x: F) -> U8<OUT_BITS, 0> {
+        SigmaDelta::process(self, x)
+    }
+}
+
+impl<F: Num, const N: usize> Block<F> for SlidingMinMax<F, N> {
+    type Output = (F, F);
+    fn process(&mut self, x: F) -> (F, F) {
+        SlidingMinMax::process(self, x)
+    }
+}
+
+impl<F: Num> Block<F> for SoftClip<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+    [(); (F::SHIFT + 2) as u32 as usize]:,
+{
+    type Output = F::Output<{ (F::SHIFT + 2) as u32 }, { F::SHIFT }>;
+    fn process(&mut self, x: F) -> Self::Output {
+        SoftClip::process(self, x)
+    }
+}
+
+impl<T, X, F, const NX: usize, const NU: usize, const NY: usize> Block<[F; NU]>
+    for StateSpace<T, X, F, NX, NU, NY>
+where
+    T: Num,
+    X: Num,
+    F: Num,
+    T::Raw: Into<i128>,
+    X::Raw: Into<i128> + TryFrom<i128>,
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    type Output = [F; NY];
+    fn process(&mut self, u: [F; NU]) -> [F; NY] {
+        self.update(u)
+    }
+}
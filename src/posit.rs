@@ -0,0 +1,210 @@
+use crate::{bfp::round_shr, Num, RangeError};
+
+/// A posit (type III unum) with `N` bits, of which up to `ES` are exponent bits,
+/// stored as its bit pattern in the low `N` bits of a `u64`.
+///
+/// This type only converts between posits and fixed-point numbers; arithmetic
+/// on posits is left to a posit library, e.g. by way of [`to_bits`](Self::to_bits)
+/// and [`from_bits`](Self::from_bits) with the `softposit` crate.  Conversions are
+/// exact wherever the destination can hold the value, and otherwise round:
+///
+/// * [`from_fp`](Self::from_fp) rounds to nearest, with ties to even, as the posit
+///   standard specifies.  Values beyond the largest posit round to the largest
+///   posit, and nonzero values below the smallest posit round to the smallest
+///   posit (never to zero, nor to NaR).
+/// * [`to_fp`](Self::to_fp) rounds to nearest, with ties away from zero, and
+///   returns an error if the result is out of the range of the fixed-point type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Posit<const N: u32, const ES: u32>(u64);
+
+/// 8-bit posit, with the exponent size of `softposit`'s `posit8_t`.
+pub type P8 = Posit<8, 0>;
+/// 16-bit posit, with the exponent size of `softposit`'s `posit16_t`.
+pub type P16 = Posit<16, 1>;
+/// 32-bit posit, with the exponent size of `softposit`'s `posit32_t`.
+pub type P32 = Posit<32, 2>;
+
+/// Error converting a [`Posit`] to a fixed-point number.
+#[derive(Debug, Eq, PartialEq)]
+pub enum PositError {
+    /// The posit is NaR ("not a real").
+    NaR,
+    TooSmall,
+    TooLarge,
+}
+
+impl From<RangeError> for PositError {
+    fn from(err: RangeError) -> Self {
+        match err {
+            RangeError::TooSmall => PositError::TooSmall,
+            RangeError::TooLarge => PositError::TooLarge,
+        }
+    }
+}
+
+impl<const N: u32, const ES: u32> Posit<N, ES> {
+    const CHECK: () = {
+        assert!(N >= 3 && N <= 64, "posits must have 3 to 64 bits");
+        assert!(ES <= 4, "posits must have at most 4 exponent bits");
+    };
+    /// Mask of the `N` bits of the bit pattern.
+    const MASK: u64 = u64::MAX >> (64 - N);
+    /// Zero.
+    pub const ZERO: Self = Self(0);
+    /// NaR ("not a real"), the single exceptional value.
+    pub const NAR: Self = Self(1 << (N - 1));
+    /// The largest finite posit, `2^((N - 2) 2^ES)`.
+    pub const MAX: Self = Self((1 << (N - 1)) - 1);
+    /// The smallest positive posit, `2^(-(N - 2) 2^ES)`.
+    pub const MIN_POSITIVE: Self = Self(1);
+    /// Interpret the low `N` bits of `bits` as a posit (the high bits are ignored).
+    pub const fn from_bits(bits: u64) -> Self {
+        let () = Self::CHECK;
+        Self(bits & Self::MASK)
+    }
+    /// Return the bit pattern of the posit, in the low `N` bits.
+    pub const fn to_bits(self) -> u64 {
+        self.0
+    }
+    /// Return true if the posit is NaR.
+    pub const fn is_nar(self) -> bool {
+        self.0 == Self::NAR.0
+    }
+    /// Decode a nonzero, finite posit into its sign and its magnitude
+    /// `(2^64 + fraction) * 2^(scale - 64)`.
+    fn decode(self) -> (bool, i32, u64) {
+        let negative = self.0 >> (N - 1) == 1;
+        let bits = if negative {
+            self.0.wrapping_neg() & Self::MASK
+        } else {
+            self.0
+        };
+        // the N - 1 bits after the sign bit, left-aligned
+        let body = bits << (65 - N);
+        let (run, regime) = if body >> 63 == 1 {
+            let run = body.leading_ones();
+            (run, run as i32 - 1)
+        } else {
+            let run = body.leading_zeros();
+            (run, -(run as i32))
+        };
+        // skip the regime and its terminating bit; bits past the end are zero
+        let rest = body.checked_shl(run + 1).unwrap_or(0);
+        let (exponent, fraction) = match ES {
+            0 => (0, rest),
+            _ => ((rest >> (64 - ES)) as i32, rest << ES),
+        };
+        (negative, (regime << ES) + exponent, fraction)
+    }
+    /// Return the posit nearest to `val`.
+    pub fn from_fp<F: Num>(val: F) -> Self
+    where
+        F::Raw: Into<i128>,
+    {
+        let () = Self::CHECK;
+        let raw: i128 = val.raw().into();
+        if raw == 0 {
+            return Self::ZERO;
+        }
+        let mut magnitude = raw.unsigned_abs();
+        // the width of the fraction, and the scale of the leading one
+        let width = 127 - magnitude.leading_zeros();
+        let scale = width as i32 - F::SHIFT;
+        magnitude ^= 1 << width;
+        // keep at most 96 bits of fraction, folding the rest into a sticky bit
+        // (the posit has at most 63 bits below its sign, so this cannot affect
+        // the rounding)
+        let (fraction, width) = if width > 96 {
+            let k = width - 96;
+            let sticky = magnitude & ((1 << k) - 1) != 0;
+            (magnitude >> k | sticky as u128, 96)
+        } else {
+            (magnitude, width)
+        };
+        let regime = scale >> ES;
+        let exponent = (scale - (regime << ES)) as u128;
+        // the regime bits (a run of ones ended by a zero, or of zeros ended by a
+        // one), which leave `room` bits for the exponent and fraction
+        let body_bits = N - 1;
+        let body = if regime >= 0 {
+            if regime as u32 + 1 >= body_bits {
+                return Self::signed(raw < 0, Self::MAX.0);
+            }
+            let run = regime as u32 + 1;
+            let room = body_bits - run - 1;
+            Self::round_body(
+                (((1 << run) - 1) << 1) << room,
+                room,
+                exponent,
+                fraction,
+                width,
+            )
+        } else {
+            let run = regime.unsigned_abs();
+            if run + 1 > body_bits {
+                return Self::signed(raw < 0, Self::MIN_POSITIVE.0);
+            }
+            let room = body_bits - run - 1;
+            Self::round_body(1 << room, room, exponent, fraction, width)
+        };
+        Self::signed(raw < 0, body.min(Self::MAX.0))
+    }
+    /// Append the exponent and the `width`-bit fraction to the regime bits in
+    /// `prefix`, rounding them to nearest even at `room` bits.
+    fn round_body(prefix: u64, room: u32, exponent: u128, fraction: u128, width: u32) -> u64 {
+        let tail_bits = ES + width;
+        let tail = exponent << width | fraction;
+        if tail_bits <= room {
+            return prefix | (tail << (room - tail_bits)) as u64;
+        }
+        let k = tail_bits - room;
+        let kept = prefix | (tail >> k) as u64;
+        let (dropped, half) = (tail & ((1 << k) - 1), 1 << (k - 1));
+        if dropped > half || (dropped == half && kept & 1 == 1) {
+            // a carry out of the fraction correctly increments the exponent or regime
+            kept + 1
+        } else {
+            kept
+        }
+    }
+    /// The posit with the given sign and positive bit pattern.
+    fn signed(negative: bool, bits: u64) -> Self {
+        Self(if negative {
+            bits.wrapping_neg() & Self::MASK
+        } else {
+            bits
+        })
+    }
+    /// Return the posit as an `F`, rounded to nearest with ties away from zero, or
+    /// a `PositError` if it is NaR or out of the range of `F`.
+    pub fn to_fp<F: Num>(self) -> Result<F, PositError>
+    where
+        F::Raw: TryFrom<i128>,
+    {
+        let () = Self::CHECK;
+        if self.0 == 0 {
+            return Ok(F::new(0i128.try_into().ok().unwrap())?);
+        } else if self.is_nar() {
+            return Err(PositError::NaR);
+        }
+        let (negative, scale, fraction) = self.decode();
+        let out_of_range = if negative {
+            PositError::TooSmall
+        } else {
+            PositError::TooLarge
+        };
+        // the magnitude is `significand * 2^k`
+        let significand = (1u128 << 64 | fraction as u128) >> fraction.trailing_zeros();
+        let k = scale - 64 + fraction.trailing_zeros() as i32 + F::SHIFT;
+        let magnitude = if k >= 0 {
+            if 128 - significand.leading_zeros() + k as u32 > 127 {
+                return Err(out_of_range);
+            }
+            (significand << k) as i128
+        } else {
+            round_shr(significand as i128, k.unsigned_abs())
+        };
+        let raw = if negative { -magnitude } else { magnitude };
+        Ok(F::new(raw.try_into().map_err(|_| out_of_range)?)?)
+    }
+}
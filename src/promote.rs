@@ -0,0 +1,125 @@
+use core::ops::{Add, Mul};
+
+use crate::add_sub::max;
+use crate::Num;
+
+/// Wrapper which opts in to automatic raw-type promotion.  Arithmetic on
+/// `Promote`d values works as it does on the inner values, except that when the
+/// number of bits in a result would exceed the width of the raw type, the result
+/// is promoted to the narrowest raw type which can hold it (so the product of two
+/// `Promote<I32<20, _>>` is a `Promote<I64<40, _>>`, rather than a compile error).
+/// Compilation still fails if the result would not fit in 128 bits.
+///
+/// Both operands must have the same signedness, but may have different raw types.
+/// Results are never narrowed: the raw type of a result is at least as wide as
+/// those of its operands.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Promote<F: Num>(pub F);
+
+/// Returns the width of the raw type of a result with `bits` bits, computed from
+/// operands whose raw type is `raw_bits` wide.
+pub const fn promoted_width(raw_bits: u32, bits: u32) -> u32 {
+    let mut width = raw_bits;
+    while width < bits {
+        width *= 2;
+    }
+    width
+}
+
+/// Marker type for a raw type of `W` bits.
+pub struct Width<const W: u32>;
+
+/// Maps a width to the signed fixed-point type with a raw type of that width.
+pub trait SignedWidth {
+    type Fp<const B: u32, const S: i32>: Num<Raw: TryFrom<i128>>;
+}
+
+/// Maps a width to the unsigned fixed-point type with a raw type of that width.
+pub trait UnsignedWidth {
+    type Fp<const B: u32, const S: i32>: Num<Raw: TryFrom<u128>>;
+}
+
+macro_rules! width_impl {
+    ($W:literal, $Iname:ident, $Uname:ident) => {
+        impl SignedWidth for Width<$W> {
+            type Fp<const B: u32, const S: i32> = crate::$Iname<B, S>;
+        }
+        impl UnsignedWidth for Width<$W> {
+            type Fp<const B: u32, const S: i32> = crate::$Uname<B, S>;
+        }
+    };
+}
+
+width_impl!(8, I8, U8);
+width_impl!(16, I16, U16);
+width_impl!(32, I32, U32);
+width_impl!(64, I64, U64);
+width_impl!(128, I128, U128);
+
+// Every operation is computed in the widest raw type of the right signedness
+// (which the optimizer narrows, since the operands are sign- or zero-extended),
+// then stored into the promoted raw type, which is guaranteed to hold the result.
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty, $Rhs:ident, $R:ty, $Wide:ty, $Width:ident) => {
+        impl<const B0: u32, const B1: u32, const S0: i32, const S1: i32>
+            Mul<Promote<crate::$Rhs<B1, S1>>> for Promote<crate::$Name<B0, S0>>
+        where
+            [(); (B0 + B1) as usize]:,
+            [(); (S0 + S1) as usize]:,
+            Width<{ promoted_width(max(<$T>::BITS, <$R>::BITS), B0 + B1) }>: $Width,
+        {
+            type Output = Promote<
+                <Width<{ promoted_width(max(<$T>::BITS, <$R>::BITS), B0 + B1) }> as $Width>::Fp<
+                    { B0 + B1 },
+                    { S0 + S1 },
+                >,
+            >;
+            fn mul(self, other: Promote<crate::$Rhs<B1, S1>>) -> Self::Output {
+                let product = self.0.raw() as $Wide * other.0.raw() as $Wide;
+                Promote(unsafe { Num::new_unchecked(product.try_into().ok().unwrap()) })
+            }
+        }
+        impl<const B0: u32, const B1: u32, const S: i32> Add<Promote<crate::$Rhs<B1, S>>>
+            for Promote<crate::$Name<B0, S>>
+        where
+            [(); (max(B0, B1) + 1) as usize]:,
+            Width<{ promoted_width(max(<$T>::BITS, <$R>::BITS), max(B0, B1) + 1) }>: $Width,
+        {
+            type Output = Promote<
+                <Width<{ promoted_width(max(<$T>::BITS, <$R>::BITS), max(B0, B1) + 1) }> as $Width>::Fp<
+                    { max(B0, B1) + 1 },
+                    S,
+                >,
+            >;
+            fn add(self, other: Promote<crate::$Rhs<B1, S>>) -> Self::Output {
+                // use wrapping_add to ensure we don't do overflow checks
+                // (overflow safety is guaranteed by the type system)
+                let sum = (self.0.raw() as $Wide).wrapping_add(other.0.raw() as $Wide);
+                Promote(unsafe { Num::new_unchecked(sum.try_into().ok().unwrap()) })
+            }
+        }
+    };
+}
+
+// Implement the operators for every pair of types in a list.
+macro_rules! fp_impls {
+    ($Wide:ty, $Width:ident, $all:tt, $($Name:ident $T:ty),*) => {
+        $( fp_impls!(@rhs $Wide, $Width, $Name $T, $all); )*
+    };
+    (@rhs $Wide:ty, $Width:ident, $Name:ident $T:ty, [$($Rhs:ident $R:ty),*]) => {
+        $( fp_impl!($Name, $T, $Rhs, $R, $Wide, $Width); )*
+    };
+}
+
+fp_impls!(
+    i128,
+    SignedWidth,
+    [I8 i8, I16 i16, I32 i32, I64 i64, I128 i128],
+    I8 i8, I16 i16, I32 i32, I64 i64, I128 i128
+);
+fp_impls!(
+    u128,
+    UnsignedWidth,
+    [U8 u8, U16 u16, U32 u32, U64 u64, U128 u128],
+    U8 u8, U16 u16, U32 u32, U64 u64, U128 u128
+);
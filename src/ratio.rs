@@ -0,0 +1,103 @@
+use crate::{exprs::round_up, Num, RangeError, Rounding, RoundingMode};
+
+/// Return the logical value of `x` as an exact fraction `(numer, denom)` in lowest
+/// terms, with `denom` positive (and a power of two), or `None` if the numerator
+/// or the denominator does not fit in an `i128`.
+pub fn to_ratio<F: Num>(x: F) -> Option<(i128, i128)>
+where
+    F::Raw: Into<i128>,
+{
+    let raw: i128 = x.raw().into();
+    let shift = F::SHIFT;
+    if raw == 0 {
+        Some((0, 1))
+    } else if shift <= 0 {
+        let k = shift.unsigned_abs();
+        // shifting back must recover `raw`, or the numerator overflowed
+        (k < 128)
+            .then(|| raw << k)
+            .filter(|&n| n >> k == raw)
+            .map(|n| (n, 1))
+    } else {
+        let tz = raw.trailing_zeros().min(shift as u32);
+        let k = shift as u32 - tz;
+        (k < 127).then(|| (raw >> tz, 1 << k))
+    }
+}
+
+/// `floor(numer * 2^t / denom)`, or `None` if it does not fit in a `u128`, along with
+/// whether the quotient is exact.
+pub(crate) const fn div_pow2(numer: u128, denom: u128, t: i32) -> Option<(u128, bool)> {
+    let (q, mut r) = (numer / denom, numer % denom);
+    if t < 0 {
+        let k = t.unsigned_abs();
+        if k >= 128 {
+            return Some((0, q == 0 && r == 0));
+        }
+        return Some((q >> k, r == 0 && q & ((1 << k) - 1) == 0));
+    }
+    let t = t as u32;
+    if q != 0 && u128::BITS - q.leading_zeros() + t > u128::BITS {
+        return None;
+    }
+    let mut quotient = if q == 0 { 0 } else { q << t };
+    let mut i = t;
+    while i > 0 {
+        i -= 1;
+        // r < denom <= 2^127, so this cannot overflow
+        r <<= 1;
+        if r >= denom {
+            if i >= u128::BITS {
+                return None;
+            }
+            r -= denom;
+            quotient |= 1 << i;
+        }
+    }
+    Some((quotient, r == 0))
+}
+
+/// Return the fixed-point number of type `F` nearest to `numer / denom`, rounded
+/// as `R` specifies, or return a `RangeError` if it is out of the range of `F`.
+/// A zero denominator gives `TooSmall` for a negative numerator, and `TooLarge`
+/// otherwise.
+pub fn from_ratio<F: Num, R: Rounding>(numer: i128, denom: i128) -> Result<F, RangeError>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    let negative = (numer < 0) != (denom < 0) && numer != 0;
+    let out_of_range = || {
+        if negative {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        }
+    };
+    if denom == 0 {
+        return Err(out_of_range());
+    }
+    // compute one more bit than needed when rounding to nearest
+    let nearest = matches!(R::MODE, RoundingMode::Nearest | RoundingMode::NearestEven);
+    let t = F::SHIFT.saturating_add(nearest as i32);
+    let (quotient, exact) =
+        div_pow2(numer.unsigned_abs(), denom.unsigned_abs(), t).ok_or_else(out_of_range)?;
+    let (q, half, sticky) = if nearest {
+        (quotient >> 1, quotient & 1 == 1, !exact)
+    } else {
+        (quotient, !exact, false)
+    };
+    let magnitude = q.checked_add(round_up(R::MODE, negative, q, half, sticky) as u128);
+    let raw = match magnitude {
+        Some(m) if negative && m <= i128::MIN.unsigned_abs() => (m as i128).wrapping_neg(),
+        Some(m) if !negative && m <= i128::MAX as u128 => m as i128,
+        _ => return Err(out_of_range()),
+    };
+    let (min, max): (i128, i128) = (F::MIN.raw().into(), F::MAX.raw().into());
+    if raw < min {
+        Err(RangeError::TooSmall)
+    } else if raw > max {
+        Err(RangeError::TooLarge)
+    } else {
+        Ok(unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) })
+    }
+}
@@ -0,0 +1,206 @@
+use core::marker::PhantomData;
+
+use crate::{ratio::div_pow2, Num};
+
+/// `round(x * num / den)` for a product of sign `negative` and magnitude `x * num`,
+/// rounded to nearest with ties away from zero, as a sign and a magnitude.  The
+/// inputs are at most 64-bit magnitudes, so the product cannot overflow.
+const fn mul_ratio_round(negative: bool, x: u128, num: u128, den: u128) -> (bool, u128) {
+    let product = x * num;
+    let (q, r) = (product / den, product % den);
+    let magnitude = if r >= den - r { q + 1 } else { q };
+    (negative && magnitude != 0, magnitude)
+}
+
+/// Number of bits needed to hold the signed value with sign `negative` and
+/// magnitude `m`.
+const fn signed_bits(negative: bool, m: u128) -> u32 {
+    if m == 0 {
+        0
+    } else if negative {
+        u128::BITS + 1 - (m - 1).leading_zeros()
+    } else {
+        u128::BITS + 1 - m.leading_zeros()
+    }
+}
+
+/// Bit width of `round(x * num / den)` over every `bits`-bit value `x`.
+const fn ratio_bits(signed: bool, bits: u32, num_negative: bool, num: u128, den: u128) -> u32 {
+    assert!(den != 0, "denominator must be positive");
+    if bits == 0 {
+        return 0;
+    }
+    if !signed {
+        let (_, max) = mul_ratio_round(false, (1 << bits) - 1, num, den);
+        return u128::BITS - max.leading_zeros();
+    }
+    // the result is monotonic in x, so the extremes come from MIN and MAX
+    let (a_neg, a) = mul_ratio_round(!num_negative, 1 << (bits - 1), num, den);
+    let (b_neg, b) = mul_ratio_round(num_negative, (1 << (bits - 1)) - 1, num, den);
+    let (a, b) = (signed_bits(a_neg, a), signed_bits(b_neg, b));
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// `round(x * num * 2^k / den)`, for `x` and `num` given as sign and magnitude,
+/// rounded to nearest with ties away from zero, or `None` if it is `2^128` or more.
+const fn scale_round(
+    negative: bool,
+    x: u128,
+    num: u128,
+    den: u128,
+    k: i32,
+) -> Option<(bool, u128)> {
+    // one more bit than needed, for rounding
+    match div_pow2(x * num, den, k.saturating_add(1)) {
+        Some((q, _)) => {
+            let magnitude = (q >> 1) + (q & 1);
+            Some((negative && magnitude != 0, magnitude))
+        }
+        None => None,
+    }
+}
+
+/// Compile-time checks for scaling `In` by `NUM / DEN` into `Out`.
+struct Scale<In, Out, const NUM: i64, const DEN: u64>(PhantomData<(In, Out)>);
+
+impl<In: Num, Out: Num, const NUM: i64, const DEN: u64> Scale<In, Out, NUM, DEN> {
+    /// The shift from the raw values of `In` to those of `Out`.
+    const K: i32 = Out::SHIFT - In::SHIFT;
+    const CHECK: () = {
+        assert!(DEN > 0, "denominator must be positive");
+        assert!(
+            In::BITS <= 64,
+            "inputs wider than 64 bits are not supported"
+        );
+        assert!(
+            !(NUM < 0 || In::SIGNED) || Out::SIGNED,
+            "a negative result cannot be stored in an unsigned type"
+        );
+        let bits = In::BITS;
+        // the result is monotonic in x, so the extremes come from MIN and MAX
+        let (min, max): (u128, u128) = match (bits, In::SIGNED) {
+            (0, _) => (0, 0),
+            (_, true) => (1 << (bits - 1), (1 << (bits - 1)) - 1),
+            (_, false) => (0, (1 << bits) - 1),
+        };
+        let num = NUM.unsigned_abs() as u128;
+        let (max_negative, max_positive) = match (Out::BITS, Out::SIGNED) {
+            (0, _) => (0, 0),
+            (b, true) => (1 << (b - 1), (1 << (b - 1)) - 1),
+            (b, false) => (0, u128::MAX >> (u128::BITS - b)),
+        };
+        let mut i = 0;
+        while i < 2 {
+            let (negative, x) = if i == 0 {
+                (In::SIGNED != (NUM < 0), min)
+            } else {
+                (NUM < 0, max)
+            };
+            let y = scale_round(negative && x != 0, x, num, DEN as u128, Self::K);
+            let fits = match y {
+                Some((true, m)) => m <= max_negative,
+                Some((false, m)) => m <= max_positive && m <= i128::MAX as u128,
+                None => false,
+            };
+            assert!(fits, "scaled value does not fit in the output type");
+            i += 1;
+        }
+    };
+}
+
+/// Convert `x` between units (e.g. from timer ticks to milliseconds) by multiplying
+/// its logical value by the constant `NUM / DEN`, and return the result as an
+/// `Out`.  The raw output is `x.raw() * NUM * 2^(Out::SHIFT - In::SHIFT) / DEN`,
+/// formed exactly and rounded once, to nearest with ties away from zero.
+///
+/// Compilation will fail unless `DEN` is positive, `In` has at most 64 bits, and
+/// `Out` can hold the scaled value of every `In` (so there is nothing to check at
+/// runtime).  E.g. a 32768 Hz tick count, as milliseconds with 4 fractional bits:
+///
+/// ```
+/// # #![allow(incomplete_features)]
+/// # #![feature(generic_const_exprs)]
+/// use fp::*;
+/// let ticks = U32::<16, 0>::new(1000).unwrap();
+/// let ms: U32<20, 4> = scale_by_ratio::<U32<16, 0>, U32<20, 4>, 1000, 32768>(ticks);
+/// assert_eq!(ms.into_f64(), 30.5); // 30.517... rounded to the nearest 1/16
+/// ```
+pub fn scale_by_ratio<In: Num, Out: Num, const NUM: i64, const DEN: u64>(x: In) -> Out
+where
+    In::Raw: Into<i128>,
+    Out::Raw: TryFrom<i128>,
+{
+    let () = Scale::<In, Out, NUM, DEN>::CHECK;
+    let raw: i128 = x.raw().into();
+    let negative = (raw < 0) != (NUM < 0) && raw != 0;
+    let num = NUM.unsigned_abs() as u128;
+    let k = Scale::<In, Out, NUM, DEN>::K;
+    let (negative, m) = scale_round(negative, raw.unsigned_abs(), num, DEN as u128, k).unwrap();
+    let y = if negative {
+        (m as i128).wrapping_neg()
+    } else {
+        m as i128
+    };
+    unsafe { Out::new_unchecked(y.try_into().ok().unwrap()) }
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// Returns the bit width of the return type from `mul_rational_const`.
+            pub const fn mul_rational_bits(num: $T, den: $T) -> u32 {
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let num_negative = num < 0;
+                #[allow(unused_comparisons)]
+                let den_negative = den < 0;
+                assert!(!den_negative, "denominator must be positive");
+                ratio_bits(
+                    <$T>::MIN != 0,
+                    BITS,
+                    num_negative,
+                    (num as i128).unsigned_abs(),
+                    den as u128,
+                )
+            }
+            /// Multiply by the constant `NUM / DEN`, with the product formed exactly
+            /// and rounded once, to nearest with ties away from zero.  The output has
+            /// exactly as many bits as the extremes of `Self` need after scaling
+            /// (e.g. 10 bits for a 10-bit value times `1000 / 1024`), rather than the
+            /// bits of `mul_const::<NUM>()` followed by `div_const::<DEN>()`.
+            /// Compilation will fail if `DEN` is not positive.
+            pub fn mul_rational_const<const NUM: $T, const DEN: $T>(
+                self,
+            ) -> $Name<{ Self::mul_rational_bits(NUM, DEN) }, SHIFT> {
+                let raw = self.raw();
+                #[allow(unused_comparisons)]
+                let negative = (raw < 0) != (NUM < 0);
+                let (negative, m) = mul_ratio_round(
+                    negative,
+                    (raw as i128).unsigned_abs(),
+                    (NUM as i128).unsigned_abs(),
+                    DEN as u128,
+                );
+                let y = if negative { -(m as i128) } else { m as i128 };
+                unsafe { $Name::new_unchecked(y as $T) }
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
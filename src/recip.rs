@@ -0,0 +1,119 @@
+#[cfg(not(feature = "deterministic"))]
+use crate::{Isize, Usize};
+use crate::{Num, RangeError, I16, I32, I64, I8, U16, U32, U64, U8};
+
+/// Returns the bit width of the return type from `recip`: the reciprocal of the
+/// smallest nonzero magnitude, `2^-s`, is `2^s`, which takes `s + frac + 1`
+/// unsigned bits at `frac` fraction bits (or one more, for a sign).
+pub const fn recip_bits(s: i32, frac: u32, signed: bool) -> u32 {
+    (s + frac as i32) as u32 + 1 + signed as u32
+}
+
+/// Fraction bits of the Newton iterates.
+const WORK: u32 = 62;
+
+/// The initial estimate of `1 / d` for `d` in `[1/2, 1)` is `48/17 - 32/17 d`,
+/// which has a relative error of at most `1/17`, or 4 bits.
+const C48: u128 = ((48u128 << WORK) + 8) / 17;
+const C32: u128 = ((32u128 << WORK) + 8) / 17;
+
+/// Each Newton iteration doubles the number of correct bits, so this many give at
+/// least `bits` of them.
+const fn newton_iterations(bits: u32) -> u32 {
+    let (mut correct, mut i) = (4, 0);
+    while correct < bits {
+        correct *= 2;
+        i += 1;
+    }
+    i
+}
+
+struct Recip<const S: i32, const FRAC: u32, const BITS: u32>;
+
+impl<const S: i32, const FRAC: u32, const BITS: u32> Recip<S, FRAC, BITS> {
+    const CHECK: () = {
+        assert!(S + FRAC as i32 >= 0, "recip result has no integer bits");
+        assert!(
+            recip_bits(S, FRAC, false) <= BITS,
+            "recip result has too many bits for its raw type"
+        );
+    };
+}
+
+/// `2^e / d`, rounded to nearest (ties away from zero), for `d > 0` and `e < 64`.
+fn recip_raw(d: u128, e: u32) -> u128 {
+    let n = u128::BITS - d.leading_zeros();
+    // `d` scaled into [1/2, 1), so that `1 / d` is in (1, 2]
+    let d_norm = if n <= WORK {
+        d << (WORK - n)
+    } else {
+        d >> (n - WORK)
+    };
+    let mut x = C48 - ((C32 * d_norm) >> WORK);
+    for _ in 0..newton_iterations(e + 2) {
+        // x (2 - d x)
+        let error = (2 << WORK) - ((d_norm * x) >> WORK);
+        x = (x * error) >> WORK;
+    }
+    // x approximates 2^(WORK + n) / d
+    let k = e as i32 - (WORK + n) as i32;
+    let mut q = if k >= 0 {
+        x << k
+    } else if k > -128 {
+        x >> -k
+    } else {
+        0
+    };
+    // correct the last few bits of the estimate with its remainder
+    let numer = 1u128 << e;
+    let mut r = numer as i128 - (q * d) as i128;
+    while r < 0 {
+        q -= 1;
+        r += d as i128;
+    }
+    while r >= d as i128 {
+        q += 1;
+        r -= d as i128;
+    }
+    q + (2 * r as u128 >= d) as u128
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty, $signed:expr) => {
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Return the reciprocal, with `FRAC` fraction bits, rounded to nearest
+            /// (ties away from zero), or `RangeError::TooLarge` for zero.  An
+            /// estimate by Newton iteration, with enough iterations for the
+            /// precision of the result, is corrected by its remainder, so the
+            /// result is always correctly rounded.  Compilation will fail if the
+            /// result does not fit in the raw type.
+            pub fn recip<const FRAC: u32>(
+                self,
+            ) -> Result<$Name<{ recip_bits(S, FRAC, $signed) }, { FRAC as i32 }>, RangeError> {
+                let () = Recip::<S, FRAC, { <$T>::BITS - $signed as u32 }>::CHECK;
+                let raw = self.raw();
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let negative = raw < 0;
+                let magnitude = (raw as i128).unsigned_abs();
+                if magnitude == 0 {
+                    return Err(RangeError::TooLarge);
+                }
+                let q = recip_raw(magnitude, (S + FRAC as i32) as u32) as $T;
+                Ok(unsafe { $Name::new_unchecked(if negative { q.wrapping_neg() } else { q }) })
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8, true);
+fp_impl!(U8, u8, false);
+fp_impl!(I16, i16, true);
+fp_impl!(U16, u16, false);
+fp_impl!(I32, i32, true);
+fp_impl!(U32, u32, false);
+fp_impl!(I64, i64, true);
+fp_impl!(U64, u64, false);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize, true);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize, false);
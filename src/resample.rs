@@ -0,0 +1,81 @@
+use crate::Num;
+
+/// Number of fractional bits in the interpolation position `mu`.
+const MU_BITS: u32 = 16;
+
+/// Arbitrary-ratio sample-rate converter, from `IN_RATE` to `OUT_RATE`
+/// (both in Hz, or any other common unit), by cubic (Catmull-Rom) interpolation
+/// between input samples.
+///
+/// The output position is tracked exactly as a rational multiple of the input
+/// period, so there is no long-term drift for any ratio (e.g. 44100 to 48000).
+/// Intermediate formats: samples are widened to `i64`; the fractional position
+/// between input samples is quantized to Q0.16; each stage of the Horner
+/// evaluation is truncated back to the LSB of `F`; and since cubic interpolation
+/// can overshoot, the output is saturated to the range of `F`.
+///
+/// The output lags the input by two input samples.
+#[derive(Clone, Debug)]
+pub struct Resampler<F: Num, const IN_RATE: u32, const OUT_RATE: u32> {
+    history: [i64; 4],
+    /// Time of the next output sample after `history[1]`, in units of `1 / OUT_RATE`
+    /// input periods.
+    position: u64,
+    _sample: core::marker::PhantomData<F>,
+}
+
+impl<F: Num, const IN_RATE: u32, const OUT_RATE: u32> Default for Resampler<F, IN_RATE, OUT_RATE>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num, const IN_RATE: u32, const OUT_RATE: u32> Resampler<F, IN_RATE, OUT_RATE>
+where
+    F::Raw: Into<i64> + TryFrom<i64>,
+{
+    const CHECK: () = {
+        assert!(IN_RATE > 0 && OUT_RATE > 0, "sample rates must be nonzero");
+        assert!(
+            F::BITS <= 32,
+            "samples wider than 32 bits are not supported"
+        );
+    };
+    /// Create a resampler with all-zero history.
+    pub fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            history: [0; 4],
+            position: OUT_RATE as u64,
+            _sample: core::marker::PhantomData,
+        }
+    }
+    /// Feed one input sample, and call `output` once for each output sample which
+    /// becomes available (on average `OUT_RATE / IN_RATE` times per input sample).
+    pub fn process(&mut self, input: F, mut output: impl FnMut(F)) {
+        self.history.rotate_left(1);
+        self.history[3] = input.raw().into();
+        self.position -= OUT_RATE as u64;
+        while self.position < OUT_RATE as u64 {
+            let mu = ((self.position << MU_BITS) / OUT_RATE as u64) as i64;
+            output(self.interpolate(mu));
+            self.position += IN_RATE as u64;
+        }
+    }
+    /// Catmull-Rom interpolation between `history[1]` and `history[2]`, at
+    /// fractional position `mu` (in Q0.16).
+    fn interpolate(&self, mu: i64) -> F {
+        let [x0, x1, x2, x3] = self.history;
+        let c1 = x2 - x0;
+        let c2 = 2 * x0 - 5 * x1 + 4 * x2 - x3;
+        let c3 = 3 * (x1 - x2) + x3 - x0;
+        let t = c2 + ((c3 * mu) >> MU_BITS);
+        let t = c1 + ((t * mu) >> MU_BITS);
+        let y = x1 + ((t * mu) >> (MU_BITS + 1));
+        let (min, max) = (F::MIN.raw().into(), F::MAX.raw().into());
+        unsafe { F::new_unchecked(y.clamp(min, max).try_into().ok().unwrap()) }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    decimal::{decimal_len, write_decimal},
+    Num,
+};
+
+/// The exact decimal representation of `mag / 2^shift`, negated if `negative`.
+pub(crate) fn decimal(negative: bool, mag: u128, shift: i32) -> String {
+    let mut buf = vec![0; decimal_len(u128::BITS, shift)];
+    let len = write_decimal(&mut buf, negative, mag, shift).unwrap();
+    buf.truncate(len);
+    // Safety: only ASCII digits, '-' and '.' are written.
+    unsafe { String::from_utf8_unchecked(buf) }
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// Return a JSON Schema (draft 2020-12) describing this format, as a
+            /// JSON object: a `number` with the exact `minimum`, `maximum` and
+            /// `multipleOf` (the LSB, `2^-SHIFT`) of the format, and a `description`
+            /// naming it.  For example, for `I16<12, 4>`:
+            ///
+            /// ```text
+            /// {"type":"number","minimum":-128,"maximum":127.9375,"multipleOf":0.0625,
+            ///  "description":"fixed-point I16<12, 4>"}
+            /// ```
+            ///
+            /// This is the schema a `schemars::JsonSchema` implementation would
+            /// return, and can be spliced into a generated schema by hand.
+            pub fn json_schema() -> String {
+                let bound = |x: Self| {
+                    let raw = x.raw();
+                    // (this code runs for both signed and unsigned types)
+                    #[allow(unused_comparisons)]
+                    let negative = raw < 0;
+                    let mag = if negative {
+                        (raw as i128).unsigned_abs()
+                    } else {
+                        raw as u128
+                    };
+                    decimal(negative, mag, SHIFT)
+                };
+                format!(
+                    concat!(
+                        r#"{{"type":"number","minimum":{},"maximum":{},"multipleOf":{},"#,
+                        r#""description":"fixed-point {}<{}, {}>"}}"#
+                    ),
+                    bound(Self::MIN),
+                    bound(Self::MAX),
+                    decimal(false, 1, SHIFT),
+                    stringify!($Name),
+                    BITS,
+                    SHIFT
+                )
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+fp_impl!(I128, i128);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
@@ -0,0 +1,135 @@
+//! A power-on self-test of the arithmetic in this crate, for firmware which must
+//! demonstrate at startup that the library computes correctly on the hardware it
+//! is deployed to.
+//!
+//! [`run`] evaluates a fixed set of known-answer vectors covering addition,
+//! subtraction, multiplication, division, shifts, saturation and conversions, in
+//! each width of raw type.  Every input passes through [`core::hint::black_box`],
+//! so the compiler cannot evaluate the vectors at build time, and the test
+//! exercises the same instructions as the application.  It does not allocate or
+//! panic.
+
+use core::hint::black_box;
+
+use crate::{Num, I128, I16, I32, I64, I8, U16, U64, U8};
+
+/// The first known-answer test to fail, named by the operation it exercises.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Failure {
+    pub case: &'static str,
+}
+
+/// An input to a test case, hidden from the optimizer.
+fn input<F: Num>(case: &'static str, raw: F::Raw) -> Result<F, Failure> {
+    F::new(black_box(raw)).map_err(|_| Failure { case })
+}
+
+/// Fail `case` unless `ok`.
+fn check(case: &'static str, ok: bool) -> Result<(), Failure> {
+    if ok {
+        Ok(())
+    } else {
+        Err(Failure { case })
+    }
+}
+
+fn add() -> Result<(), Failure> {
+    const CASE: &str = "add";
+    let a: I16<12, 4> = input(CASE, 1000)?;
+    let b: I16<10, 4> = input(CASE, -300)?;
+    check(CASE, (a + b).raw() == 700)?;
+    let c: I64<63, 0> = input(CASE, (1 << 62) - 1)?;
+    check(CASE, (c + c).raw() == i64::MAX - 1)?;
+    let d: U8<7, 0> = input(CASE, 127)?;
+    check(CASE, (d + d).raw() == 254)
+}
+
+fn sub() -> Result<(), Failure> {
+    const CASE: &str = "sub";
+    let a: U16<15, 0> = input(CASE, 1000)?;
+    let b: U16<15, 0> = input(CASE, 32767)?;
+    check(CASE, (a - b).raw() == -31767)?;
+    let c: I32<31, 8> = input(CASE, -(1 << 30))?;
+    let d: I32<31, 8> = input(CASE, (1 << 30) - 1)?;
+    check(CASE, (c - d).raw() == i32::MIN + 1)?;
+    check(CASE, (-c).raw() == 1 << 30)
+}
+
+fn mul() -> Result<(), Failure> {
+    const CASE: &str = "mul";
+    let a: I32<16, 8> = input(CASE, -300)?;
+    let b: I32<16, 8> = input(CASE, 200)?;
+    check(CASE, (a * b).raw() == -60000)?;
+    let c: U64<32, 0> = input(CASE, 0xffff_ffff)?;
+    check(CASE, (c * c).raw() == 0xffff_fffe_0000_0001)?;
+    let d: I8<4, 2> = input(CASE, -8)?;
+    check(CASE, (d * d).raw() == 64)?;
+    let f: I128<64, 0> = input(CASE, -(1 << 63))?;
+    check(CASE, (f * f).raw() == 1 << 126)?;
+    let e: I16<8, 0> = input(CASE, -100)?;
+    check(CASE, e.mul_const::<3>().raw() == -300)
+}
+
+fn div() -> Result<(), Failure> {
+    const CASE: &str = "div";
+    let a: I32<20, 8> = input(CASE, -1000)?;
+    let b: I32<8, 0> = input(CASE, 7)?;
+    // division truncates toward zero, as integer division does
+    check(CASE, (a / b).raw() == -142)?;
+    let c: I64<63, 0> = input(CASE, -(1 << 62))?;
+    let d: I64<63, 0> = input(CASE, -3)?;
+    check(CASE, (c / d).raw() == 1537228672809129301)?;
+    let e: I16<8, 0> = input(CASE, -100)?;
+    check(CASE, e.div_const::<-4>().raw() == 25)
+}
+
+fn shift() -> Result<(), Failure> {
+    const CASE: &str = "shift";
+    let a: I32<16, 4> = input(CASE, 12345)?;
+    check(CASE, a.raw_shr::<4>().raw() == 771)?;
+    let b: I32<16, 4> = input(CASE, -12345)?;
+    // the raw right shift rounds toward negative infinity
+    check(CASE, b.raw_shr::<4>().raw() == -772)?;
+    check(CASE, b.raw_shl::<3>().raw() == -98760)?;
+    let c: U64<40, 8> = input(CASE, 0xab_cdef_0123)?;
+    check(CASE, c.raw_shl::<24>().raw() == 0xabcd_ef01_2300_0000)?;
+    check(CASE, c.logical_shl::<3>().raw() == c.raw())
+}
+
+fn saturate() -> Result<(), Failure> {
+    const CASE: &str = "saturate";
+    let a: I32<16, 0> = input(CASE, 30000)?;
+    let b: I32<16, 0> = input(CASE, -30000)?;
+    check(CASE, a.saturate::<8>().raw() == 127)?;
+    check(CASE, b.saturate::<8>().raw() == -128)?;
+    check(CASE, a.set_bits::<8>().is_err())?;
+    check(CASE, a.set_bits::<16>().is_ok())?;
+    let c: I16<16, 0> = input(CASE, 0x1234)?;
+    check(CASE, c.wrapping_set_bits::<8>().raw() == 0x34)?;
+    check(CASE, c.wrapping_set_bits::<6>().raw() == -12)?;
+    let d: U8<8, 0> = input(CASE, 200)?;
+    check(CASE, d.saturate::<7>().raw() == 127)
+}
+
+fn convert() -> Result<(), Failure> {
+    const CASE: &str = "convert";
+    let a = I16::<12, 4>::from_f64(black_box(-1.25)).map_err(|_| Failure { case: CASE })?;
+    check(CASE, a.raw() == -20)?;
+    let b: I32<20, 4> = a.into_fp();
+    check(CASE, b.raw() == -20)?;
+    let c: I32<20, 10> = input(CASE, -1536)?;
+    check(CASE, c.into_f64() == -1.5 && c.into_f32() == -1.5)?;
+    check(CASE, U8::<8, 8>::from_f32(black_box(1.)).is_err())?;
+    check(CASE, c.checked_cast::<i16>() == Some(-2))
+}
+
+/// Run every known-answer test, and return the first failure, if any.
+pub fn run() -> Result<(), Failure> {
+    add()?;
+    sub()?;
+    mul()?;
+    div()?;
+    shift()?;
+    saturate()?;
+    convert()
+}
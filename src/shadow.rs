@@ -0,0 +1,125 @@
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{Num, RangeError};
+
+/// A fixed-point number paired with an `f64` "shadow" which receives the same
+/// operations in floating point.  Intended as a debugging aid when porting a
+/// floating-point algorithm: the divergence between the fixed-point value and
+/// its shadow shows exactly how much precision each stage loses.
+///
+/// The worst divergence observed anywhere in the computation which produced
+/// this value is carried along, so the final result of a long chain of
+/// operations reports the worst stage of the whole chain.
+#[derive(Clone, Copy, Debug)]
+pub struct Shadow<F: Num> {
+    value: F,
+    shadow: f64,
+    worst: f64,
+}
+
+impl<F: Num> Shadow<F> {
+    /// Create a shadowed value whose shadow is exactly the logical value of `value`.
+    pub fn new(value: F) -> Self {
+        Self {
+            value,
+            shadow: value.into_f64(),
+            worst: 0.,
+        }
+    }
+    /// Create a shadowed value from an ideal floating-point value.  The fixed-point
+    /// value is the quantized version of `val`, while the shadow is `val` itself.
+    pub fn from_f64(val: f64) -> Result<Self, RangeError> {
+        Ok(Self::with_shadow(F::from_f64(val)?, val, 0.))
+    }
+    /// Pair `value` with an explicit shadow, recording their divergence in addition
+    /// to the previously observed worst divergence `worst`.
+    fn with_shadow(value: F, shadow: f64, worst: f64) -> Self {
+        let divergence = (value.into_f64() - shadow).abs();
+        Self {
+            value,
+            shadow,
+            worst: divergence.max(worst),
+        }
+    }
+    /// Return the fixed-point value.
+    pub fn value(self) -> F {
+        self.value
+    }
+    /// Return the floating-point shadow value.
+    pub fn shadow(self) -> f64 {
+        self.shadow
+    }
+    /// Return the absolute difference between the logical value and the shadow.
+    pub fn divergence(self) -> f64 {
+        (self.value.into_f64() - self.shadow).abs()
+    }
+    /// Return the divergence measured in units of the least-significant bit of `F`.
+    pub fn divergence_lsbs(self) -> f64 {
+        self.divergence() * crate::fp_impl::pow2_f64(F::SHIFT)
+    }
+    /// Return the worst divergence observed at any stage of the computation
+    /// which produced this value.
+    pub fn worst_divergence(self) -> f64 {
+        self.worst
+    }
+    /// Apply an arbitrary operation to the value and a corresponding operation
+    /// to the shadow, e.g. `x.map(|v| v.raw_shr::<4>(), |s| s)`.
+    pub fn map<G: Num>(
+        self,
+        op: impl FnOnce(F) -> G,
+        shadow_op: impl FnOnce(f64) -> f64,
+    ) -> Shadow<G> {
+        Shadow::with_shadow(op(self.value), shadow_op(self.shadow), self.worst)
+    }
+}
+
+impl<A: Num + Add<B, Output = C>, B: Num, C: Num> Add<Shadow<B>> for Shadow<A> {
+    type Output = Shadow<C>;
+    fn add(self, other: Shadow<B>) -> Self::Output {
+        Shadow::with_shadow(
+            self.value + other.value,
+            self.shadow + other.shadow,
+            self.worst.max(other.worst),
+        )
+    }
+}
+
+impl<A: Num + Sub<B, Output = C>, B: Num, C: Num> Sub<Shadow<B>> for Shadow<A> {
+    type Output = Shadow<C>;
+    fn sub(self, other: Shadow<B>) -> Self::Output {
+        Shadow::with_shadow(
+            self.value - other.value,
+            self.shadow - other.shadow,
+            self.worst.max(other.worst),
+        )
+    }
+}
+
+impl<A: Num + Mul<B, Output = C>, B: Num, C: Num> Mul<Shadow<B>> for Shadow<A> {
+    type Output = Shadow<C>;
+    fn mul(self, other: Shadow<B>) -> Self::Output {
+        Shadow::with_shadow(
+            self.value * other.value,
+            self.shadow * other.shadow,
+            self.worst.max(other.worst),
+        )
+    }
+}
+
+impl<A: Num + Div<B, Output = C>, B: Num, C: Num> Div<Shadow<B>> for Shadow<A> {
+    type Output = Shadow<C>;
+    fn div(self, other: Shadow<B>) -> Self::Output {
+        Shadow::with_shadow(
+            self.value / other.value,
+            self.shadow / other.shadow,
+            self.worst.max(other.worst),
+        )
+    }
+}
+
+impl<A: Num + Neg<Output = C>, C: Num> Neg for Shadow<A> {
+    type Output = Shadow<C>;
+    fn neg(self) -> Self::Output {
+        Shadow::with_shadow(-self.value, -self.shadow, self.worst)
+    }
+}
@@ -0,0 +1,70 @@
+use core::marker::PhantomData;
+
+use crate::{Num, U8};
+
+/// First-order sigma-delta modulator, from a signed input stream to a stream of
+/// `OUT_BITS`-bit output codes (e.g. `OUT_BITS = 1` for a one-bit DAC or PDM
+/// output), whose running average tracks the input.
+///
+/// The `2^OUT_BITS` output codes `c` stand for evenly spaced levels from
+/// `F::MIN` to `-F::MIN` (one LSB above `F::MAX`); i.e. code `c` represents
+/// `(2c / (2^OUT_BITS - 1) - 1)` times full scale.  So for one-bit output, code 0
+/// is negative full scale and code 1 is positive full scale.
+///
+/// The modulator keeps the quantization error of each sample and feeds it into the
+/// next (`e[n] = x[n] + e[n-1] - level(c[n])`).  The error is kept exactly, scaled
+/// by `2^OUT_BITS - 1` so that the levels are integers, in an `i64`: for any input
+/// the error is at most half a level, so the accumulator needs only
+/// `F::BITS + OUT_BITS` bits and cannot overflow or wind up.
+#[derive(Clone, Debug)]
+pub struct SigmaDelta<F: Num, const OUT_BITS: u32> {
+    error: i64,
+    _sample: PhantomData<F>,
+}
+
+impl<F: Num, const OUT_BITS: u32> Default for SigmaDelta<F, OUT_BITS>
+where
+    F::Raw: Into<i64>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num, const OUT_BITS: u32> SigmaDelta<F, OUT_BITS>
+where
+    F::Raw: Into<i64>,
+{
+    const CHECK: () = {
+        assert!(F::SIGNED, "sigma-delta input must be signed");
+        assert!(F::BITS >= 1, "input must have at least one bit");
+        assert!(
+            OUT_BITS >= 1 && OUT_BITS <= 8,
+            "output must have 1 to 8 bits"
+        );
+        assert!(F::BITS + OUT_BITS < i64::BITS, "state too wide for i64");
+    };
+    /// Create a modulator with zero initial error.
+    pub fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            error: 0,
+            _sample: PhantomData,
+        }
+    }
+    /// Modulate one sample, returning the output code.
+    pub fn process(&mut self, x: F) -> U8<OUT_BITS, 0> {
+        let full_scale = 1i64 << (F::BITS - 1);
+        let steps = (1i64 << OUT_BITS) - 1;
+        let u = self.error + steps * x.raw().into();
+        // nearest code: round((u / steps + full_scale) / (2 * full_scale / steps))
+        let code = ((u + (steps + 1) * full_scale) >> F::BITS).clamp(0, steps);
+        self.error = u - (2 * code - steps) * full_scale;
+        unsafe { U8::new_unchecked(code as u8) }
+    }
+    /// Return the quantization error carried into the next sample, in units of
+    /// the input LSB.
+    pub fn error(&self) -> f64 {
+        self.error as f64 / ((1i64 << OUT_BITS) - 1) as f64
+    }
+}
@@ -0,0 +1,87 @@
+use crate::Num;
+
+/// Fixed-capacity double-ended queue of `(arrival index, sample)` pairs, with
+/// samples monotonic from front to back (Lemire's "wedge").
+#[derive(Clone, Debug)]
+struct Wedge<F: Num, const N: usize> {
+    buf: [(u64, F); N],
+    head: usize,
+    len: usize,
+}
+
+impl<F: Num, const N: usize> Wedge<F, N> {
+    fn new() -> Self {
+        Self {
+            buf: [(0, F::MIN); N],
+            head: 0,
+            len: 0,
+        }
+    }
+    fn slot(&self, i: usize) -> usize {
+        (self.head + i) % N
+    }
+    /// Push sample `x` with arrival index `index`, first discarding every sample
+    /// which can no longer be the extremum (those for which `dominated(sample, x)`),
+    /// and every sample which has left the window of length `N`.
+    fn push(&mut self, index: u64, x: F, dominated: impl Fn(F, F) -> bool) {
+        while self.len > 0 && dominated(self.buf[self.slot(self.len - 1)].1, x) {
+            self.len -= 1;
+        }
+        if self.len > 0 && self.buf[self.head].0 + N as u64 <= index {
+            self.head = self.slot(1);
+            self.len -= 1;
+        }
+        let back = self.slot(self.len);
+        self.buf[back] = (index, x);
+        self.len += 1;
+    }
+    fn front(&self) -> F {
+        self.buf[self.head].1
+    }
+}
+
+/// Minimum and maximum over a sliding window of the most recent `N` samples,
+/// in `O(1)` amortized time per sample, without allocation.  Useful for envelope
+/// tracking and peak-hold displays.  Before `N` samples have arrived, the window
+/// consists of all samples so far.
+#[derive(Clone, Debug)]
+pub struct SlidingMinMax<F: Num, const N: usize> {
+    min: Wedge<F, N>,
+    max: Wedge<F, N>,
+    count: u64,
+}
+
+impl<F: Num, const N: usize> Default for SlidingMinMax<F, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Num, const N: usize> SlidingMinMax<F, N> {
+    const CHECK: () = assert!(N > 0, "window length must be nonzero");
+    /// Create an empty sliding window.
+    pub fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            min: Wedge::new(),
+            max: Wedge::new(),
+            count: 0,
+        }
+    }
+    /// Insert a sample into the window, evicting the oldest sample if the
+    /// window is full, and return the new `(min, max)`.
+    pub fn process(&mut self, x: F) -> (F, F) {
+        self.min.push(self.count, x, |s, x| s >= x);
+        self.max.push(self.count, x, |s, x| s <= x);
+        self.count += 1;
+        (self.min.front(), self.max.front())
+    }
+    /// Return the minimum of the window, or `None` if no samples have arrived.
+    pub fn min(&self) -> Option<F> {
+        (self.count > 0).then(|| self.min.front())
+    }
+    /// Return the maximum of the window, or `None` if no samples have arrived.
+    pub fn max(&self) -> Option<F> {
+        (self.count > 0).then(|| self.max.front())
+    }
+}
@@ -0,0 +1,123 @@
+use core::marker::PhantomData;
+
+use crate::{const_math, Num};
+
+/// Number of intervals per unit input in the `tanh` table.
+const TANH_STEPS: usize = 64;
+/// Largest input in the `tanh` table; beyond this, `tanh` is within 2.3e-7 of 1.
+pub(crate) const TANH_LIMIT: usize = 8;
+
+/// `tanh(i / TANH_STEPS)` in unsigned Q0.31, for `0 <= i <= TANH_LIMIT * TANH_STEPS`.
+static TANH: [u32; TANH_LIMIT * TANH_STEPS + 1] = {
+    let mut table = [0; TANH_LIMIT * TANH_STEPS + 1];
+    let mut i = 0;
+    while i < table.len() {
+        let x = i as f64 / TANH_STEPS as f64;
+        let tanh = 1. - 2. / (const_math::exp(2. * x) + 1.);
+        table[i] = (tanh * (1u64 << 31) as f64 + 0.5) as u32;
+        i += 1;
+    }
+    table
+};
+
+/// `tanh(x)` in Q30, for `x` in Q30 from 0 to `TANH_LIMIT`, by linear
+/// interpolation in the table.
+pub(crate) fn tanh_q30(x: u64) -> i128 {
+    let (index, frac) = ((x >> 24) as usize, (x & ((1 << 24) - 1)) as i128);
+    let y0 = TANH[index] as i128;
+    let y1 = TANH[(index + 1).min(TANH.len() - 1)] as i128;
+    let y = y0 + (((y1 - y0) * frac + (1 << 23)) >> 24);
+    (y + 1) >> 1
+}
+
+/// The transfer curve of a [`SoftClip`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Curve {
+    /// `1.5 x - 0.5 x^3` for `|x| <= 1`, and `+/- 1` beyond.  This is exact
+    /// (up to the final rounding), has unit slope at zero, and reaches the limits
+    /// with zero slope, but is not smooth beyond `|x| == 1`.
+    Cubic,
+    /// `tanh(x)`, by linear interpolation in a 512-interval table over `0 <= x <= 8`,
+    /// accurate to within 3e-5.  Inputs beyond 8 saturate to the final table entry.
+    Tanh,
+}
+
+/// Waveshaper which maps its input through a bounded, odd, monotonic
+/// nonlinearity, so that large inputs are saturated smoothly rather than
+/// clipped.  Inputs should be scaled so that the onset of clipping is near 1.
+///
+/// The input type must be signed.  The output, which lies between -1 and 1, has
+/// the same raw type and shift as the input and `SHIFT + 2` bits, the fewest that
+/// represent both limits.  The curve is evaluated at 30 fractional bits, and the
+/// result is rounded (with ties away from zero, so that the curve stays odd) to
+/// the output LSB.
+#[derive(Clone, Debug)]
+pub struct SoftClip<F: Num> {
+    curve: Curve,
+    _sample: PhantomData<F>,
+}
+
+impl<F: Num> SoftClip<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+    [(); (F::SHIFT + 2) as u32 as usize]:,
+{
+    const CHECK: () = {
+        assert!(F::SIGNED, "soft clipper input must be signed");
+        assert!(
+            F::SHIFT >= 0 && F::SHIFT as u32 + 2 <= <F::Raw as Num>::BITS,
+            "output of SHIFT + 2 bits does not fit in the raw type"
+        );
+    };
+    /// Create a soft clipper with the transfer curve `curve`.  Every curve gives
+    /// an output of `SHIFT + 2` bits at the shift of `F`, from -1 to 1.
+    /// Compilation will fail unless `F` is signed, with a nonnegative `SHIFT`
+    /// and room for the output bits in its raw type.
+    pub fn new(curve: Curve) -> Self {
+        let () = Self::CHECK;
+        Self {
+            curve,
+            _sample: PhantomData,
+        }
+    }
+    /// Create a soft clipper with the [`Cubic`](Curve::Cubic) curve: exactly
+    /// `1.5 x - 0.5 x^3`, rounded to the output LSB, for `|x| <= 1`, and
+    /// exactly -1 or 1 beyond.
+    pub fn cubic() -> Self {
+        Self::new(Curve::Cubic)
+    }
+    /// Create a soft clipper with the [`Tanh`](Curve::Tanh) curve: `tanh(x)`,
+    /// to within 3e-5 plus half an output LSB, which is smooth everywhere and
+    /// approaches -1 and 1 only gradually.
+    pub fn tanh() -> Self {
+        Self::new(Curve::Tanh)
+    }
+    /// Shape one sample.
+    pub fn process(&self, x: F) -> F::Output<{ (F::SHIFT + 2) as u32 }, { F::SHIFT }> {
+        let shift = F::SHIFT as u32;
+        let limit = TANH_LIMIT as i128;
+        // Convert to Q30, clamped to the table range (which also prevents overflow).
+        let raw: i128 = x.raw().into();
+        let x = if shift >= 30 {
+            raw >> (shift - 30)
+        } else {
+            raw.clamp(-limit << shift, limit << shift) << (30 - shift)
+        };
+        let x = x.clamp(-limit << 30, limit << 30) as i64;
+        let magnitude = match self.curve {
+            Curve::Cubic => {
+                let x = x.unsigned_abs().min(1 << 30) as i128;
+                (((3 * x) << 60) - x * x * x + (1 << 60)) >> 61
+            }
+            Curve::Tanh => tanh_q30(x.unsigned_abs()),
+        };
+        // Convert from Q30 to the output shift, rounding the magnitude.
+        let magnitude = if shift >= 30 {
+            magnitude << (shift - 30)
+        } else {
+            (magnitude + (1 << (29 - shift))) >> (30 - shift)
+        };
+        let y = if x < 0 { -magnitude } else { magnitude };
+        unsafe { F::Output::new_unchecked(y.try_into().ok().unwrap()) }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{Num, RangeError};
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty, $Key:ty) => {
+        use crate::$Name;
+        impl<const BITS: u32, const SHIFT: i32> $Name<BITS, SHIFT> {
+            /// Return an unsigned integer whose order is the order of the logical
+            /// values (e.g. for a radix sort, or as a database or priority queue
+            /// key).  For signed types, this is the raw value with its sign bit
+            /// flipped.
+            pub fn to_sort_key(self) -> $Key {
+                (self.raw() as $Key) ^ (<$T>::MIN as $Key)
+            }
+            /// Invert [`to_sort_key`](Self::to_sort_key), or return a `RangeError`
+            /// if `key` is not the key of any value of `Self`.
+            pub fn from_sort_key(key: $Key) -> Result<Self, RangeError> {
+                Self::new((key ^ (<$T>::MIN as $Key)) as $T)
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8, u8);
+fp_impl!(U8, u8, u8);
+fp_impl!(I16, i16, u16);
+fp_impl!(U16, u16, u16);
+fp_impl!(I32, i32, u32);
+fp_impl!(U32, u32, u32);
+fp_impl!(I64, i64, u64);
+fp_impl!(U64, u64, u64);
+fp_impl!(I128, i128, u128);
+fp_impl!(U128, u128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize, usize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize, usize);
@@ -0,0 +1,65 @@
+use crate::Num;
+
+/// Returns the bit width of the return type from `sqrt`: half the bits of the
+/// input, rounded up, after a shift which is odd has been made even by shifting
+/// the raw value left by one.
+pub const fn sqrt_bits(b: u32, s: i32) -> u32 {
+    (b + s.rem_euclid(2) as u32).div_ceil(2)
+}
+
+/// Returns the shift of the return type from `sqrt`: half the shift of the input,
+/// rounded up.
+pub const fn sqrt_shift(s: i32) -> i32 {
+    (s + s.rem_euclid(2)) / 2
+}
+
+/// `floor(sqrt(x << shl))`, by the binary digit-by-digit method, for `shl` of zero
+/// or one.  The shifted value may have 129 bits, so it is fed in a pair of bits at a
+/// time, rather than being formed.
+pub(crate) const fn isqrt(x: u128, shl: u32) -> u128 {
+    let bits = u128::BITS - x.leading_zeros() + shl;
+    let (mut root, mut rem) = (0u128, 0u128);
+    let mut i = bits.div_ceil(2);
+    while i > 0 {
+        i -= 1;
+        // bits 2i + 1 and 2i of the shifted value
+        let pair = if 2 * i >= shl {
+            (x >> (2 * i - shl)) & 3
+        } else {
+            (x << shl) & 3
+        };
+        // rem < 2 * root + 1 < 2^66, so this cannot overflow
+        rem = (rem << 2) | pair;
+        let trial = (root << 2) | 1;
+        root <<= 1;
+        if rem >= trial {
+            rem -= trial;
+            root |= 1;
+        }
+    }
+    root
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const B: u32, const S: i32> $Name<B, S> {
+            /// Return the square root, rounded toward zero.  The result has half the
+            /// bits and half the shift of the input (rounded up).  If the shift is
+            /// odd, the raw value is shifted left by one first, so the root is exact
+            /// to the last bit of the result.
+            pub fn sqrt(self) -> $Name<{ sqrt_bits(B, S) }, { sqrt_shift(S) }> {
+                let root = isqrt(self.raw() as u128, S.rem_euclid(2) as u32);
+                unsafe { $Name::new_unchecked(root as $T) }
+            }
+        }
+    };
+}
+
+fp_impl!(U8, u8);
+fp_impl!(U16, u16);
+fp_impl!(U32, u32);
+fp_impl!(U64, u64);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
@@ -0,0 +1,137 @@
+use core::marker::PhantomData;
+
+use crate::{bfp::round_shr, clog2, Matrix, Num};
+
+/// Discrete-time linear state-space system, with state `x`, input `u` and
+/// output `y`:
+///
+/// ```text
+/// y[n]     = C x[n] + D u[n]
+/// x[n + 1] = A x[n] + B u[n]
+/// ```
+///
+/// with up to `NX` states, `NU` inputs and `NY` outputs.  The matrix elements all
+/// have type `T`, the state has type `X`, and the inputs and outputs have type `F`;
+/// all three must be signed, and `T::SHIFT` must not be negative.
+///
+/// Each row of the right-hand sides is accumulated exactly in the format given
+/// by [`ACC_BITS`](Self::ACC_BITS) and [`ACC_SHIFT`](Self::ACC_SHIFT), which are
+/// derived from the formats of the matrices, the state and the input; compilation
+/// will fail if that format needs more than 127 bits.  Each result is then rounded
+/// to nearest (with ties away from zero) and saturated to the range of `X` or `F`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StateSpace<T, X, F, const NX: usize, const NU: usize, const NY: usize>
+where
+    T: Num,
+    X: Num,
+    F: Num,
+{
+    a: Matrix<T, NX, NX>,
+    b: Matrix<T, NX, NU>,
+    c: Matrix<T, NY, NX>,
+    d: Matrix<T, NY, NU>,
+    state: [X; NX],
+    _io: PhantomData<F>,
+}
+
+impl<T, X, F, const NX: usize, const NU: usize, const NY: usize> StateSpace<T, X, F, NX, NU, NY>
+where
+    T: Num,
+    X: Num,
+    F: Num,
+    T::Raw: Into<i128>,
+    X::Raw: Into<i128> + TryFrom<i128>,
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// Number of fractional bits of the internal accumulator: enough to hold
+    /// every product of a matrix element with a state or input exactly.
+    pub const ACC_SHIFT: i32 = T::SHIFT
+        + if X::SHIFT > F::SHIFT {
+            X::SHIFT
+        } else {
+            F::SHIFT
+        };
+    /// Number of bits of the internal accumulator: enough to hold the sum of `NX`
+    /// products with the state and `NU` products with the input, each aligned to
+    /// [`ACC_SHIFT`](Self::ACC_SHIFT).
+    pub const ACC_BITS: u32 = {
+        let x = T::BITS + X::BITS + (Self::ACC_SHIFT - T::SHIFT - X::SHIFT) as u32;
+        let u = T::BITS + F::BITS + (Self::ACC_SHIFT - T::SHIFT - F::SHIFT) as u32;
+        (if x > u { x } else { u }) + clog2(NX + NU)
+    };
+    const CHECK: () = {
+        assert!(
+            T::SIGNED && X::SIGNED && F::SIGNED,
+            "state-space formats must be signed"
+        );
+        assert!(T::SHIFT >= 0, "matrix shift must not be negative");
+        assert!(Self::ACC_BITS < i128::BITS, "accumulator too wide for i128");
+    };
+    /// Create a system with the given matrices and a zero state.
+    pub fn new(
+        a: Matrix<T, NX, NX>,
+        b: Matrix<T, NX, NU>,
+        c: Matrix<T, NY, NX>,
+        d: Matrix<T, NY, NU>,
+    ) -> Self {
+        let () = Self::CHECK;
+        Self {
+            a,
+            b,
+            c,
+            d,
+            state: [Self::narrow(0); NX],
+            _io: PhantomData,
+        }
+    }
+    /// Return the current state.
+    pub fn state(&self) -> [X; NX] {
+        self.state
+    }
+    /// Replace the current state.
+    pub fn set_state(&mut self, state: [X; NX]) {
+        self.state = state;
+    }
+    /// Reset the state to zero.
+    pub fn reset(&mut self) {
+        self.state = [Self::narrow(0); NX];
+    }
+    /// Compute one row of `M x + N u` in the accumulator format.
+    fn accumulate(m: &[T; NX], n: &[T; NU], x: &[X; NX], u: &[F; NU]) -> i128 {
+        let mx: i128 = m
+            .iter()
+            .zip(x)
+            .map(|(m, x)| m.raw().into() * x.raw().into())
+            .sum();
+        let nu: i128 = n
+            .iter()
+            .zip(u)
+            .map(|(n, u)| n.raw().into() * u.raw().into())
+            .sum();
+        (mx << (Self::ACC_SHIFT - T::SHIFT - X::SHIFT))
+            + (nu << (Self::ACC_SHIFT - T::SHIFT - F::SHIFT))
+    }
+    /// Round an accumulator to `G`, saturating.
+    fn narrow<G: Num>(acc: i128) -> G
+    where
+        G::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let y = round_shr(acc, (Self::ACC_SHIFT - G::SHIFT) as u32);
+        let (min, max) = (G::MIN.raw().into(), G::MAX.raw().into());
+        unsafe { G::new_unchecked(y.clamp(min, max).try_into().ok().unwrap()) }
+    }
+    /// Apply one input sample, returning the output and advancing the state.
+    pub fn update(&mut self, u: [F; NU]) -> [F; NY] {
+        let x = &self.state;
+        let mut y = [F::MIN; NY];
+        for (y, (c, d)) in y.iter_mut().zip(self.c.rows().iter().zip(self.d.rows())) {
+            *y = Self::narrow(Self::accumulate(c, d, x, &u));
+        }
+        let mut next = self.state;
+        for (next, (a, b)) in next.iter_mut().zip(self.a.rows().iter().zip(self.b.rows())) {
+            *next = Self::narrow(Self::accumulate(a, b, x, &u));
+        }
+        self.state = next;
+        y
+    }
+}
@@ -0,0 +1,140 @@
+use core::iter::Sum;
+use core::ops::Add;
+
+use crate::{Convert, Num, RangeError};
+
+/// Ceiling of the base-2 logarithm of `n` (with `clog2(0) == 0`).  Summing `n`
+/// values which each fit in `BITS` bits produces a value which fits in
+/// `BITS + clog2(n)` bits.
+pub const fn clog2(n: usize) -> u32 {
+    if n <= 1 {
+        0
+    } else {
+        usize::BITS - (n - 1).leading_zeros()
+    }
+}
+
+/// Below this length, a pairwise sum is computed as a linear fold.  This keeps
+/// the recursion shallow and leaves a straight-line loop for the vectorizer.
+const LEAF_LEN: usize = 8;
+
+/// Raw pairwise sum.  Overflow safety is the caller's responsibility.
+pub(crate) fn tree_sum_raw<F: Num>(xs: &[F]) -> F::Raw
+where
+    F::Raw: Default + Add<Output = F::Raw>,
+{
+    if xs.len() <= LEAF_LEN {
+        xs.iter().fold(F::Raw::default(), |acc, x| acc + x.raw())
+    } else {
+        let (lo, hi) = xs.split_at(xs.len() / 2);
+        tree_sum_raw(lo) + tree_sum_raw(hi)
+    }
+}
+
+/// Sum an array of fixed-point numbers by pairwise (tree) reduction.  The result
+/// has the same raw type and shift as the inputs, and `clog2(N)` more bits,
+/// which is sufficient to guarantee that the sum cannot overflow.
+/// Compilation will fail if the output number of bits is too large for the raw type.
+pub fn tree_sum<F: Num, const N: usize>(
+    xs: &[F; N],
+) -> F::Output<{ F::BITS + clog2(N) }, { F::SHIFT }>
+where
+    F::Raw: Default + Add<Output = F::Raw>,
+    [(); (F::BITS + clog2(N)) as usize]:,
+{
+    unsafe { F::Output::new_unchecked(tree_sum_raw(xs)) }
+}
+
+/// Sum a slice of fixed-point numbers by pairwise (tree) reduction,
+/// into the caller's choice of accumulator type `Acc`.  `Acc` must have the
+/// same raw type, shift, and signedness as `F` (or compilation will fail).  Returns a `RangeError` if
+/// the slice is too long for `Acc` to be guaranteed to hold the sum
+/// (i.e. if `F::BITS + clog2(xs.len()) > Acc::BITS`).
+pub fn tree_sum_slice<F: Num, Acc: Num<Raw = F::Raw>>(xs: &[F]) -> Result<Acc, RangeError>
+where
+    F::Raw: Default + Add<Output = F::Raw>,
+{
+    let () = Convert::<F, Acc>::SAME_SCALE;
+    if F::BITS + clog2(xs.len()) > Acc::BITS {
+        return Err(RangeError::TooLarge);
+    }
+    Ok(unsafe { Acc::new_unchecked(tree_sum_raw(xs)) })
+}
+
+/// Dot product of two arrays of fixed-point numbers with the same raw type, e.g.
+/// the output of an FIR filter.  The result has the shift of the products, and
+/// `A::BITS + B::BITS + clog2(N)` bits, which is sufficient to guarantee that the
+/// sum of the `N` exact products cannot overflow.  Compilation will fail if the
+/// output number of bits is too large for the raw type.
+pub fn dot<A: Num, B: Num<Raw = A::Raw>, const N: usize>(
+    a: &[A; N],
+    b: &[B; N],
+) -> A::Output<{ A::BITS + B::BITS + clog2(N) }, { A::SHIFT + B::SHIFT }>
+where
+    A::Raw: Into<i128> + TryFrom<i128>,
+    [(); (A::BITS + B::BITS + clog2(N)) as usize]:,
+    [(); (A::SHIFT + B::SHIFT) as usize]:,
+{
+    // the products and their sum fit in the output, so this cannot overflow
+    let sum: i128 = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| x.raw().into() * y.raw().into())
+        .sum();
+    unsafe { A::Output::new_unchecked(sum.try_into().ok().unwrap()) }
+}
+
+/// Sum the fixed-point numbers of `xs` into the caller's choice of accumulator type
+/// `Acc`, or return a `RangeError` if the sum is out of the range of `Acc`.  `Acc`
+/// must have the same shift and signedness as `F` (or compilation will fail), but
+/// may have a different raw type.  The sum is accumulated exactly, in an `i128`,
+/// and checked against the range of `Acc` once, at the end; it is only checked
+/// earlier if it would overflow the `i128`.
+pub fn sum_into<Acc: Num, F: Num>(xs: impl IntoIterator<Item = F>) -> Result<Acc, RangeError>
+where
+    F::Raw: Into<i128>,
+    Acc::Raw: TryFrom<i128>,
+{
+    let () = Convert::<F, Acc>::SAME_SCALE;
+    let mut sum = 0i128;
+    for x in xs {
+        let x: i128 = x.raw().into();
+        sum = sum.checked_add(x).ok_or(if x < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        })?;
+    }
+    match Acc::Raw::try_from(sum) {
+        Ok(raw) => Acc::new(raw),
+        Err(_) if sum < 0 => Err(RangeError::TooSmall),
+        Err(_) => Err(RangeError::TooLarge),
+    }
+}
+
+// `Sum` cannot report an out-of-range sum, so it is implemented for `Result` (as
+// in `iter.sum::<Result<Acc, RangeError>>()`), by way of `sum_into`.
+macro_rules! fp_impl {
+    ($Name:ident, $Acc:ident) => {
+        impl<const B0: u32, const B1: u32, const S: i32> Sum<crate::$Name<B0, S>>
+            for Result<crate::$Acc<B1, S>, RangeError>
+        {
+            fn sum<I: Iterator<Item = crate::$Name<B0, S>>>(xs: I) -> Self {
+                sum_into(xs)
+            }
+        }
+    };
+}
+
+// Implement `Sum` for every pair of types in a list.
+macro_rules! fp_impls {
+    ($all:tt, $($Name:ident),*) => {
+        $( fp_impls!(@acc $Name, $all); )*
+    };
+    (@acc $Name:ident, [$($Acc:ident),*]) => {
+        $( fp_impl!($Name, $Acc); )*
+    };
+}
+
+fp_impls!([I8, I16, I32, I64, I128], I8, I16, I32, I64, I128);
+fp_impls!([U8, U16, U32, U64, U128], U8, U16, U32, U64);
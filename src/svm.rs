@@ -0,0 +1,73 @@
+use crate::{bfp::round_shr, Num, U32};
+
+/// `sqrt(3)` and `sqrt(3) / 2` in Q30.
+const SQRT_3: i128 = 1_859_775_393;
+const SQRT_3_2: i128 = 929_887_697;
+
+/// Output of [`svm`]: the duty cycles of the three phase legs, and the sector
+/// of the voltage vector.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SvmOutput {
+    /// Duty cycles of phases a, b and c, from 0 to 1 inclusive.
+    pub duties: [U32<17, 16>; 3],
+    /// Sector of the voltage vector, from 1 to 6: sector `k` covers angles from
+    /// `60 (k - 1)` to `60 k` degrees, measured from the alpha axis toward beta.
+    /// A vector on the boundary between two sectors may be reported as either,
+    /// since the duty cycles are the same.
+    pub sector: u8,
+}
+
+/// Compile-time checks on the format of the voltage command.
+struct Command<F>(core::marker::PhantomData<F>);
+
+impl<F: Num> Command<F> {
+    const CHECK: () = {
+        assert!(F::SIGNED, "voltage command must be signed");
+        assert!(
+            F::SHIFT >= -14 && F::BITS <= 64 && F::BITS as i32 + F::SHIFT <= 96,
+            "unsupported voltage command format"
+        );
+    };
+}
+
+/// Space-vector modulation: map a stationary-frame voltage command, with
+/// components `alpha` and `beta` in units of the DC bus voltage, to the duty cycles
+/// of a three-phase inverter.
+///
+/// This is the min-max (symmetric zero-vector) form of SVM: the phase voltages of
+/// the inverse Clarke transform are offset by the midpoint of their largest and
+/// smallest values, and centered on a duty cycle of 1/2.  The modulation is linear
+/// for magnitudes up to `1 / sqrt(3)`; beyond that the duty cycles are clamped to
+/// the range from 0 to 1 (so the output voltage is distorted rather than wrapped).
+///
+/// Intermediate formats: the phase voltages are formed exactly in an `i128`, from
+/// `sqrt(3) / 2` in Q30, and the duty cycles are then rounded to nearest (with ties
+/// away from zero) at 16 fractional bits.  Compilation will fail unless `F` is
+/// signed, with at least -14 fractional bits, at most 64 bits, and at most 96
+/// bits and fractional bits together (`F::BITS + F::SHIFT <= 96`).
+pub fn svm<F: Num>(alpha: F, beta: F) -> SvmOutput
+where
+    F::Raw: Into<i128>,
+{
+    let () = Command::<F>::CHECK;
+    let (alpha, beta): (i128, i128) = (alpha.raw().into(), beta.raw().into());
+    let s = alpha * SQRT_3;
+    let sector = match (beta >= 0, beta << 30) {
+        (true, b) if s > b => 1,
+        (true, b) if s > -b => 2,
+        (true, _) => 3,
+        (false, b) if s < b => 4,
+        (false, b) if s < -b => 5,
+        (false, _) => 6,
+    };
+    // phase voltages in Q(F::SHIFT + 31)
+    let (a, b) = (alpha << 31, beta * SQRT_3_2 * 2);
+    let v = [a, b - a / 2, -b - a / 2];
+    let (max, min) = (v.iter().max().unwrap(), v.iter().min().unwrap());
+    let offset = (1 << (F::SHIFT + 30)) - (max + min) / 2;
+    let duties = v.map(|v| {
+        let d = round_shr(v + offset, (F::SHIFT + 15) as u32).clamp(0, 1 << 16);
+        unsafe { U32::new_unchecked(d as u32) }
+    });
+    SvmOutput { duties, sector }
+}
@@ -0,0 +1,167 @@
+use core::marker::PhantomData;
+
+use crate::fp_impl::pow2_f64;
+use crate::Num;
+
+/// Build an [`FpTable`] at compile time, by evaluating a function at `N` points
+/// evenly spaced over the whole range of the input format `In`, and rounding
+/// each result to the nearest value of the output format `Out`.
+///
+/// `N` must be `2^k + 1` for some `k <= In::BITS`.  Entry `i` is the value of
+/// the function at the input whose raw value is `In::MIN.raw() + i * 2^(In::BITS - k)`,
+/// so the first entry is at `In::MIN` and the last is one LSB above `In::MAX`.
+///
+/// The function is written like a closure taking an `f64`, but its body is
+/// evaluated in const context, so it may only call `const fn`s; the functions in
+/// [`const_math`](crate::const_math) cover most needs.  The table is always
+/// built at compile time, even when assigned to a `let` binding, so compilation
+/// fails if any value is out of the range of `Out`:
+///
+/// ```
+/// use fp::{const_math::powf, *};
+/// // display gamma curve, from an 8-bit code to a linear intensity
+/// const GAMMA: FpTable<U8<8, 8>, U16<16, 15>, 257> =
+///     fp_table!(|x: f64| powf(x, 2.2), 257, U8<8, 8>, U16<16, 15>);
+/// assert_eq!(GAMMA.lookup(U8::new(128).unwrap()).raw(), 7132);
+/// ```
+///
+/// ```compile_fail
+/// use fp::*;
+/// // 2x exceeds the range of the output near the top of the input range
+/// let table = fp_table!(|x: f64| 2. * x, 17, U8<8, 8>, U8<8, 8>);
+/// ```
+#[macro_export]
+macro_rules! fp_table {
+    (|$x:ident : f64| $body:expr, $n:expr, $In:ty, $Out:ty $(,)?) => {
+        const {
+            const fn f($x: f64) -> f64 {
+                $body
+            }
+            let mut raw = [0 as <$Out as $crate::Num>::Raw; $n];
+            let mut i = 0;
+            while i < $n {
+                let x = $crate::table_point::<$In>($n, i);
+                raw[i] = $crate::table_quantize::<$Out>(f(x)) as <$Out as $crate::Num>::Raw;
+                i += 1;
+            }
+            // Safety: `table_quantize` checks that every entry is in range.
+            unsafe { $crate::FpTable::<$In, $Out, $n>::from_raw_unchecked(raw) }
+        }
+    };
+}
+
+/// The logical value of table entry `i` of `n`, for input format `In`.
+#[doc(hidden)]
+pub const fn table_point<In: Num>(n: usize, i: usize) -> f64 {
+    assert!(
+        n >= 2 && (n - 1).is_power_of_two(),
+        "table length must be 2^k + 1"
+    );
+    let k = (n - 1).trailing_zeros();
+    assert!(k <= In::BITS, "more table entries than input values");
+    let min = if In::SIGNED {
+        -pow2_f64(In::BITS as i32 - 1)
+    } else {
+        0.
+    };
+    (min + i as f64 * pow2_f64((In::BITS - k) as i32)) * pow2_f64(-In::SHIFT)
+}
+
+/// The raw value nearest to `y` in output format `Out`, rounding ties away from zero.
+#[doc(hidden)]
+pub const fn table_quantize<Out: Num>(y: f64) -> i128 {
+    let scaled = y * pow2_f64(Out::SHIFT);
+    let rounded = if scaled < 0. {
+        scaled - 0.5
+    } else {
+        scaled + 0.5
+    };
+    let (min, max) = if Out::SIGNED {
+        (
+            -pow2_f64(Out::BITS as i32 - 1),
+            pow2_f64(Out::BITS as i32 - 1),
+        )
+    } else {
+        (0., pow2_f64(Out::BITS as i32))
+    };
+    assert!(
+        rounded > min - 1. && rounded < max,
+        "value out of range of the output format"
+    );
+    rounded as i128
+}
+
+/// Table of a function from fixed-point format `In` to fixed-point format
+/// `Out`, sampled at `N` evenly spaced points.  Usually built at compile time
+/// by [`fp_table!`].
+#[derive(Clone, Copy, Debug)]
+pub struct FpTable<In: Num, Out: Num, const N: usize> {
+    values: [Out::Raw; N],
+    _input: PhantomData<In>,
+}
+
+impl<In: Num, Out: Num, const N: usize> FpTable<In, Out, N> {
+    /// Create a table from its raw entries.
+    ///
+    /// # Safety
+    ///
+    /// Every entry must lie between `Out::MIN.raw()` and `Out::MAX.raw()`,
+    /// and `N` must be `2^k + 1` for some `k <= In::BITS`.
+    #[doc(hidden)]
+    pub const unsafe fn from_raw_unchecked(values: [Out::Raw; N]) -> Self {
+        Self {
+            values,
+            _input: PhantomData,
+        }
+    }
+}
+
+impl<In: Num, Out: Num, const N: usize> FpTable<In, Out, N>
+where
+    In::Raw: Into<i128>,
+    Out::Raw: Into<i128> + TryFrom<i128>,
+{
+    /// Number of raw input values between consecutive table entries, as a power of two.
+    const STEP_BITS: u32 = In::BITS - (N - 1).trailing_zeros();
+    /// Return entry `i` of the table, or `None` if `i >= N`.
+    pub fn get(&self, i: usize) -> Option<Out> {
+        let raw = *self.values.get(i)?;
+        Some(unsafe { Out::new_unchecked(raw) })
+    }
+    /// Return the fixed-point offset of `x` from the first entry, split into the
+    /// index of the entry below `x` and the raw distance above that entry.
+    fn position(x: In) -> (usize, i128) {
+        let offset = x.raw().into() - In::MIN.raw().into();
+        let index = (offset >> Self::STEP_BITS) as usize;
+        (index, offset & ((1 << Self::STEP_BITS) - 1))
+    }
+    /// Return the table entry nearest to `x` (rounding ties upward).
+    pub fn lookup(&self, x: In) -> Out {
+        let (index, frac) = Self::position(x);
+        let half = (1 << Self::STEP_BITS) >> 1;
+        let index = if Self::STEP_BITS > 0 && frac >= half {
+            index + 1
+        } else {
+            index
+        };
+        unsafe { Out::new_unchecked(self.values[index]) }
+    }
+    /// Return the value at `x` by linear interpolation between the two nearest
+    /// table entries, rounded to the nearest LSB of `Out`.  The slope of each
+    /// segment is the difference of its endpoints, so no separate slope table is needed.
+    pub fn interpolate(&self, x: In) -> Out {
+        let (index, frac) = Self::position(x);
+        let y0: i128 = self.values[index].into();
+        if frac == 0 {
+            return unsafe { Out::new_unchecked(self.values[index]) };
+        }
+        let y1: i128 = self.values[index + 1].into();
+        let half = 1 << (Self::STEP_BITS - 1);
+        let y = y0 + (((y1 - y0) * frac + half) >> Self::STEP_BITS);
+        unsafe { Out::new_unchecked(y.try_into().ok().unwrap()) }
+    }
+    /// Return the raw values of all table entries.
+    pub fn raw(&self) -> &[Out::Raw; N] {
+        &self.values
+    }
+}
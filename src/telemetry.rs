@@ -0,0 +1,169 @@
+//! A compact, self-describing wire format for logging fixed-point values.
+//!
+//! Each [`Record`] carries its own [`Format`] (signedness, `BITS` and `SHIFT`)
+//! alongside the raw value, so a host-side tool can decode a log into logical
+//! values without being compiled against the firmware's types.  The encoding of
+//! a record is:
+//!
+//! 1. the *tag*, `zigzag(SHIFT) << 1 | SIGNED`, as an unsigned LEB128 varint
+//!    (one byte for `-32 <= SHIFT < 32`);
+//! 2. `BITS`, as one byte (0 to 128);
+//! 3. the low `BITS` bits of the raw value (two's complement), in
+//!    `ceil(BITS / 8)` little-endian bytes, with unused high bits zero.
+//!
+//! So, e.g., an `I16<12, 8>` occupies 4 bytes.
+
+use crate::Num;
+
+/// Upper bound on the encoded length of any record, in bytes.
+pub const MAX_RECORD_LEN: usize = 5 + 1 + 16;
+
+/// The format of a fixed-point number, i.e. everything about its type except
+/// the raw type.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Format {
+    pub signed: bool,
+    pub bits: u32,
+    pub shift: i32,
+}
+
+impl Format {
+    /// Return the format of `F`.
+    pub fn of<F: Num>() -> Self {
+        Self {
+            signed: F::SIGNED,
+            bits: F::BITS,
+            shift: F::SHIFT,
+        }
+    }
+}
+
+/// Error decoding a [`Record`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The input ended in the middle of a record.
+    Truncated,
+    /// The record is not valid: its format cannot be represented, or its value
+    /// has nonzero bits beyond `BITS`.
+    Invalid,
+}
+
+/// A fixed-point value together with its format.  Values are held as `i128`,
+/// so 128-bit unsigned formats are not supported.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Record {
+    pub format: Format,
+    pub raw: i128,
+}
+
+impl Record {
+    /// Create a record of `x`.
+    pub fn new<F: Num>(x: F) -> Self
+    where
+        F::Raw: Into<i128>,
+    {
+        Self {
+            format: Format::of::<F>(),
+            raw: x.raw().into(),
+        }
+    }
+    /// Return the logical value of the record.  Truncation is possible.
+    pub fn into_f64(self) -> f64 {
+        self.raw as f64 * crate::fp_impl::pow2_f64(self.format.shift.saturating_neg())
+    }
+    /// Return the value of the record as an `F`, or `None` if the record's
+    /// format is not exactly the format of `F`.
+    pub fn into_fp<F: Num>(self) -> Option<F>
+    where
+        F::Raw: TryFrom<i128>,
+    {
+        if self.format != Format::of::<F>() {
+            return None;
+        }
+        F::new(self.raw.try_into().ok()?).ok()
+    }
+    /// Encode the record at the start of `buf`, and return the number of bytes
+    /// written, or `None` (with the contents of `buf` unspecified) if `buf` is too short.
+    /// Also returns `None` for a format which `decode` would reject, i.e. more than
+    /// 128 bits, or 128 unsigned bits.
+    pub fn encode(&self, buf: &mut [u8]) -> Option<usize> {
+        let Format {
+            signed,
+            bits,
+            shift,
+        } = self.format;
+        if bits > 128 || (bits == 128 && !signed) {
+            return None;
+        }
+        let zigzag = ((shift << 1) ^ (shift >> 31)) as u32 as u64;
+        let mut tag = zigzag << 1 | signed as u64;
+        let mut len = 0;
+        loop {
+            let byte = tag as u8 & 0x7f;
+            tag >>= 7;
+            *buf.get_mut(len)? = if tag == 0 { byte } else { byte | 0x80 };
+            len += 1;
+            if tag == 0 {
+                break;
+            }
+        }
+        *buf.get_mut(len)? = bits as u8;
+        len += 1;
+        let value_len = bits.div_ceil(8) as usize;
+        let masked = if bits == 0 {
+            0
+        } else {
+            self.raw as u128 & (u128::MAX >> (128 - bits))
+        };
+        buf.get_mut(len..len + value_len)?
+            .copy_from_slice(&masked.to_le_bytes()[..value_len]);
+        Some(len + value_len)
+    }
+    /// Decode a record from the start of `buf`, and return it along with the
+    /// number of bytes it occupied.
+    pub fn decode(buf: &[u8]) -> Result<(Self, usize), DecodeError> {
+        let mut tag: u64 = 0;
+        let mut len = 0;
+        loop {
+            let byte = *buf.get(len).ok_or(DecodeError::Truncated)?;
+            if len == 5 {
+                return Err(DecodeError::Invalid);
+            }
+            tag |= ((byte & 0x7f) as u64) << (7 * len);
+            len += 1;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let signed = tag & 1 == 1;
+        let zigzag = u32::try_from(tag >> 1).map_err(|_| DecodeError::Invalid)?;
+        let shift = (zigzag >> 1) as i32 ^ -((zigzag & 1) as i32);
+        let bits = *buf.get(len).ok_or(DecodeError::Truncated)? as u32;
+        len += 1;
+        if bits > 128 || (bits == 128 && !signed) {
+            return Err(DecodeError::Invalid);
+        }
+        let value_len = bits.div_ceil(8) as usize;
+        let value = buf
+            .get(len..len + value_len)
+            .ok_or(DecodeError::Truncated)?;
+        let mut bytes = [0; 16];
+        bytes[..value_len].copy_from_slice(value);
+        let masked = u128::from_le_bytes(bytes);
+        if bits < 128 && masked >> bits != 0 {
+            return Err(DecodeError::Invalid);
+        }
+        let raw = if signed && bits > 0 {
+            // sign-extend from the top stored bit
+            (masked << (128 - bits)) as i128 >> (128 - bits)
+        } else {
+            masked as i128
+        };
+        let format = Format {
+            signed,
+            bits,
+            shift,
+        };
+        Ok((Self { format, raw }, len + value_len))
+    }
+}
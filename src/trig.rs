@@ -0,0 +1,196 @@
+//! Sine and cosine by CORDIC, for oscillators, rotations and demodulation on
+//! targets without floating point.
+//!
+//! Angles are in turns, as in [`sin_lut`](crate::sin_lut): a `U32<32, 32>` wraps
+//! modulo one turn, so a phase accumulator can be passed directly (and a signed
+//! angle can be converted with `wrapping_cast`).  Results are in `[-1, 1]`, with
+//! `FRAC` fraction bits chosen by the caller, up to 30.  The iterations use only
+//! shifts and additions on 64-bit integers, so the results are the same on every
+//! target, and are within one LSB of the true value, which is rounded to nearest.
+//!
+//! The same iterations, in vectoring mode, give `atan2` and `hypot` for signed
+//! numbers of up to 64 bits.  The angle is in turns, as an `I32<32, 32>` in
+//! `[-1/2, 1/2)`, and the magnitude has the type of the inputs, made unsigned
+//! (which always fits, since `|MIN| * sqrt(2) < 2^BITS`).
+
+use crate::{
+    checked::mul_shift, const_math, sqrt::isqrt, Num, I16, I32, I64, I8, U16, U32, U64, U8,
+};
+#[cfg(not(feature = "deterministic"))]
+use crate::{Isize, Usize};
+
+/// Fraction bits of the internal coordinates, and of the internal angle (in
+/// turns).
+const WORK: u32 = 40;
+
+/// Number of iterations.  The last rotation is by less than `2^-WORK` radians.
+const ITERATIONS: usize = WORK as usize;
+
+/// `atan(2^-i)`, in units of `2^-WORK` turns.
+static ATAN: [i64; ITERATIONS] = {
+    let mut table = [0; ITERATIONS];
+    let mut i = 0;
+    let mut t = 1.;
+    while i < ITERATIONS {
+        let turns = const_math::atan(t) / core::f64::consts::TAU;
+        table[i] = (turns * (1u64 << WORK) as f64 + 0.5) as i64;
+        t /= 2.;
+        i += 1;
+    }
+    table
+};
+
+/// The reciprocal of the gain of the iterations, `prod 1 / sqrt(1 + 2^-2i)`, in
+/// units of `2^-63`.  Its square is formed in integers, to `2^-126`, so that it is
+/// accurate enough to scale magnitudes of 64-bit vectors.
+const INV_GAIN_Q63: u128 = {
+    let mut square = 1u128 << 126;
+    let mut i = 0;
+    while i < ITERATIONS {
+        // multiply by 1 / (1 + 4^-i) = 1 - 1 / (4^i + 1)
+        square -= square / ((1 << (2 * i)) + 1);
+        i += 1;
+    }
+    isqrt(square, 0)
+};
+
+/// The reciprocal of the gain of the iterations, in units of `2^-WORK`.
+const INV_GAIN: i64 = ((INV_GAIN_Q63 + (1 << 22)) >> 23) as i64;
+
+struct Frac<const FRAC: u32>;
+
+impl<const FRAC: u32> Frac<FRAC> {
+    const CHECK: () = assert!(FRAC <= 30, "trig results have at most 30 fraction bits");
+}
+
+/// `(cos, sin)` of `turns`, in units of `2^-WORK`.
+fn rotate(turns: U32<32, 32>) -> (i64, i64) {
+    // rotate by a half turn first if needed, so that the remaining angle is within
+    // a quarter turn, where the iterations converge
+    let mut angle = turns.raw() as i32;
+    let flip = angle.unsigned_abs() > 1 << 30;
+    if flip {
+        angle = angle.wrapping_add(i32::MIN);
+    }
+    let mut z = (angle as i64) << (WORK - 32);
+    let (mut x, mut y) = (INV_GAIN, 0);
+    for (i, atan) in ATAN.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if z < 0 {
+            (x, y, z) = (x + dx, y - dy, z + atan);
+        } else {
+            (x, y, z) = (x - dx, y + dy, z - atan);
+        }
+    }
+    if flip {
+        (-x, -y)
+    } else {
+        (x, y)
+    }
+}
+
+/// Round a value in units of `2^-WORK` to `FRAC` fraction bits.
+fn round<const FRAC: u32>(v: i64) -> I32<{ FRAC + 2 }, { FRAC as i32 }> {
+    let () = Frac::<FRAC>::CHECK;
+    let k = WORK - FRAC;
+    let one = 1 << FRAC;
+    let raw = ((v + (1 << (k - 1))) >> k).clamp(-one, one);
+    unsafe { I32::new_unchecked(raw as i32) }
+}
+
+/// Return the cosine and sine of an angle in turns, with `FRAC` fraction bits
+/// (computing both costs the same as computing either).
+pub fn cos_sin<const FRAC: u32>(
+    turns: U32<32, 32>,
+) -> (
+    I32<{ FRAC + 2 }, { FRAC as i32 }>,
+    I32<{ FRAC + 2 }, { FRAC as i32 }>,
+) {
+    let (x, y) = rotate(turns);
+    (round::<FRAC>(x), round::<FRAC>(y))
+}
+
+/// Return the sine of an angle in turns, with `FRAC` fraction bits.
+pub fn sin<const FRAC: u32>(turns: U32<32, 32>) -> I32<{ FRAC + 2 }, { FRAC as i32 }> {
+    round::<FRAC>(rotate(turns).1)
+}
+
+/// Return the cosine of an angle in turns, with `FRAC` fraction bits.
+pub fn cos<const FRAC: u32>(turns: U32<32, 32>) -> I32<{ FRAC + 2 }, { FRAC as i32 }> {
+    round::<FRAC>(rotate(turns).0)
+}
+
+/// Rotate `(x, y)` onto the positive x axis, and return the rotated `x` (which is
+/// the magnitude, times the gain of the iterations) and the angle of the vector, in
+/// units of `2^-WORK` turns.
+fn vector(mut x: i128, mut y: i128) -> (i128, i64) {
+    // rotate by a half turn first if needed, as in `rotate`
+    let mut z = 0;
+    if x < 0 {
+        z = if y < 0 {
+            -1 << (WORK - 1)
+        } else {
+            1 << (WORK - 1)
+        };
+        (x, y) = (-x, -y);
+    }
+    for (i, atan) in ATAN.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if y > 0 {
+            (x, y, z) = (x + dx, y - dy, z + atan);
+        } else {
+            (x, y, z) = (x - dx, y + dy, z - atan);
+        }
+    }
+    (x, z)
+}
+
+/// Return the rotated `x` and the angle from `vector`, with `(x, y)` first shifted
+/// left so that the larger magnitude has 100 bits, along with that shift (or
+/// `None` for the zero vector).
+fn vector_normalized(x: i128, y: i128) -> Option<(i128, i64, u32)> {
+    let len = u128::BITS - (x.unsigned_abs() | y.unsigned_abs()).leading_zeros();
+    let norm = 100u32.checked_sub(len).filter(|_| len > 0)?;
+    let (x, z) = vector(x << norm, y << norm);
+    Some((x, z, norm))
+}
+
+macro_rules! fp_impl {
+    ($Iname:ident, $Uname:ident, $U:ty) => {
+        impl<const B: u32, const S: i32> $Iname<B, S> {
+            /// Return the angle of the vector `(x, y)`, where this number is `y`, in
+            /// turns, rounded to nearest.  The angle of the zero vector is zero, and
+            /// the angle of a vector on the negative x axis is `-1/2`.
+            pub fn atan2(self, x: Self) -> I32<32, 32> {
+                let z = match vector_normalized(x.raw() as i128, self.raw() as i128) {
+                    Some((_, z, _)) => z,
+                    None => 0,
+                };
+                // a half turn wraps to minus a half turn
+                let raw = ((z + (1 << (WORK - 33))) >> (WORK - 32)) as i32;
+                unsafe { I32::new_unchecked(raw) }
+            }
+            /// Return the magnitude `sqrt(x^2 + y^2)` of the vector `(x, y)`, where
+            /// this number is `x`, rounded to within one LSB (or two, for 64-bit
+            /// raw types).
+            pub fn hypot(self, y: Self) -> $Uname<B, S> {
+                let raw = match vector_normalized(self.raw() as i128, y.raw() as i128) {
+                    Some((x, _, norm)) => {
+                        // the scaled magnitude is the nearest multiple of 1/2
+                        let (_, m, _) = mul_shift(false, x as u128, INV_GAIN_Q63, 62 + norm as i32);
+                        ((m + 1) >> 1).min($Uname::<B, S>::MAX.raw() as u128) as $U
+                    }
+                    None => 0,
+                };
+                unsafe { $Uname::new_unchecked(raw) }
+            }
+        }
+    };
+}
+
+fp_impl!(I8, U8, u8);
+fp_impl!(I16, U16, u16);
+fp_impl!(I32, U32, u32);
+fp_impl!(I64, U64, u64);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, Usize, usize);
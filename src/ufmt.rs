@@ -0,0 +1,111 @@
+//! Formatting without `core::fmt`, for targets (e.g. bootloaders) where its code
+//! size is too large, in the style of the `ufmt` crate.
+//!
+//! The traits here have the same shape as `ufmt`'s `uWrite`, `uDisplay` and
+//! `uDebug`, but are defined in this crate, so that the feature needs no
+//! dependency.  A logging stack built on `ufmt` can forward its formatter to
+//! them with a few lines: implement [`uWrite`] for (a wrapper of) its writer, and
+//! call [`uDisplay::fmt`] with a [`Formatter`] of that writer.
+//!
+//! Every fixed-point type implements [`uDisplay`], which writes the exact
+//! decimal value (as [`write_decimal`](crate::I32::write_decimal) does), and
+//! [`uDebug`], which writes the type name and raw value (as `Debug` does, e.g.
+//! `I16(384)`).  Neither uses floating point or allocation.
+#![allow(non_camel_case_types)]
+
+use crate::decimal::{decimal_len, write_decimal};
+#[cfg(not(feature = "deterministic"))]
+use crate::{Isize, Usize};
+use crate::{I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
+
+/// A sink for formatted output, like `ufmt::uWrite`.
+pub trait uWrite {
+    /// The error returned if the output cannot be written.
+    type Error;
+    /// Write `s` to the output.
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error>;
+}
+
+/// The writer passed to [`uDisplay::fmt`] and [`uDebug::fmt`].
+pub struct Formatter<'w, W: uWrite + ?Sized> {
+    writer: &'w mut W,
+}
+
+impl<'w, W: uWrite + ?Sized> Formatter<'w, W> {
+    /// Create a formatter which writes to `writer`.
+    pub fn new(writer: &'w mut W) -> Self {
+        Self { writer }
+    }
+    /// Write `s` to the output.
+    pub fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        self.writer.write_str(s)
+    }
+}
+
+/// User-facing formatting, like `ufmt::uDisplay`.
+pub trait uDisplay {
+    /// Write `self` to `f`.
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>;
+}
+
+/// Programmer-facing formatting, like `ufmt::uDebug`.
+pub trait uDebug {
+    /// Write `self` to `f`.
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>;
+}
+
+/// Write the ASCII of `buf[..len]`, whose length was sized in advance, to `f`.
+fn write_ascii<W: uWrite + ?Sized>(f: &mut Formatter<'_, W>, buf: &[u8]) -> Result<(), W::Error> {
+    // Safety: only ASCII digits, '-' and '.' are written by `write_decimal`.
+    f.write_str(unsafe { core::str::from_utf8_unchecked(buf) })
+}
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        impl<const B: u32, const S: i32> uDisplay for $Name<B, S>
+        where
+            [(); decimal_len(B, S)]:,
+        {
+            fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+                let mut buf = [0; decimal_len(B, S)];
+                // DECIMAL_LEN bytes are always enough
+                let len = self.write_decimal(&mut buf).ok().unwrap();
+                write_ascii(f, &buf[..len])
+            }
+        }
+
+        impl<const B: u32, const S: i32> uDebug for $Name<B, S> {
+            fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+                let raw = crate::Num::raw(*self);
+                #[allow(unused_comparisons)] // (this code runs for both signed and unsigned types)
+                let negative = raw < 0;
+                let mag = if negative {
+                    (raw as i128).unsigned_abs()
+                } else {
+                    raw as u128
+                };
+                // a sign and the 39 digits of u128::MAX
+                let mut buf = [0; 40];
+                let len = write_decimal(&mut buf, negative, mag, 0).ok().unwrap();
+                f.write_str(concat!(stringify!($Name), "("))?;
+                write_ascii(f, &buf[..len])?;
+                f.write_str(")")
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+fp_impl!(I128, i128);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
@@ -0,0 +1,74 @@
+//! A compact representation of fixed-point values for JavaScript, e.g. across a
+//! `wasm-bindgen` boundary, so that a browser UI can display and round-trip the
+//! values of a device exactly instead of re-implementing their scaling.
+
+use crate::{bfp::round_shr, schema::decimal, Num, RangeError};
+
+/// A fixed-point value as its raw value and its shift, i.e. `raw * 2^-shift`,
+/// in any format of at most 64 bits.  Both fields are plain integers, which
+/// cross a `wasm-bindgen` boundary unchanged (`raw` as a `BigInt`), so a value
+/// sent to JavaScript and back is exactly the value that was sent.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct JsFixed {
+    pub raw: i64,
+    pub shift: i32,
+}
+
+impl JsFixed {
+    /// Return the representation of `x`, or `None` if its raw value does not
+    /// fit in an `i64` (i.e. for the upper half of a 64-bit unsigned format, or
+    /// for 128-bit formats).
+    pub fn from_fp<F: Num>(x: F) -> Option<Self>
+    where
+        F::Raw: Into<i128>,
+    {
+        Some(Self {
+            raw: x.raw().into().try_into().ok()?,
+            shift: F::SHIFT,
+        })
+    }
+    /// Return the value as an `F`, or a `RangeError` if it is out of the range of
+    /// `F`.  This is exact if `F` has at least the precision of `self` (e.g. if
+    /// `self` came from an `F`), and otherwise rounds to nearest, with ties away
+    /// from zero.
+    pub fn to_fp<F: Num>(self) -> Result<F, RangeError>
+    where
+        F::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let raw = self.raw as i128;
+        let out_of_range = if raw < 0 {
+            RangeError::TooSmall
+        } else {
+            RangeError::TooLarge
+        };
+        let k = F::SHIFT as i64 - self.shift as i64;
+        let raw = if k <= 0 {
+            round_shr(raw, (-k).min(127) as u32)
+        } else if raw == 0 {
+            0
+        } else if k < 64 {
+            // |raw| < 2^63, so this cannot overflow
+            raw << k
+        } else {
+            return Err(out_of_range);
+        };
+        let (min, max): (i128, i128) = (F::MIN.raw().into(), F::MAX.raw().into());
+        if raw < min {
+            Err(RangeError::TooSmall)
+        } else if raw > max {
+            Err(RangeError::TooLarge)
+        } else {
+            Ok(unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) })
+        }
+    }
+    /// Return the value as an `f64` (rounded, if it has more than 53 significant
+    /// bits), for plotting.  Use [`to_decimal_string`](Self::to_decimal_string)
+    /// to display it exactly.
+    pub fn to_f64(self) -> f64 {
+        self.raw as f64 * crate::fp_impl::pow2_f64(self.shift.saturating_neg())
+    }
+    /// Return the exact decimal representation of the value, e.g. `"-1.5"`.
+    pub fn to_decimal_string(self) -> String {
+        decimal(self.raw < 0, self.raw.unsigned_abs() as u128, self.shift)
+    }
+}
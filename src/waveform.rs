@@ -0,0 +1,187 @@
+use core::marker::PhantomData;
+
+use crate::{const_math, Num, I32, U32};
+
+/// Number of intervals in the quarter-wave sine table.
+const QUARTER_LEN: usize = 256;
+
+/// Quarter-wave sine table, `sin(pi/2 * i / QUARTER_LEN)` in unsigned Q0.31
+/// (so that the final entry is exactly `1 << 31`).
+static QUARTER_SINE: [u32; QUARTER_LEN + 1] = {
+    let mut table = [0; QUARTER_LEN + 1];
+    let mut i = 0;
+    while i <= QUARTER_LEN {
+        let x = core::f64::consts::FRAC_PI_2 * i as f64 / QUARTER_LEN as f64;
+        table[i] = (const_math::sin(x) * (1u64 << 31) as f64 + 0.5) as u32;
+        i += 1;
+    }
+    table
+};
+
+/// Sine of `phase` (in turns), in Q31 as an `i64` in `[-2^31, 2^31]`.
+fn sin_q31(phase: u32) -> i64 {
+    // The top two bits select the quadrant, the next 8 bits index the table,
+    // and the remaining 22 bits interpolate linearly between table entries.
+    let quadrant = phase >> 30;
+    let offset = phase & ((1 << 30) - 1);
+    let offset = if quadrant & 1 == 0 {
+        offset
+    } else {
+        (1 << 30) - offset
+    };
+    let index = (offset >> 22) as usize;
+    let frac = (offset & ((1 << 22) - 1)) as i64;
+    let value = if index == QUARTER_LEN {
+        QUARTER_SINE[QUARTER_LEN] as i64
+    } else {
+        let (y0, y1) = (QUARTER_SINE[index] as i64, QUARTER_SINE[index + 1] as i64);
+        y0 + (((y1 - y0) * frac + (1 << 21)) >> 22)
+    };
+    if quadrant < 2 {
+        value
+    } else {
+        -value
+    }
+}
+
+/// Sine of an angle expressed in turns (i.e. `turns == 0.25` is a right angle), by
+/// interpolated table lookup.  The result is in Q1.30, and is accurate to within
+/// 5e-6 (the error of linear interpolation over a 1024-point table).
+pub fn sin_lut(turns: U32<32, 32>) -> I32<32, 30> {
+    unsafe { I32::new_unchecked((sin_q31(turns.raw()) >> 1) as i32) }
+}
+
+/// Cosine of an angle expressed in turns; see [`sin_lut`].
+pub fn cos_lut(turns: U32<32, 32>) -> I32<32, 30> {
+    unsafe { I32::new_unchecked((sin_q31(turns.raw().wrapping_add(1 << 30)) >> 1) as i32) }
+}
+
+/// The shape of a [`Waveform`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shape {
+    /// Sine wave, starting at zero and rising.
+    Sine,
+    /// Square wave: positive full scale for the first half of each period,
+    /// then negative full scale.
+    Square,
+    /// Triangle wave, starting at zero and rising.
+    Triangle,
+    /// Sawtooth wave, starting at zero, rising to positive full scale and then
+    /// stepping to negative full scale halfway through each period.
+    Sawtooth,
+}
+
+/// An infinite iterator of samples of a periodic test waveform, of frequency `FREQ`
+/// at a sample rate of `RATE` (both in Hz, or any other common unit).
+///
+/// Samples span the full scale of the output type `F`, symmetrically: from
+/// `-(2^(BITS-1) - 1)` to `2^(BITS-1) - 1` for signed `F`, and from `1` to
+/// `2^BITS - 1` (centered on `2^(BITS-1)`) for unsigned `F`.  The scale is
+/// relative to the raw value, so `SHIFT` has no effect.
+///
+/// The phase is tracked by a 32-bit accumulator; its increment is rounded to the
+/// nearest 2^-32 of a turn, so the generated frequency may differ from `FREQ` by
+/// up to `RATE / 2^33`.  Square, triangle and sawtooth samples are exact for the
+/// accumulated phase; sine samples come from [`sin_lut`].
+#[derive(Clone, Debug)]
+pub struct Waveform<F: Num, const RATE: u32, const FREQ: u32> {
+    shape: Shape,
+    phase: u32,
+    _output: PhantomData<F>,
+}
+
+impl<F: Num, const RATE: u32, const FREQ: u32> Waveform<F, RATE, FREQ>
+where
+    F::Raw: TryFrom<i64>,
+{
+    /// Phase increment per sample, in 2^-32 turns.
+    const STEP: u32 = {
+        assert!(FREQ < RATE, "frequency must be less than the sample rate");
+        ((((FREQ as u64) << 32) + RATE as u64 / 2) / RATE as u64) as u32
+    };
+    /// Full-scale amplitude (in raw units) of the output.
+    const AMPLITUDE: i64 = {
+        assert!(F::BITS >= 1 && F::BITS <= 63, "unsupported number of bits");
+        (1 << (F::BITS - 1)) - 1
+    };
+    /// Create a waveform generator of the given shape, starting at phase zero.
+    pub fn new(shape: Shape) -> Self {
+        let _ = (Self::STEP, Self::AMPLITUDE); // force the compile-time checks
+        Self {
+            shape,
+            phase: 0,
+            _output: PhantomData,
+        }
+    }
+    /// Create a sine wave generator: the first sample is zero, and the wave
+    /// rises to full scale a quarter of a period later.
+    pub fn sine() -> Self {
+        Self::new(Shape::Sine)
+    }
+    /// Create a square wave generator: positive full scale for the first half
+    /// of each period (including the first sample), then negative full scale.
+    pub fn square() -> Self {
+        Self::new(Shape::Square)
+    }
+    /// Create a triangle wave generator: the first sample is zero, and the wave
+    /// rises to positive full scale at a quarter of a period, falls to negative
+    /// full scale at three quarters, and returns to zero.
+    pub fn triangle() -> Self {
+        Self::new(Shape::Triangle)
+    }
+    /// Create a sawtooth wave generator: the first sample is zero, and the wave
+    /// rises to positive full scale halfway through each period, steps to
+    /// negative full scale, and rises back to zero.
+    pub fn sawtooth() -> Self {
+        Self::new(Shape::Sawtooth)
+    }
+    /// Return the current phase, in turns.
+    pub fn phase(&self) -> U32<32, 32> {
+        unsafe { U32::new_unchecked(self.phase) }
+    }
+    /// Return the current value of the waveform, in Q31 as an `i64` in `[-2^31, 2^31]`.
+    fn value_q31(&self) -> i64 {
+        let p = self.phase as i64;
+        match self.shape {
+            Shape::Sine => sin_q31(self.phase),
+            Shape::Square => {
+                if p < 1 << 31 {
+                    1 << 31
+                } else {
+                    -1 << 31
+                }
+            }
+            Shape::Triangle => {
+                if p < 1 << 30 {
+                    2 * p
+                } else if p < 3 << 30 {
+                    (1 << 32) - 2 * p
+                } else {
+                    2 * p - (1 << 33)
+                }
+            }
+            Shape::Sawtooth => self.phase as i32 as i64,
+        }
+    }
+}
+
+impl<F: Num, const RATE: u32, const FREQ: u32> Iterator for Waveform<F, RATE, FREQ>
+where
+    F::Raw: TryFrom<i64>,
+{
+    type Item = F;
+    fn next(&mut self) -> Option<F> {
+        // Scale from Q31 to the amplitude, rounding to nearest (with ties away from
+        // zero, so that the positive and negative halves of a waveform are symmetric).
+        let product = self.value_q31() as i128 * Self::AMPLITUDE as i128;
+        let magnitude = ((product.abs() + (1 << 30)) >> 31) as i64;
+        let scaled = if product < 0 { -magnitude } else { magnitude };
+        let raw = if F::SIGNED {
+            scaled
+        } else {
+            scaled + Self::AMPLITUDE + 1
+        };
+        self.phase = self.phase.wrapping_add(Self::STEP);
+        Some(unsafe { F::new_unchecked(raw.try_into().ok().unwrap()) })
+    }
+}
@@ -0,0 +1,222 @@
+use core::marker::PhantomData;
+
+use crate::Num;
+
+/// Round `num / den` to nearest, with ties away from zero.
+fn div_round(num: u128, den: u128) -> u128 {
+    let (q, r) = (num / den, num % den);
+    if r >= den - r {
+        q + 1
+    } else {
+        q
+    }
+}
+
+/// Round `(i + f / n) / d` to nearest, with ties away from zero, where
+/// `f < n` and `d < 2^127`.
+fn div_round_fraction(i: u128, f: u128, n: u128, d: u128) -> u128 {
+    let (q, r) = (i / d, i % d);
+    // the remainder is r + f / n, with 0 <= f / n < 1
+    let up = if 2 * r >= d {
+        true
+    } else if d - 2 * r == 1 {
+        2 * f >= n
+    } else {
+        false
+    };
+    q + up as u128
+}
+
+/// Streaming mean and variance of a sequence of samples, without storing them.
+///
+/// This computes the same quantities as Welford's algorithm (the count, the
+/// mean, and `M2`, the sum of squared deviations from the mean), but Welford's
+/// recurrences would round the mean on every sample, so that in fixed point the
+/// error would grow with the number of samples.  Instead, the accumulator keeps
+/// the exact sums of the deviations, and of their squares, from the first sample
+/// (in an `i128` and a `u128`), and derives the statistics from those when they
+/// are queried.  Every query is therefore correctly rounded however long the
+/// accumulator runs, and the first sample keeps the sums small when the mean is
+/// far from zero.
+///
+/// `F::BITS` must be at most 32; the sums cannot overflow for fewer than `2^60`
+/// samples.
+#[derive(Clone, Debug)]
+pub struct Welford<F: Num> {
+    count: u64,
+    pivot: i128,
+    sum: i128,
+    sum_squares: u128,
+    _sample: PhantomData<F>,
+}
+
+impl<F: Num> Default for Welford<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The shift from the format of `M2` (`2 * F::SHIFT`) to that of the variance
+/// `V`, checked at compile time.
+struct VarianceShift<F, V>(PhantomData<(F, V)>);
+
+impl<F: Num, V: Num> VarianceShift<F, V> {
+    const K: i32 = {
+        let k = V::SHIFT - 2 * F::SHIFT;
+        assert!(k >= -64 && k <= 64, "variance shift out of range");
+        k
+    };
+}
+
+impl<F: Num> Welford<F>
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    const CHECK: () = assert!(
+        F::BITS <= 32,
+        "samples wider than 32 bits are not supported"
+    );
+    /// Create an empty accumulator.
+    pub fn new() -> Self {
+        let () = Self::CHECK;
+        Self {
+            count: 0,
+            pivot: 0,
+            sum: 0,
+            sum_squares: 0,
+            _sample: PhantomData,
+        }
+    }
+    /// Forget every sample.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+    /// Add one sample.
+    pub fn push(&mut self, x: F) {
+        let x: i128 = x.raw().into();
+        if self.count == 0 {
+            self.pivot = x;
+        }
+        let d = x - self.pivot;
+        self.count += 1;
+        self.sum += d;
+        self.sum_squares += (d * d) as u128;
+    }
+    /// Add every sample of `other`, as if each had been pushed to this
+    /// accumulator, e.g. to combine accumulators of the parts of a sequence.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        } else if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        // Re-express the sums of `other` relative to this pivot: each deviation
+        // changes by delta = other.pivot - self.pivot.
+        let (n, delta) = (other.count as i128, other.pivot - self.pivot);
+        let squares = other.sum_squares as i128 + delta * (2 * other.sum + n * delta);
+        self.count += other.count;
+        self.sum += other.sum + n * delta;
+        self.sum_squares += squares as u128;
+    }
+    /// Return the number of samples.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+    /// Return the mean, rounded to nearest (with ties away from zero), or
+    /// `None` if there are no samples.
+    pub fn mean(&self) -> Option<F> {
+        if self.count == 0 {
+            return None;
+        }
+        let total = self.pivot * self.count as i128 + self.sum;
+        let q = div_round(total.unsigned_abs(), self.count as u128) as i128;
+        let mean = if total < 0 { -q } else { q };
+        Some(unsafe { F::new_unchecked(mean.try_into().ok().unwrap()) })
+    }
+    /// Return `M2`, the sum of squared deviations from the mean, truncated to
+    /// an integer in units of the squared LSB of `F` (i.e. with a shift of
+    /// `2 * F::SHIFT`).
+    pub fn m2_raw(&self) -> u128 {
+        self.m2_exact().0
+    }
+    /// Return `M2` exactly, as `(i, f)` with `M2 = i + f / n` and `f < n`, in
+    /// the units of `m2_raw`.
+    fn m2_exact(&self) -> (u128, u128) {
+        if self.count == 0 {
+            return (0, 0);
+        }
+        // M2 = sum_squares - sum^2 / n, with sum^2 / n <= sum_squares.  Writing
+        // |sum| = q n + r, sum^2 / n = q^2 n + 2 q r + r^2 / n, each term of which
+        // fits in a u128.
+        let n = self.count as u128;
+        let (q, r) = (self.sum.unsigned_abs() / n, self.sum.unsigned_abs() % n);
+        let whole = self.sum_squares - (q * q * n + 2 * q * r + (r * r) / n);
+        match (r * r) % n {
+            0 => (whole, 0),
+            b => (whole - 1, n - b),
+        }
+    }
+    /// Return `M2 / divisor` as a `V`, rounded to nearest and saturated.
+    fn scaled_m2<V: Num>(&self, divisor: u64) -> V
+    where
+        V::Raw: Into<i128> + TryFrom<i128>,
+    {
+        let k = VarianceShift::<F, V>::K;
+        let (i, f) = self.m2_exact();
+        let n = self.count as u128;
+        let max: i128 = V::MAX.raw().into();
+        let v = if k >= 0 {
+            // M2 2^k = (i 2^k + g) + h / n, where f 2^k = g n + h
+            let (g, h) = ((f << k) / n, (f << k) % n);
+            match (i.leading_zeros() > k as u32)
+                .then(|| (i << k).checked_add(g))
+                .flatten()
+            {
+                Some(i) => div_round_fraction(i, h, n, divisor as u128),
+                None => max as u128,
+            }
+        } else {
+            div_round_fraction(i, f, n, (divisor as u128) << -k)
+        };
+        let v = v.min(max as u128) as i128;
+        unsafe { V::new_unchecked(v.try_into().ok().unwrap()) }
+    }
+    /// Return the population variance, `M2 / n`, rounded to nearest in `V` and
+    /// saturated to the range of `V`, or `None` if there are no samples.
+    /// Compilation will fail unless `V::SHIFT` is within 64 of `2 * F::SHIFT`.
+    pub fn variance<V: Num>(&self) -> Option<V>
+    where
+        V::Raw: Into<i128> + TryFrom<i128>,
+    {
+        (self.count >= 1).then(|| self.scaled_m2(self.count))
+    }
+    /// Return the sample variance, `M2 / (n - 1)`, rounded to nearest in `V` and
+    /// saturated to the range of `V`, or `None` if there are fewer than two
+    /// samples.  Compilation will fail unless `V::SHIFT` is within 64 of
+    /// `2 * F::SHIFT`.
+    pub fn sample_variance<V: Num>(&self) -> Option<V>
+    where
+        V::Raw: Into<i128> + TryFrom<i128>,
+    {
+        (self.count >= 2).then(|| self.scaled_m2(self.count - 1))
+    }
+    /// Return the population standard deviation, rounded to nearest, or `None`
+    /// if there are no samples.
+    pub fn std_dev(&self) -> Option<F> {
+        if self.count == 0 {
+            return None;
+        }
+        // round(sqrt(M2 / n)) = (isqrt(floor(4 M2 / n)) + 1) / 2, and with
+        // M2 = i + f / n, floor(4 M2 / n) = floor((4 i + floor(4 f / n)) / n)
+        let (i, f) = self.m2_exact();
+        let n = self.count as u128;
+        let root = (((i << 2) + (f << 2) / n) / n).isqrt();
+        let max: i128 = F::MAX.raw().into();
+        let sd = ((root + 1) >> 1).min(max as u128) as i128;
+        Some(unsafe { F::new_unchecked(sd.try_into().ok().unwrap()) })
+    }
+}
@@ -0,0 +1,88 @@
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{checked::mul_shift, wrap_raw, Num};
+
+/// Wrapper which opts in to modular arithmetic.  Arithmetic on `Wrapping` values
+/// keeps the type of its operands, and wraps modulo `2^BITS` in the raw value (i.e.
+/// modulo `2^(BITS - SHIFT)` in the logical value), as two's-complement hardware
+/// does: e.g. for phase accumulators, or mirrors of hardware counters.
+///
+/// The compound assignment operators (`+=`, `-=` and `*=`) wrap in the same way.
+/// (For running totals which must not wrap, see `checked_add_assign` and
+/// `saturating_add_assign` on the fixed-point types.)
+///
+/// Products are rounded toward negative infinity to the shift of the operands (as
+/// `raw_shr` does) before they wrap.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Wrapping<F: Num>(pub F);
+
+macro_rules! fp_impl {
+    ($Name:ident, $T:ty) => {
+        use crate::$Name;
+        impl<const B: u32, const S: i32> Wrapping<$Name<B, S>> {
+            /// Wrap the raw value `raw` into the range of the inner type.
+            fn wrap(raw: $T) -> Self {
+                Wrapping(unsafe { $Name::new_unchecked(wrap_raw(raw, B)) })
+            }
+        }
+        impl<const B: u32, const S: i32> Add for Wrapping<$Name<B, S>> {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                Self::wrap(self.0.raw().wrapping_add(other.0.raw()))
+            }
+        }
+        impl<const B: u32, const S: i32> Sub for Wrapping<$Name<B, S>> {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                Self::wrap(self.0.raw().wrapping_sub(other.0.raw()))
+            }
+        }
+        impl<const B: u32, const S: i32> Neg for Wrapping<$Name<B, S>> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self::wrap(self.0.raw().wrapping_neg())
+            }
+        }
+        impl<const B: u32, const S: i32> Mul for Wrapping<$Name<B, S>> {
+            type Output = Self;
+            fn mul(self, other: Self) -> Self {
+                let (a_negative, a) = self.0.sign_magnitude();
+                let (b_negative, b) = other.0.sign_magnitude();
+                // only the low bits of the magnitude are needed, exact or not
+                let (negative, m, _) = mul_shift(a_negative != b_negative, a, b, S);
+                let m = if negative { m.wrapping_neg() } else { m };
+                Self::wrap(m as $T)
+            }
+        }
+        impl<const B: u32, const S: i32> AddAssign for Wrapping<$Name<B, S>> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+        impl<const B: u32, const S: i32> SubAssign for Wrapping<$Name<B, S>> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+        impl<const B: u32, const S: i32> MulAssign for Wrapping<$Name<B, S>> {
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+    };
+}
+
+fp_impl!(I8, i8);
+fp_impl!(U8, u8);
+fp_impl!(I16, i16);
+fp_impl!(U16, u16);
+fp_impl!(I32, i32);
+fp_impl!(U32, u32);
+fp_impl!(I64, i64);
+fp_impl!(U64, u64);
+fp_impl!(I128, i128);
+fp_impl!(U128, u128);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Isize, isize);
+#[cfg(not(feature = "deterministic"))]
+fp_impl!(Usize, usize);
@@ -0,0 +1,90 @@
+use fp::*;
+
+#[test]
+fn tanh_error() {
+    let mut worst: f64 = 0.;
+    for raw in (-12 << 16..12 << 16).step_by(97) {
+        let x = I32::<32, 16>::new(raw).unwrap();
+        let y: I32<32, 30> = tanh(x);
+        worst = worst.max((y.into_f64() - x.into_f64().tanh()).abs());
+        assert_eq!(
+            tanh::<_, I32<32, 30>>(I32::<32, 16>::new(-raw).unwrap()).raw(),
+            -y.raw()
+        );
+    }
+    assert!(worst < 3e-5, "{worst}");
+    // the output format sets the resolution, and saturates
+    let one: I16<16, 15> = tanh(I32::<32, 4>::new(1000).unwrap());
+    assert_eq!(one, I16::MAX);
+    let y: I8<8, 4> = tanh(I32::<32, 0>::new(1).unwrap());
+    assert_eq!(y.raw(), 12); // tanh(1) = 0.7616 rounds to 12 / 16
+    // a negative input shift scales the raw value up
+    let x = I8::<8, -2>::new(1).unwrap();
+    let y: I32<32, 30> = tanh(x);
+    assert!((y.into_f64() - 4f64.tanh()).abs() < 3e-5);
+    let y: I32<32, 30> = tanh(I8::<8, -2>::new(-1).unwrap());
+    assert!((y.into_f64() + 4f64.tanh()).abs() < 3e-5);
+    let y: I32<32, 30> = tanh(I64::<64, -100>::new(-1).unwrap());
+    assert!((y.into_f64() + 1.).abs() < 3e-5);
+}
+
+#[test]
+fn sigmoid_error() {
+    let mut worst: f64 = 0.;
+    for raw in (-20 << 16..20 << 16).step_by(89) {
+        let x = I32::<32, 16>::new(raw).unwrap();
+        let y: U32<32, 31> = sigmoid(x);
+        let exact = 1. / (1. + (-x.into_f64()).exp());
+        worst = worst.max((y.into_f64() - exact).abs());
+    }
+    assert!(worst < 1.6e-5, "{worst}");
+    let half: U8<8, 8> = sigmoid(I8::<8, 0>::new(0).unwrap());
+    assert_eq!(half.raw(), 128);
+    // 1 is not representable, so large inputs saturate
+    let y: U8<8, 8> = sigmoid(I8::<8, 0>::new(100).unwrap());
+    assert_eq!(y, U8::MAX);
+    let y: U8<8, 8> = sigmoid(I8::<8, 0>::new(-100).unwrap());
+    assert_eq!(y.raw(), 0);
+    let y: U32<32, 31> = sigmoid(I8::<8, -2>::new(1).unwrap());
+    assert!((y.into_f64() - 1. / (1. + (-4f64).exp())).abs() < 1.6e-5);
+    let y: U32<32, 31> = sigmoid(I64::<64, -100>::new(1).unwrap());
+    assert!((y.into_f64() - 1.).abs() < 1.6e-5);
+}
+
+#[test]
+fn softmax_error() {
+    let mut rng = Xorshift64::new(11);
+    for _ in 0..200 {
+        let xs = [0; 10].map(|_| I32::<20, 12>::random(&mut rng));
+        let ys: [U32<32, 24>; 10] = softmax(&xs);
+        let max = xs.iter().map(|x| x.into_f64()).fold(f64::MIN, f64::max);
+        let sum: f64 = xs.iter().map(|x| (x.into_f64() - max).exp()).sum();
+        for (x, y) in xs.iter().zip(ys) {
+            let exact = (x.into_f64() - max).exp() / sum;
+            assert!((y.into_f64() - exact).abs() < 2e-4);
+        }
+    }
+}
+
+#[test]
+fn softmax_extremes() {
+    // inputs far beyond the range of exp do not overflow
+    let xs = [I64::<64, 0>::MAX, I64::MIN, I64::MAX];
+    let ys: [U16<16, 15>; 3] = softmax(&xs);
+    assert_eq!(ys.map(|y| y.raw()), [1 << 14, 0, 1 << 14]);
+    let ys: [U16<16, 15>; 4] = softmax(&[I8::<8, 0>::new(5).unwrap(); 4]);
+    assert_eq!(ys.map(|y| y.raw()), [1 << 13; 4]);
+    // negative input shifts, up to far beyond the range of exp
+    let xs = [1, 0, -1].map(|x| I8::<8, -1>::new(x).unwrap());
+    let ys: [U32<32, 24>; 3] = softmax(&xs);
+    let sum = 1. + (-2f64).exp() + (-4f64).exp();
+    let exact = [1. / sum, (-2f64).exp() / sum, (-4f64).exp() / sum];
+    for (y, e) in ys.iter().zip(exact) {
+        assert!((y.into_f64() - e).abs() < 2e-4);
+    }
+    let xs = [3, 2].map(|x| I32::<32, -100>::new(x).unwrap());
+    let ys: [U16<16, 15>; 2] = softmax(&xs);
+    assert_eq!(ys.map(|y| y.raw()), [1 << 15, 0]);
+    let ys: [U16<16, 15>; 0] = softmax::<I8<8, 0>, _, 0>(&[]);
+    assert!(ys.is_empty());
+}
@@ -5,9 +5,9 @@ use fp::*;
 
 use core::ops::{Add, Neg, Sub};
 
-fn validate<A: Num, B: Num, C: Num>()
+fn validate<A, B: Num, C: Num>()
 where
-    A: Add<A, Output = B> + Sub<A, Output = C> + Neg<Output = C>,
+    A: Num + Add<A, Output = B> + Sub<A, Output = C> + Neg<Output = C>,
     <A as Num>::Raw: Add<<A as Num>::Raw, Output = <B as Num>::Raw>,
     <A as Num>::Raw: TryInto<<C as Num>::Raw>,
     <C as Num>::Raw: Sub<<C as Num>::Raw, Output = <C as Num>::Raw>,
@@ -35,6 +35,18 @@ where
 fn add_sub_limits() {
     validate::<I8<7, -3>, I8<8, -3>, I8<8, -3>>();
     validate::<I32<4, 0>, I32<5, 0>, I32<5, 0>>();
+    #[cfg(not(feature = "deterministic"))]
     validate::<Usize<12, 0>, Usize<13, 0>, Isize<13, 0>>();
     validate::<U128<127, 41>, U128<128, 41>, I128<128, 41>>();
 }
+
+#[test]
+fn abs() {
+    let x: U8<8, 2> = I8::<8, 2>::MIN.abs();
+    assert_eq!(x.raw(), 128);
+    assert_eq!(I8::<8, 2>::MAX.abs().raw(), 127);
+    let y: U32<5, 0> = I32::<5, 0>::new(-16).unwrap().abs();
+    assert_eq!(y.raw(), 16);
+    assert_eq!(I32::<5, 0>::new(3).unwrap().abs().raw(), 3);
+    assert_eq!(I128::<128, 0>::MIN.abs().raw(), 1 << 127);
+}
@@ -0,0 +1,46 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn align_and_add() {
+    let a = I32::<10, 2>::from_f64(-3.25).unwrap();
+    let b = I32::<12, 6>::from_f64(1.765625).unwrap();
+    let (x, y): (I32<14, 6>, I32<12, 6>) = align_shifts(a, b);
+    assert_eq!((x.into_f64(), y.into_f64()), (-3.25, 1.765625));
+    assert_eq!((x + y).into_f64(), -1.484375);
+    let (y, x): (I32<12, 6>, I32<14, 6>) = align_shifts(b, a);
+    // at a common shift, raw values compare as the logical values do
+    assert!(y.raw() > x.raw());
+}
+
+#[test]
+fn negative_shifts() {
+    let a = U16::<8, -4>::new(3).unwrap();
+    let b = U16::<4, 0>::new(15).unwrap();
+    let (x, y): (U16<12, 0>, U16<4, 0>) = align_shifts(a, b);
+    assert_eq!(x.raw(), 48);
+    assert_eq!((x + y).raw(), 63);
+    let (x, y): (U16<8, -4>, U16<8, -4>) = align_shifts(a, a);
+    assert_eq!(x, y);
+}
+
+#[test]
+fn add_aligned() {
+    let a = I32::<10, 2>::from_f64(-3.25).unwrap();
+    let b = I32::<12, 6>::from_f64(1.765625).unwrap();
+    let sum: I32<15, 6> = a.add_aligned(b);
+    assert_eq!(sum.into_f64(), -1.484375);
+    let sum: I32<15, 6> = b.add_aligned(a);
+    assert_eq!(sum.into_f64(), -1.484375);
+    // the extremes still fit
+    let sum: I32<15, 6> = I32::<10, 2>::MIN.add_aligned(I32::<12, 6>::MIN);
+    assert_eq!(sum.raw(), -(512 << 4) - 2048);
+    let c = U16::<8, -4>::MAX;
+    let d = U16::<4, 0>::MAX;
+    let sum: U16<13, 0> = c.add_aligned(d);
+    assert_eq!(sum.raw(), 255 * 16 + 15);
+    let sum: U16<9, -4> = c.add_aligned(c);
+    assert_eq!(sum.raw(), 510);
+}
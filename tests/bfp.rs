@@ -0,0 +1,123 @@
+use fp::*;
+
+#[test]
+fn normalize() {
+    let xs = [0.75, -0.125, 3.5, -4.].map(|x| I32::<32, 16>::from_f64(x).unwrap());
+    let block = BfpBlock::<4, i8>::from_fp(&xs);
+    // -4 needs 2 integer bits plus a sign bit, leaving 5 fractional bits in an i8
+    assert_eq!(block.exponent(), -5);
+    assert_eq!(block.mantissas(), &[24, -4, 112, -128]);
+    assert_eq!(block.to_f64(), [0.75, -0.125, 3.5, -4.]);
+    assert_eq!(block.to_fp::<I32<32, 16>>().unwrap(), xs);
+    let block = BfpBlock::<2, i16>::new([1, -3], 10);
+    assert_eq!(
+        (block.mantissas(), block.exponent()),
+        (&[1 << 13, -3 << 13], -3)
+    );
+    assert!(BfpBlock::<3, i32>::new([0; 3], 7).is_zero());
+}
+
+#[test]
+fn rounding() {
+    let xs = [1000, -1001, 3].map(|x| I32::<32, 0>::new(x).unwrap());
+    let block = BfpBlock::<3, i8>::from_fp(&xs);
+    assert_eq!(block.exponent(), 3);
+    // 1000 / 8 = 125 and -1001 / 8 = -125.125 round to nearest
+    assert_eq!(block.mantissas(), &[125, -125, 0]);
+    // rounding 127.5 up would overflow i8, so the exponent increases instead
+    let ys = [255, 1].map(|x| I32::<32, 1>::new(x).unwrap());
+    let block = BfpBlock::<2, i8>::from_fp(&ys);
+    assert_eq!((block.mantissas(), block.exponent()), (&[64, 0], 1));
+    assert!(matches!(
+        block.to_fp::<I32<8, 1>>(),
+        Err(RangeError::TooLarge)
+    ));
+    assert_eq!(
+        block.to_fp::<I32<10, 1>>().unwrap().map(|x| x.raw()),
+        [256, 0]
+    );
+}
+
+#[test]
+fn arithmetic() {
+    let a =
+        BfpBlock::<3, i16>::from_fp(&[1.5, -2., 0.25].map(|x| I32::<32, 8>::from_f64(x).unwrap()));
+    let b = BfpBlock::<3, i16>::from_fp(
+        &[100., 0.5, -0.25].map(|x| I32::<32, 8>::from_f64(x).unwrap()),
+    );
+    assert_eq!((a + b).to_f64(), [101.5, -1.5, 0.]);
+    assert_eq!((a - b).to_f64(), [-98.5, -2.5, 0.5]);
+    assert_eq!((a * b).to_f64(), [150., -1., -0.0625]);
+    assert_eq!((-a).to_f64(), [-1.5, 2., -0.25]);
+    let zero = BfpBlock::<3, i16>::new([0; 3], 1000);
+    assert_eq!(a + zero, a);
+    assert_eq!(zero - a, -a);
+}
+
+#[test]
+fn slices() {
+    let xs = [0.5, -1.25, 2.].map(|x| I16::<16, 8>::from_f64(x).unwrap());
+    let block = BlockFp::<i16, 3>::from_slice(&xs).unwrap();
+    assert_eq!(block, BfpBlock::<3, i16>::from_fp(&xs));
+    assert_eq!(BlockFp::<i16, 3>::from_slice(&xs[..2]), Err(LengthMismatch));
+    let mut out = [I16::<16, 8>::MIN; 3];
+    block.to_slice(&mut out).unwrap();
+    assert_eq!(out, xs);
+    let mut small = [I16::<8, 6>::MIN; 3];
+    assert!(matches!(
+        block.to_slice(&mut small),
+        Err(SliceError::Range(RangeError::TooLarge))
+    ));
+    assert_eq!(small, [I16::<8, 6>::MIN; 3]);
+    let mut short = [I16::<16, 8>::MIN; 2];
+    assert!(matches!(
+        block.to_slice(&mut short),
+        Err(SliceError::LengthMismatch)
+    ));
+    assert_eq!(short, [I16::<16, 8>::MIN; 2]);
+}
+
+#[test]
+fn renormalize() {
+    let block = BlockFp::<i8, 3>::new([100, -37, 5], -6);
+    // round to multiples of 2^-3: 12.5, -4.625 and 0.625 round away from zero
+    let coarse = block.renormalize(-3);
+    assert_eq!(coarse.to_f64(), [1.625, -0.625, 0.125]);
+    assert_eq!(
+        (coarse.mantissas(), coarse.exponent()),
+        (&[104, -40, 8], -6)
+    );
+    assert_eq!(block.renormalize(-6), block);
+    assert_eq!(block.renormalize(-100), block);
+    assert!(block.renormalize(10).is_zero());
+    assert!(block.renormalize(i32::MAX).is_zero());
+}
+
+#[test]
+fn extreme_exponents() {
+    let big = BlockFp::<i8, 2>::new([64, -3], 2_000_000_000);
+    let small = BlockFp::<i8, 2>::new([64, -3], -2_000_000_000);
+    // the smaller block is far below the rounding point of the larger one
+    assert_eq!(big + small, big);
+    assert_eq!(small - big, -big);
+    // exponents saturate rather than overflowing
+    assert_eq!((big * big).exponent(), i32::MAX);
+    assert_eq!(big.scale_pow2(i32::MAX).exponent(), i32::MAX);
+    assert!((small * small).is_zero());
+    assert!(small.scale_pow2(i32::MIN).is_zero());
+    let tiny = BlockFp::<i8, 2>::new([64, -3], i32::MIN);
+    assert_eq!(tiny.mantissas(), &[64, -3]);
+    assert_eq!(tiny.exponent(), i32::MIN);
+    assert_eq!(
+        tiny.to_fp::<I32<32, 16>>().unwrap().map(|x| x.raw()),
+        [0, 0]
+    );
+    assert!(matches!(
+        big.to_fp::<I32<32, 16>>(),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(matches!(
+        big.to_fp::<I32<32, { i32::MAX }>>(),
+        Err(RangeError::TooLarge)
+    ));
+}
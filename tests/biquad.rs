@@ -0,0 +1,71 @@
+use fp::*;
+use std::f64::consts::FRAC_1_SQRT_2;
+
+const LOWPASS: BiquadCoeffs<30> = BiquadDesign::lowpass(48000., 1000., FRAC_1_SQRT_2)
+    .quantize()
+    .unwrap();
+
+/// Reference implementation of the cookbook low-pass filter, using std floats.
+fn lowpass_reference(fs: f64, f0: f64, q: f64) -> [f64; 5] {
+    let w0 = std::f64::consts::TAU * f0 / fs;
+    let (cos, alpha) = (w0.cos(), w0.sin() / (2. * q));
+    let a0 = 1. + alpha;
+    let b = (1. - cos) / 2. / a0;
+    [b, 2. * b, b, -2. * cos / a0, (1. - alpha) / a0]
+}
+
+#[test]
+fn matches_reference() {
+    let design = BiquadDesign::lowpass(48000., 1000., FRAC_1_SQRT_2);
+    for (x, y) in design
+        .to_array()
+        .iter()
+        .zip(lowpass_reference(48000., 1000., FRAC_1_SQRT_2))
+    {
+        assert!((x - y).abs() < 1e-14);
+    }
+    for (c, x) in LOWPASS.coeffs().iter().zip(design.to_array()) {
+        assert!((c.into_f64() - x).abs() <= LOWPASS.max_error());
+    }
+    assert!(LOWPASS.max_error() <= 0.5 / (1u64 << 30) as f64);
+}
+
+/// Gain at DC (z = 1) and at Nyquist (z = -1).
+fn gains(d: BiquadDesign) -> (f64, f64) {
+    let [b0, b1, b2, a1, a2] = d.to_array();
+    (
+        (b0 + b1 + b2) / (1. + a1 + a2),
+        (b0 - b1 + b2) / (1. - a1 + a2),
+    )
+}
+
+#[test]
+fn responses() {
+    let (dc, ny) = gains(BiquadDesign::lowpass(48000., 1000., FRAC_1_SQRT_2));
+    assert!((dc - 1.).abs() < 1e-12 && ny.abs() < 1e-12);
+    let (dc, ny) = gains(BiquadDesign::highpass(48000., 1000., FRAC_1_SQRT_2));
+    assert!(dc.abs() < 1e-12 && (ny - 1.).abs() < 1e-12);
+    let (dc, ny) = gains(BiquadDesign::notch(48000., 1000., 2.));
+    assert!((dc - 1.).abs() < 1e-12 && (ny - 1.).abs() < 1e-12);
+    let db = |g: f64| 20. * g.abs().log10();
+    let (dc, ny) = gains(BiquadDesign::low_shelf(48000., 200., FRAC_1_SQRT_2, 6.));
+    assert!((db(dc) - 6.).abs() < 1e-9 && db(ny).abs() < 1e-3);
+    let (dc, ny) = gains(BiquadDesign::high_shelf(48000., 8000., FRAC_1_SQRT_2, -12.));
+    assert!(db(dc).abs() < 1e-3 && (db(ny) + 12.).abs() < 1e-9);
+}
+
+#[test]
+fn quantization_error() {
+    let design = BiquadDesign::notch(44100., 60., 10.);
+    let coarse = design.quantize::<14>().unwrap();
+    let fine = design.quantize::<28>().unwrap();
+    assert!(coarse.max_error() > fine.max_error());
+    assert!(coarse.max_error() <= 0.5 / (1 << 14) as f64);
+}
+
+#[test]
+fn out_of_range() {
+    let design = BiquadDesign::high_shelf(48000., 1000., FRAC_1_SQRT_2, 24.);
+    assert!(design.quantize::<30>().is_none());
+    assert!(design.quantize::<26>().is_some());
+}
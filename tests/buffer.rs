@@ -0,0 +1,24 @@
+use fp::*;
+
+#[test]
+fn raw_views() {
+    let xs = [-1.5, 0.25, 3.].map(|x| I16::<12, 4>::from_f64(x).unwrap());
+    assert_eq!(I16::as_raw_slice(&xs), &[-24, 4, 48]);
+    assert_eq!(I16::<12, 4>::from_raw_slice(&[-24, 4, 48]).unwrap(), &xs);
+    assert!(matches!(
+        I16::<12, 4>::from_raw_slice(&[0, 2048]),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(U8::<4, 0>::from_raw_slice(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn numpy_format() {
+    let format = NumpyFormat::of::<I16<12, 4>>().unwrap();
+    assert_eq!((format.bits, format.shift, format.scale()), (12, 4, 0.0625));
+    let little = cfg!(target_endian = "little");
+    assert_eq!(format.typestr, if little { "<i2" } else { ">i2" });
+    assert_eq!(NumpyFormat::of::<U8<8, -2>>().unwrap().typestr, "|u1");
+    assert_eq!(NumpyFormat::of::<U8<8, -2>>().unwrap().scale(), 4.);
+    assert!(NumpyFormat::of::<I128<100, 0>>().is_none());
+}
@@ -0,0 +1,135 @@
+use fp::*;
+
+#[test]
+fn add_sub_neg() {
+    let a = I16::<12, 4>::new(2000).unwrap();
+    let b = I16::<12, 4>::new(100).unwrap();
+    assert_eq!(a.checked_add(b), None);
+    assert_eq!(a.checked_sub(b).unwrap().raw(), 1900);
+    assert_eq!(b.checked_sub(a).unwrap().raw(), -1900);
+    assert_eq!(a.checked_neg().unwrap().raw(), -2000);
+    assert_eq!(I16::<12, 4>::MIN.checked_neg(), None);
+    assert_eq!(I16::<12, 4>::MIN.checked_sub(b), None);
+    // the raw type does not overflow, though the number does
+    assert_eq!(I8::<8, 0>::MAX.checked_add(I8::MAX), None);
+    let c = U8::<6, 0>::new(40).unwrap();
+    assert_eq!(c.checked_add(c), None);
+    assert_eq!(c.checked_sub(c).unwrap().raw(), 0);
+    assert_eq!(U8::<6, 0>::new(3).unwrap().checked_sub(c), None);
+    assert_eq!(c.checked_neg(), None);
+    assert_eq!(U8::<6, 0>::MIN.checked_neg(), Some(U8::MIN));
+}
+
+/// Check every product of 8-bit numbers with shift `S` against a reference.
+fn check_mul<const S: i32>() {
+    for a in -128..128 {
+        for b in -128..128 {
+            let (x, y) = (I32::<8, S>::new(a).unwrap(), I32::<8, S>::new(b).unwrap());
+            let expected = if S >= 0 { (a * b) >> S } else { (a * b) << -S };
+            let fits = (-128..128).contains(&expected);
+            assert_eq!(x.checked_mul(y).map(|z| z.raw()), fits.then_some(expected));
+        }
+    }
+}
+
+#[test]
+fn mul_small() {
+    check_mul::<-3>();
+    check_mul::<0>();
+    check_mul::<2>();
+    check_mul::<7>();
+    check_mul::<12>();
+}
+
+#[test]
+fn mul_wide() {
+    // the full 256-bit product is formed before the shift
+    let a = I128::<128, 100>::new(-3 << 100).unwrap();
+    let b = I128::<128, 100>::new(5 << 99).unwrap();
+    assert_eq!(a.checked_mul(b).unwrap().into_f64(), -7.5);
+    let c = I128::<128, 100>::new(-1).unwrap();
+    // -2^-100 * 2.5 rounds down to -2^-99
+    assert_eq!(c.checked_mul(b).unwrap().raw(), -3);
+    assert_eq!(a.checked_mul(a).unwrap().into_f64(), 9.);
+    let big = I128::<128, 100>::new(-3 << 120).unwrap();
+    assert_eq!(big.checked_mul(big), None);
+    let d = U128::<128, 64>::MAX;
+    assert_eq!(d.checked_mul(U128::new(1 << 64).unwrap()), Some(d));
+    assert_eq!(d.checked_mul(U128::new((1 << 64) + 1).unwrap()), None);
+    let e = U128::<128, 200>::MAX;
+    assert_eq!(e.checked_mul(e).unwrap().raw(), u128::MAX >> 72);
+    let f = I64::<64, -8>::new(1 << 40).unwrap();
+    assert_eq!(
+        f.checked_mul(I64::new(1 << 14).unwrap()).unwrap().raw(),
+        1 << 62
+    );
+    assert_eq!(f.checked_mul(I64::new(1 << 15).unwrap()), None);
+}
+
+#[test]
+fn saturating() {
+    let a = I16::<12, 4>::new(2000).unwrap();
+    let b = I16::<12, 4>::new(100).unwrap();
+    assert_eq!(a.saturating_add(b), I16::MAX);
+    assert_eq!(a.saturating_neg().saturating_sub(b), I16::MIN);
+    assert_eq!(a.saturating_sub(b).raw(), 1900);
+    assert_eq!(I16::<12, 4>::MIN.saturating_neg(), I16::MAX);
+    assert_eq!(I8::<8, 0>::MIN.saturating_sub(I8::MAX), I8::MIN);
+    assert_eq!(a.saturating_mul(a), I16::MAX);
+    assert_eq!(a.saturating_mul(b.saturating_neg()), I16::MIN);
+    assert_eq!(a.saturating_mul(I16::new(-10).unwrap()).raw(), -1250);
+    let c = U8::<6, 0>::new(40).unwrap();
+    assert_eq!(c.saturating_add(c), U8::MAX);
+    assert_eq!(U8::<6, 0>::new(3).unwrap().saturating_sub(c), U8::MIN);
+    assert_eq!(c.saturating_neg(), U8::MIN);
+    assert_eq!(c.saturating_mul(c), U8::MAX);
+    let d = I128::<128, 64>::MIN;
+    assert_eq!(d.saturating_mul(d), I128::MAX);
+    assert_eq!(d.saturating_mul(I128::MAX), I128::MIN);
+    assert_eq!(
+        d.saturating_mul(I128::new(1 << 63).unwrap()).raw(),
+        i128::MIN >> 1
+    );
+}
+
+#[test]
+fn assign() {
+    let mut total = I16::<12, 4>::new(2000).unwrap();
+    let d = I16::<8, 4>::new(40).unwrap();
+    total.checked_add_assign(d).unwrap();
+    assert_eq!(total.raw(), 2040);
+    assert!(matches!(
+        total.checked_add_assign(d),
+        Err(RangeError::TooLarge)
+    ));
+    assert_eq!(total.raw(), 2040);
+    total.saturating_add_assign(d);
+    assert_eq!(total, I16::MAX);
+    total.checked_sub_assign(I16::<8, 4>::MIN).unwrap_err();
+    total.checked_sub_assign(I16::<8, 4>::MAX).unwrap();
+    assert_eq!(total.raw(), 2047 - 127);
+    let mut low = I16::<12, 4>::MIN;
+    assert!(matches!(
+        low.checked_sub_assign(d),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(matches!(
+        low.checked_add_assign(I16::<8, 4>::MIN),
+        Err(RangeError::TooSmall)
+    ));
+    low.saturating_sub_assign(d);
+    assert_eq!(low, I16::MIN);
+    // the raw type overflows
+    let mut x = U8::<8, 0>::new(250).unwrap();
+    assert!(matches!(
+        x.checked_add_assign(U8::<4, 0>::MAX),
+        Err(RangeError::TooLarge)
+    ));
+    let mut y = U8::<8, 0>::MIN;
+    assert!(matches!(
+        y.checked_sub_assign(U8::<4, 0>::MAX),
+        Err(RangeError::TooSmall)
+    ));
+    x.saturating_add_assign(U8::<4, 0>::MAX);
+    assert_eq!(x, U8::MAX);
+}
@@ -0,0 +1,73 @@
+use fp::*;
+
+type F = I32<32, 20>;
+
+fn f64_cholesky(a: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut l = [[0.; 3]; 3];
+    for j in 0..3 {
+        let d: f64 = (0..j).map(|k| l[j][k] * l[j][k]).sum();
+        l[j][j] = (a[j][j] - d).sqrt();
+        for i in j + 1..3 {
+            let d: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            l[i][j] = (a[i][j] - d) / l[j][j];
+        }
+    }
+    l
+}
+
+#[test]
+fn factor_and_solve() {
+    let a = [[4., 2., -2.], [2., 10., 4.], [-2., 4., 9.]];
+    let m = Matrix::<F, 3, 3>::from_f64(a).unwrap();
+    let l = m.cholesky().unwrap();
+    let expected = f64_cholesky(a);
+    for (row, expected) in l.rows().iter().zip(expected) {
+        for (x, expected) in row.iter().zip(expected) {
+            assert!((x.into_f64() - expected).abs() <= 0.5 / (1 << 20) as f64 + 1e-12);
+        }
+    }
+    // x = [1, -2, 0.5]
+    let b = [-1., -16., -5.5].map(|b| F::from_f64(b).unwrap());
+    let x = m.cholesky_solve(b).unwrap();
+    for (x, expected) in x.iter().zip([1., -2., 0.5]) {
+        assert!((x.into_f64() - expected).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn triangular() {
+    let l = Matrix::<I16<16, 4>, 2, 2>::from_f64([[2., 99.], [1., -4.]]).unwrap();
+    let x = l
+        .solve_lower([3., 5.].map(|b| I16::from_f64(b).unwrap()))
+        .unwrap();
+    assert_eq!(x.map(|x| x.into_f64()), [1.5, -0.875]);
+    let u = l.transpose();
+    let x = u
+        .solve_upper([3., 4.].map(|b| I16::from_f64(b).unwrap()))
+        .unwrap();
+    assert_eq!(x.map(|x| x.into_f64()), [2., -1.]);
+    let singular = Matrix::<I16<16, 4>, 2, 2>::from_f64([[0., 0.], [1., 1.]]).unwrap();
+    assert_eq!(
+        singular.solve_lower([I16::new(1).unwrap(); 2]),
+        Err(SolveError::Singular)
+    );
+    let x = Matrix::<I16<16, 4>, 1, 1>::from_f64([[1. / 16.]])
+        .unwrap()
+        .solve_lower([I16::from_f64(200.).unwrap()]);
+    assert_eq!(x, Err(SolveError::TooLarge));
+}
+
+#[test]
+fn not_positive_definite() {
+    let m = Matrix::<F, 2, 2>::from_f64([[1., 2.], [2., 1.]]).unwrap();
+    assert_eq!(m.cholesky(), Err(SolveError::NotPositiveDefinite));
+    let m = Matrix::<F, 2, 2>::from_f64([[-1., 0.], [0., 1.]]).unwrap();
+    assert_eq!(m.cholesky(), Err(SolveError::NotPositiveDefinite));
+    // nearly singular, but still positive definite at this precision
+    let eps = 1. / (1 << 20) as f64;
+    let m = Matrix::<F, 2, 2>::from_f64([[1., 1. - eps], [1. - eps, 1.]]).unwrap();
+    assert!(m.cholesky().is_ok());
+    // positive definite (with determinant 2 / 256), but not after rounding
+    let m = Matrix::new([[9, 5], [5, 3]].map(|row| row.map(|x| I16::<16, 4>::new(x).unwrap())));
+    assert_eq!(m.cholesky(), Err(SolveError::NotPositiveDefinite));
+}
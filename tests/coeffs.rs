@@ -0,0 +1,24 @@
+use fp::*;
+
+const HALFBAND: [f64; 7] = [-0.0322, 0., 0.2822, 0.5, 0.2822, 0., -0.0322];
+
+#[test]
+fn quantized() {
+    const TAPS: FpCoeffs<I16<16, 15>, 7> = fp_coeffs!(HALFBAND, I16<16, 15>, 0.5 / 32768.);
+    let taps = TAPS.coeffs();
+    let mut worst: f64 = 0.;
+    for (tap, c) in taps.iter().zip(HALFBAND) {
+        let error = (tap.into_f64() - c).abs();
+        assert!(error <= 0.5 / 32768.);
+        worst = worst.max(error);
+    }
+    assert_eq!(TAPS.max_error(), worst);
+    assert_eq!(TAPS.raw()[3], 16384);
+    // exact coefficients have no error
+    const EXACT: FpCoeffs<I8<8, 2>, 3> = fp_coeffs!([0.25, -1.5, 3.], I8<8, 2>, 0.);
+    assert_eq!(EXACT.raw(), &[1, -6, 12]);
+    assert_eq!(EXACT.max_error(), 0.);
+    // quantized at compile time even outside const position
+    let exact = fp_coeffs!([0.25, -1.5, 3.], I8<8, 2>, 0.);
+    assert_eq!(exact.raw(), EXACT.raw());
+}
@@ -0,0 +1,110 @@
+use fp::{color::*, Num, U16, U8};
+
+fn c(x: u8) -> U8<8, 8> {
+    U8::new(x).unwrap()
+}
+
+#[test]
+fn blend_is_exact() {
+    for src in 0..=255u32 {
+        for alpha in 0..=255u32 {
+            let dst = 255 - src / 2;
+            let exact = ((src * alpha + dst * (255 - alpha)) as f64 / 255.).round() as u8;
+            let blended = blend(c(src as u8), c(dst as u8), c(alpha as u8));
+            assert!(blended.raw() == exact);
+        }
+        let exact = ((src * (src / 3)) as f64 / 255.).round() as u8;
+        assert!(mul_channel(c(src as u8), c(src as u8 / 3)).raw() == exact);
+    }
+    assert!(blend(c(200), c(10), c(255)).raw() == 200);
+    assert!(blend(c(200), c(10), c(0)).raw() == 10);
+}
+
+#[test]
+fn blend_wide() {
+    let src = U16::<16, 8>::new(0xffff).unwrap();
+    let dst = U16::<16, 8>::new(0).unwrap();
+    assert!(blend_8_8(src, dst, c(255)) == src);
+    assert!(blend_8_8(src, dst, c(0)) == dst);
+    assert!(blend_8_8(src, dst, c(51)).raw() == 13107);
+}
+
+#[test]
+fn saturating_channels() {
+    assert!(add_channel(c(200), c(100)).raw() == 255);
+    assert!(add_channel(c(20), c(100)).raw() == 120);
+    assert!(sub_channel(c(20), c(100)).raw() == 0);
+    assert!(sub_channel(c(120), c(100)).raw() == 20);
+}
+
+#[test]
+fn yuv_reference_values() {
+    let white = [c(255), c(255), c(255)];
+    assert!(YuvMatrix::BT601.rgb_to_yuv(white).map(|x| x.raw()) == [235, 128, 128]);
+    assert!(YuvMatrix::BT709_FULL.rgb_to_yuv(white).map(|x| x.raw()) == [255, 128, 128]);
+    let red = [c(255), c(0), c(0)];
+    assert!(YuvMatrix::BT601.rgb_to_yuv(red).map(|x| x.raw()) == [81, 90, 240]);
+    assert!(YuvMatrix::BT709.rgb_to_yuv(red).map(|x| x.raw()) == [63, 102, 240]);
+    // out-of-gamut YUV saturates
+    let rgb = YuvMatrix::BT601.yuv_to_rgb([c(235), c(16), c(240)]);
+    assert!(rgb[0].raw() == 255);
+    let rgb = YuvMatrix::BT601.yuv_to_rgb([c(16), c(16), c(16)]);
+    assert!(rgb[0].raw() == 0 && rgb[2].raw() == 0);
+}
+
+#[test]
+fn yuv_round_trip() {
+    for m in [
+        YuvMatrix::BT601_FULL,
+        YuvMatrix::BT709_FULL,
+        YuvMatrix::BT601,
+        YuvMatrix::BT709,
+    ] {
+        for i in 0..4096u32 {
+            let rgb = [
+                c((i * 37) as u8),
+                c(((i * 101) >> 3) as u8),
+                c((i >> 4) as u8),
+            ];
+            let back = m.yuv_to_rgb(m.rgb_to_yuv(rgb));
+            for (a, b) in rgb.iter().zip(&back) {
+                assert!((a.raw() as i32 - b.raw() as i32).abs() <= 2);
+            }
+        }
+    }
+}
+
+fn decode(e: f64) -> f64 {
+    if e <= 0.04045 {
+        e / 12.92
+    } else {
+        ((e + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn encode(l: f64) -> f64 {
+    if l <= 0.0031308 {
+        l * 12.92
+    } else {
+        1.055 * l.powf(1. / 2.4) - 0.055
+    }
+}
+
+#[test]
+fn srgb_tables() {
+    for code in 0..=255u8 {
+        let linear = srgb_to_linear(c(code));
+        assert!((linear.into_f64() - decode(code as f64 / 255.)).abs() <= 0.5 / 32768.);
+        assert!(linear_to_srgb(linear).raw() == code);
+    }
+    assert!(srgb_to_linear(c(255)).raw() == 1 << 15);
+    for raw in (0..=u16::MAX).step_by(7) {
+        let l = U16::<16, 15>::new(raw).unwrap();
+        let exact = (encode(l.into_f64().min(1.)) * 255.).round() as u8;
+        assert!(linear_to_srgb(l).raw() == exact);
+        let e = linear_to_srgb_interp(l).into_f64();
+        assert!((e - encode(l.into_f64().min(1.)).min(65535. / 65536.)).abs() < 2.5e-4);
+        let e = U16::<16, 16>::new(raw).unwrap();
+        assert!((srgb_to_linear_interp(e).into_f64() - decode(e.into_f64())).abs() < 4e-5);
+    }
+}
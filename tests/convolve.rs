@@ -0,0 +1,44 @@
+use fp::*;
+
+fn raws<F: Num<Raw = i32>>(ys: Vec<F>) -> Vec<i32> {
+    ys.iter().map(|y| y.raw()).collect()
+}
+
+#[test]
+fn modes() {
+    let a = [1, 2, 3, 4, 5].map(|x| I16::<8, 2>::new(x).unwrap());
+    let b = [1, 1, -1, 2].map(|x| I16::<4, 1>::new(x).unwrap());
+    let conv = |mode| convolve::<_, _, I32<14, 3>>(&a, &b, mode).map(raws);
+    assert_eq!(
+        conv(ConvolveMode::Full).unwrap(),
+        [1, 3, 4, 7, 10, 7, 3, 10]
+    );
+    assert_eq!(conv(ConvolveMode::Same).unwrap(), [3, 4, 7, 10, 7]);
+    assert_eq!(conv(ConvolveMode::Valid).unwrap(), [7, 10]);
+    // the result is symmetric in the order of the inputs
+    let swapped = convolve::<_, _, I32<14, 3>>(&b, &a, ConvolveMode::Same).map(raws);
+    assert_eq!(swapped.unwrap(), [3, 4, 7, 10, 7]);
+}
+
+#[test]
+fn correlation() {
+    let a = [0, 1, 3, -2, 0, 0].map(|x| I16::<8, 0>::new(x).unwrap());
+    let template = [1, 3, -2].map(|x| I16::<8, 0>::new(x).unwrap());
+    let corr = correlate::<_, _, I32<18, 0>>(&a, &template, ConvolveMode::Valid).unwrap();
+    assert_eq!(raws(corr), [-3, 14, -3, -2]);
+    let full = correlate::<_, _, I32<18, 0>>(&a, &template, ConvolveMode::Full).unwrap();
+    assert_eq!(raws(full), [0, -2, -3, 14, -3, -2, 0, 0]);
+}
+
+#[test]
+fn accumulator_width() {
+    let a = [I16::<8, 0>::MAX; 100];
+    let b = [I16::<8, 0>::MIN; 4];
+    // only 4 terms are ever summed, so 8 + 8 + 2 bits suffice
+    let full = convolve::<_, _, I32<18, 0>>(&a, &b, ConvolveMode::Full).unwrap();
+    assert_eq!(full.len(), 103);
+    assert_eq!(full[50].raw(), 4 * 127 * -128);
+    assert!(convolve::<_, _, I32<17, 0>>(&a, &b, ConvolveMode::Full).is_err());
+    let empty = convolve::<_, _, I32<18, 0>>(&a, &b[..0], ConvolveMode::Full);
+    assert!(empty.unwrap().is_empty());
+}
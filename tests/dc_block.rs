@@ -0,0 +1,32 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn removes_dc_without_creep() {
+    let mut dc = DcBlocker::<I32<16, 15>, 6>::new();
+    let mut rng = Xorshift64::new(3);
+    let mut sum = 0i64;
+    for i in 0..100_000 {
+        // constant offset of 5000 LSBs, plus a little noise
+        let x = I32::<16, 15>::new(5000 + I32::<4, 0>::random(&mut rng).raw()).unwrap();
+        let y: I32<17, 15> = dc.process(x);
+        if i >= 50_000 {
+            sum += y.raw() as i64;
+        }
+    }
+    // the mean output is zero to within a fraction of an LSB
+    assert!(sum.abs() < 50_000 / 100);
+}
+
+#[test]
+fn passes_alternating_signal() {
+    let mut dc = DcBlocker::<I32<8, 0>, 4>::default();
+    let mut y = I32::<9, 0>::new(0).unwrap();
+    for i in 0..1000 {
+        y = dc.process(I32::new(if i % 2 == 0 { 127 } else { -128 }).unwrap());
+    }
+    // gain at Nyquist is 2 / (2 - 2^-K)
+    assert!((y.raw() + 255 * 16 / 31).abs() <= 1);
+}
@@ -0,0 +1,63 @@
+use fp::*;
+
+fn decimal<F: Num>(x: F, write: fn(F, &mut [u8]) -> Result<usize, BufferTooSmall>) -> String {
+    let mut buf = [0; 200];
+    let len = write(x, &mut buf).unwrap();
+    String::from_utf8(buf[..len].to_vec()).unwrap()
+}
+
+#[test]
+fn exact_values() {
+    let w = I32::<16, 3>::write_decimal;
+    assert_eq!(decimal(I32::from_f64(-12.375).unwrap(), w), "-12.375");
+    assert_eq!(decimal(I32::from_f64(40.).unwrap(), w), "40");
+    assert_eq!(decimal(I32::from_f64(0.125).unwrap(), w), "0.125");
+    assert_eq!(decimal(I32::from_f64(-0.5).unwrap(), w), "-0.5");
+    assert_eq!(decimal(I32::new(0).unwrap(), w), "0");
+    let w = U8::<8, 8>::write_decimal;
+    assert_eq!(decimal(U8::MAX, w), "0.99609375");
+    let w = I8::<8, -4>::write_decimal;
+    assert_eq!(decimal(I8::MIN, w), "-2048");
+    let w = I128::<128, 0>::write_decimal;
+    assert_eq!(decimal(I128::MIN, w), i128::MIN.to_string());
+    let w = U128::<128, 0>::write_decimal;
+    assert_eq!(decimal(U128::MAX, w), u128::MAX.to_string());
+}
+
+#[test]
+fn extreme_shifts() {
+    // 2^-130 has 130 decimal places
+    let x = decimal(U8::<1, 130>::new(1).unwrap(), U8::<1, 130>::write_decimal);
+    assert_eq!(x.len(), 132);
+    let exact = "0.0000000000000000000000000000000000000007346839692639296924804603357639035\
+                 486366659729825547009429698164240107871592044830322265625";
+    assert_eq!(x, exact);
+    let x = decimal(
+        I16::<2, -100>::new(-1).unwrap(),
+        I16::<2, -100>::write_decimal,
+    );
+    assert_eq!(x, "-1267650600228229401496703205376");
+}
+
+#[test]
+fn buffer_too_small() {
+    let x = I32::<16, 3>::from_f64(-12.375).unwrap();
+    let mut buf = [0; 7];
+    assert_eq!(x.write_decimal(&mut buf), Ok(7));
+    for len in 0..7 {
+        assert_eq!(x.write_decimal(&mut buf[..len]), Err(BufferTooSmall));
+    }
+}
+
+#[test]
+fn decimal_str() {
+    let mut buf = [0; U8::<8, 8>::DECIMAL_LEN];
+    assert_eq!(U8::<8, 8>::MAX.decimal_str(&mut buf), Ok("0.99609375"));
+    let mut buf = [0; I128::<128, 0>::DECIMAL_LEN];
+    let min = I128::<128, 0>::MIN.decimal_str(&mut buf).unwrap();
+    assert_eq!(min, i128::MIN.to_string());
+    assert_eq!(I128::<128, 0>::DECIMAL_LEN, 40);
+    let mut buf = [0; I16::<2, -100>::DECIMAL_LEN];
+    let x = I16::<2, -100>::new(-2).unwrap();
+    assert_eq!(x.decimal_str(&mut buf).unwrap().len(), 32);
+}
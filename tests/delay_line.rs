@@ -0,0 +1,50 @@
+use fp::*;
+
+#[test]
+fn taps() {
+    let mut line = DelayLine::<I16<12, 4>, 4>::new(I16::new(0).unwrap());
+    let mut evicted = vec![];
+    for x in 1..=6 {
+        evicted.push(line.push(I16::new(x).unwrap()).raw());
+    }
+    assert!(evicted == [0, 0, 0, 0, 1, 2]);
+    let taps: Vec<i16> = (0..5)
+        .map(|k| line.tap(k).map_or(-1, |x| x.raw()))
+        .collect();
+    assert!(taps == [6, 5, 4, 3, -1]);
+    let all: Vec<i16> = line.iter().map(|x| x.raw()).collect();
+    assert!(all == [6, 5, 4, 3]);
+}
+
+#[test]
+fn interpolated() {
+    let mut line = DelayLine::<I32<16, 0>, 3>::new(I32::new(0).unwrap());
+    for x in [100, -100, 7] {
+        line.push(I32::new(x).unwrap());
+    }
+    let tap = |d: f64| {
+        line.tap_interpolate(U32::from_f64(d).unwrap())
+            .map(|x| x.raw())
+    };
+    assert_eq!(tap(0.), Some(7));
+    assert_eq!(tap(0.5), Some(-47)); // -46.5, rounded away from zero
+    assert_eq!(tap(1.25), Some(-50));
+    assert_eq!(tap(2.), Some(100));
+    assert_eq!(tap(1.5), Some(0));
+    assert_eq!(tap(2.5), None);
+    assert_eq!(tap(3.), None);
+}
+
+#[test]
+fn comb_filter() {
+    // y[n] = x[n] + y[n - 3] / 2, as a feedback comb
+    let mut line = DelayLine::<I32<24, 8>, 3>::new(I32::new(0).unwrap());
+    let mut ys = vec![];
+    for n in 0..7 {
+        let x = if n == 0 { 256 } else { 0 };
+        let y = x + (line.tap(2).unwrap().raw() >> 1);
+        line.push(I32::new(y).unwrap());
+        ys.push(y);
+    }
+    assert!(ys == [256, 0, 0, 128, 0, 0, 64]);
+}
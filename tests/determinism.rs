@@ -0,0 +1,25 @@
+//! Known-answer tests for operations whose results must be identical on every target.
+
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn float_scaling_is_exact() {
+    assert!(I32::<32, 31>::from_f64(0.5).unwrap().raw() == 1 << 30);
+    assert!(I64::<64, -40>::new(3).unwrap().into_f64() == 3. * 2f64.powi(40));
+    assert!(I64::<64, 1074>::new(1).unwrap().into_f64() == f64::from_bits(1));
+    assert!(I32::<32, 149>::new(1).unwrap().into_f32() == f32::from_bits(1));
+    assert!(U8::<8, 8>::from_f32(0.99).unwrap().raw() == 253);
+    assert!(I16::<16, 4>::from_f64(-1.99).unwrap().raw() == -31);
+}
+
+#[test]
+fn integer_rounding() {
+    let a = I32::<8, 0>::new(-7).unwrap();
+    let b = I32::<8, 0>::new(2).unwrap();
+    assert!((a / b).raw() == -3);
+    assert!(a.raw_shr::<1>().raw() == -4);
+    assert!(a.div_const::<2>().raw() == -3);
+}
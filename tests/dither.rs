@@ -0,0 +1,50 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn q31_to_q15() {
+    let mut rng = Xorshift64::new(5);
+    // a constant quarter of an output LSB averages to a quarter, where raw_shr
+    // and round_lsb would always give zero
+    let x = I32::<32, 31>::new(1 << 14).unwrap();
+    let mut sum = 0;
+    for _ in 0..10000 {
+        let y: I32<16, 15> = x.raw_shr_dithered::<16>(&mut rng);
+        // the dither is less than one LSB either way
+        assert!((-1..=1).contains(&y.raw()));
+        sum += y.raw();
+    }
+    assert!((sum - 2500).abs() < 250, "{sum}");
+    // the error is independent of the signal, so a signal between two levels is
+    // split between them
+    let x = I32::<32, 31>::new(-3 << 15).unwrap();
+    let mut sum = 0;
+    for _ in 0..10000 {
+        sum += x.raw_shr_dithered::<16>(&mut rng).raw();
+    }
+    assert!((sum + 15000).abs() < 250, "{sum}");
+}
+
+#[test]
+fn reproducible_and_clamped() {
+    let (mut a, mut b) = (Xorshift64::new(8), Xorshift64::new(8));
+    let x = I16::<16, 15>::new(12345).unwrap();
+    for _ in 0..100 {
+        assert_eq!(
+            x.raw_shr_dithered::<8>(&mut a),
+            x.raw_shr_dithered::<8>(&mut b)
+        );
+    }
+    let mut rng = Xorshift64::new(1);
+    for _ in 0..1000 {
+        assert!(I16::<16, 15>::MAX.raw_shr_dithered::<8>(&mut rng) <= I16::<8, 7>::MAX);
+        assert!(I16::<16, 15>::MIN.raw_shr_dithered::<8>(&mut rng) >= I16::<8, 7>::MIN);
+        assert!(U8::<8, 0>::MAX.raw_shr_dithered::<4>(&mut rng) <= U8::<4, -4>::MAX);
+        // (dither below zero is clamped, for an unsigned type)
+        let zero = U8::<8, 0>::new(0).unwrap();
+        assert!(zero.raw_shr_dithered::<4>(&mut rng).raw() <= 1);
+        assert_eq!(x.raw_shr_dithered::<0>(&mut rng), x);
+    }
+}
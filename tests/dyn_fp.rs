@@ -0,0 +1,90 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::telemetry::Format;
+use fp::*;
+
+fn format(signed: bool, bits: u32, shift: i32) -> Format {
+    Format {
+        signed,
+        bits,
+        shift,
+    }
+}
+
+#[test]
+fn construction() {
+    // a 12-bit unsigned signal, scaled by 2^-4 (from a descriptor)
+    let x = DynFp::new(0xabc, format(false, 12, 4)).unwrap();
+    assert_eq!(x.into_f64(), 171.75);
+    assert!(matches!(
+        DynFp::new(0x1000, format(false, 12, 4)),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(matches!(
+        DynFp::new(-1, format(false, 12, 4)),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(DynFp::new(-8, format(true, 4, 0)).is_ok());
+    assert!(DynFp::new(8, format(true, 4, 0)).is_err());
+    assert!(DynFp::new(0, format(false, 128, 0)).is_err());
+    assert!(DynFp::new(i128::MIN, format(true, 128, 0)).is_ok());
+    let x = DynFp::new(1, format(true, 8, i32::MIN)).unwrap();
+    assert_eq!(x.into_f64(), f64::INFINITY);
+}
+
+#[test]
+fn static_conversions() {
+    let x = I16::<12, 4>::new(-24).unwrap();
+    let d = DynFp::from_fp(x);
+    assert_eq!(d.format(), Format::of::<I16<12, 4>>());
+    assert_eq!(d.raw(), -24);
+    assert_eq!(d.to_fp::<I16<12, 4>>().unwrap(), x);
+    assert_eq!(d.to_fp::<I32<20, 6>>().unwrap().raw(), -96);
+    // rounding toward negative infinity, as checked_cast does
+    assert_eq!(d.to_fp::<i8>().unwrap(), -2);
+    assert!(matches!(d.to_fp::<U8<8, 0>>(), Err(RangeError::TooSmall)));
+    assert!(matches!(
+        DynFp::new(1, format(true, 2, -200)).unwrap().to_fp::<i64>(),
+        Err(RangeError::TooLarge)
+    ));
+}
+
+#[test]
+fn arithmetic_matches_static_types() {
+    let (a, b) = (
+        I32::<12, 4>::new(-24).unwrap(),
+        I32::<10, 2>::new(7).unwrap(),
+    );
+    let (da, db) = (DynFp::from_fp(a), DynFp::from_fp(b));
+    assert_eq!(da.checked_add(db), Some(DynFp::from_fp(a.add_aligned(b))));
+    assert_eq!(da.checked_mul(db), Some(DynFp::from_fp(a * b)));
+    assert_eq!(da.checked_div(db), Some(DynFp::from_fp(a / b)));
+    let sum = da.checked_sub(db).unwrap();
+    assert_eq!(sum.format(), format(true, 13, 4));
+    assert_eq!(sum.raw(), -24 - 28);
+    let (u, v) = (U16::<8, 0>::new(3).unwrap(), U16::<8, 0>::new(200).unwrap());
+    let (du, dv) = (DynFp::from_fp(u), DynFp::from_fp(v));
+    assert_eq!(du.checked_sub(dv), Some(DynFp::from_fp(u - v)));
+    assert_eq!(du.checked_add(dv), Some(DynFp::from_fp(u + v)));
+    assert_eq!(du.checked_neg(), Some(DynFp::from_fp(-u)));
+    // mixed signedness gives a signed result, with a sign bit for the unsigned operand
+    assert_eq!(da.checked_add(du).unwrap().format(), format(true, 14, 4));
+}
+
+#[test]
+fn checked_failures() {
+    let wide = DynFp::from_fp(I128::<100, 0>::MAX);
+    assert!(wide.checked_mul(wide).is_none());
+    assert!(wide.checked_add(wide).is_some());
+    let full = DynFp::from_fp(i128::MIN);
+    assert!(full.checked_neg().is_none());
+    assert!(full.checked_add(full).is_none());
+    assert!(full.checked_div(DynFp::from_fp(-1i8)).is_none());
+    assert!(wide.checked_div(DynFp::from_fp(0u8)).is_none());
+    // shifts which cannot be aligned within 128 bits
+    let coarse = DynFp::new(1, format(true, 2, -100)).unwrap();
+    let fine = DynFp::new(1, format(true, 2, 100)).unwrap();
+    assert!(coarse.checked_add(fine).is_none());
+    assert!(coarse.checked_mul(fine).is_some());
+}
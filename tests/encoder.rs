@@ -0,0 +1,44 @@
+use fp::*;
+
+type Observer = EncoderObserver<1, 4, 1, 64>;
+
+fn count(x: i64) -> I32<32, 0> {
+    I32::new(x as i32).unwrap()
+}
+
+#[test]
+fn tracks_constant_velocity() {
+    let mut obs = Observer::default();
+    for n in 0..2000 {
+        // 0.37 counts per update, so the count only changes on some updates
+        obs.update(count(n * 37 / 100));
+    }
+    let v = obs.velocity().into_f64();
+    assert!((v - 0.37).abs() < 0.01, "{v}");
+    let p = obs.position().into_f64();
+    assert!((p - 1999. * 0.37).abs() < 1.5, "{p}");
+}
+
+#[test]
+fn counter_wraparound() {
+    let mut obs = Observer::new(count(i32::MAX as i64));
+    let mut raw = i32::MAX;
+    for _ in 0..3000 {
+        raw = raw.wrapping_add(1000);
+        obs.update(count(raw as i64));
+    }
+    assert!((obs.velocity().into_f64() - 1000.).abs() < 1e-3);
+    // the position wraps with the count
+    let expected = i32::MAX.wrapping_add(3_000_000) as f64;
+    assert!((obs.position().into_f64() - expected).abs() < 1e-3);
+}
+
+#[test]
+fn settles_after_step() {
+    let mut obs = Observer::default();
+    for _ in 0..500 {
+        obs.update(count(100));
+    }
+    assert!(obs.velocity().into_f64().abs() < 1e-3);
+    assert!((obs.position().into_f64() - 100.).abs() < 1e-3);
+}
@@ -0,0 +1,40 @@
+use fp::*;
+
+#[repr(C, packed)]
+struct Frame {
+    id: u8,
+    temperature: BeI16<12, 4>,
+    pressure: LeU32<24, 8>,
+}
+
+#[test]
+fn packed_struct() {
+    assert_eq!(core::mem::size_of::<Frame>(), 7);
+    let bytes = [7, 0x01, 0x58, 0x00, 0x80, 0x00, 0x00];
+    let frame = Frame {
+        id: bytes[0],
+        temperature: BeI16::from_bytes([bytes[1], bytes[2]]),
+        pressure: LeU32::from_bytes([bytes[3], bytes[4], bytes[5], bytes[6]]),
+    };
+    assert_eq!(frame.id, 7);
+    let temperature = frame.temperature;
+    assert_eq!(temperature.get().unwrap().into_f64(), 21.5);
+    let pressure: U32<24, 8> = frame.pressure.try_into().unwrap();
+    assert_eq!(pressure.raw(), 0x8000);
+}
+
+#[test]
+fn byte_order() {
+    let x = I32::<32, 0>::new(0x0102_0304).unwrap();
+    assert_eq!(LeI32::new(x).to_bytes(), [4, 3, 2, 1]);
+    assert_eq!(BeI32::from(x).to_bytes(), [1, 2, 3, 4]);
+    let mut y = BeU64::<40, 0>::from_bytes([0; 8]);
+    y.set(U64::new(0xab).unwrap());
+    assert_eq!(y.to_bytes(), [0, 0, 0, 0, 0, 0, 0, 0xab]);
+    assert_eq!(y.get().unwrap().raw(), 0xab);
+    let big = LeI128::<128, 64>::new(I128::new(-1).unwrap());
+    assert_eq!(big.to_bytes(), [0xff; 16]);
+    // out of range of the 12-bit type
+    let bad = LeI16::<12, 4>::from_bytes([0, 0x08]);
+    assert!(matches!(bad.get(), Err(RangeError::TooLarge)));
+}
@@ -0,0 +1,59 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn exact_ops_propagate() {
+    let a = ErrorBound::new(I32::<10, 4>::from_f64(3.25).unwrap());
+    let b = ErrorBound::with_bound_lsbs(I32::<10, 4>::from_f64(-1.5).unwrap(), 0.5);
+    assert!(b.bound() == 0.03125);
+    assert!((a + b).bound() == 0.03125);
+    assert!((a - b - b).bound() == 0.0625);
+    assert!((-b).bound() == 0.03125);
+    // |a| * 1/32, and nothing from `a`, which is exact
+    assert!((a * b).bound() == 3.25 / 32.);
+    assert!((b * b).bound() == 1.5 / 32. * 2. + 1. / 1024.);
+    assert!(a.bound_lsbs() == 0. && a.guard_bits() == 0);
+}
+
+#[test]
+fn truncation_accumulates() {
+    let x = ErrorBound::new(I32::<16, 8>::from_f64(1.7).unwrap());
+    // 0.1 is not representable: the conversion error is the bound
+    let c = ErrorBound::<I32<16, 8>>::from_f64(0.1).unwrap();
+    assert!(c.bound() == (c.value().into_f64() - 0.1).abs());
+    // raw_shr::<4> can lose 15 LSBs of the input, nearly one LSB of the output
+    let y = x.raw_shr::<4>();
+    assert!(y.bound_lsbs() == 15. / 16.);
+    assert_eq!(y.guard_bits(), 0);
+    let z = y.raw_shr::<2>();
+    assert!(z.bound_lsbs() == 15. / 64. + 3. / 4.);
+    // rounding to nearest loses at most half an LSB of the result
+    let r = x.rescale::<4, Nearest>();
+    assert!(r.bound_lsbs() == 0.5);
+    let t = x.rescale::<4, TowardZero>();
+    assert!(t.bound_lsbs() == 15. / 16.);
+    assert!(x.rescale::<10, Floor>().bound() == 0.);
+    let m = x.map(|v| v.round_lsb::<4>(), 0.5);
+    assert!(m.bound_lsbs() == 0.5);
+    // accumulating four truncations needs two guard bits
+    let sum = y + y + y + y;
+    assert!(sum.bound_lsbs() == 3.75);
+    assert_eq!(sum.guard_bits(), 2);
+}
+
+#[test]
+fn division() {
+    let a = ErrorBound::new(I32::<16, 8>::from_f64(3.).unwrap());
+    let b = ErrorBound::new(I32::<16, 8>::from_f64(2.).unwrap());
+    // exact operands: only the truncation of the quotient, one LSB at shift 0
+    let q = a / b;
+    assert!(q.bound() == 1.);
+    let b = ErrorBound::with_bound_lsbs(I32::<16, 8>::from_f64(2.).unwrap(), 256.);
+    let q = a / b;
+    // with b in [1, 3], 3/b is in [1, 3]
+    assert!(q.bound() == 1.5 / 1. + 1.);
+    let zero = ErrorBound::with_bound_lsbs(I32::<16, 8>::from_f64(1.).unwrap(), 512.);
+    assert_eq!((a / zero).guard_bits(), u32::MAX);
+}
@@ -0,0 +1,72 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn log2_exact() {
+    let x = U32::<32, 8>::from_f64(8.).unwrap();
+    let y: I32<{ log2_bits(32, 8, 16) }, 16> = x.log2::<16>().unwrap();
+    assert_eq!(y.into_f64(), 3.);
+    assert_eq!(
+        U32::<32, 8>::new(1)
+            .unwrap()
+            .log2::<16>()
+            .unwrap()
+            .into_f64(),
+        -8.
+    );
+    assert!(matches!(
+        U8::<8, 0>::new(0).unwrap().log2::<4>(),
+        Err(RangeError::TooSmall)
+    ));
+    assert_eq!(U128::<128, 0>::MAX.log2::<0>().unwrap().raw(), 127);
+    assert_eq!(
+        U8::<8, -4>::new(1).unwrap().log2::<2>().unwrap().into_f64(),
+        4.
+    );
+}
+
+#[test]
+fn log2_accuracy() {
+    for raw in 1..=u16::MAX {
+        let x = U16::<16, 4>::new(raw).unwrap();
+        let y = x.log2::<24>().unwrap();
+        let exact = (raw as f64 / 16.).log2();
+        let error = y.into_f64() - exact;
+        assert!(error <= 1e-12 && error > -(2f64.powi(-24)), "{raw}");
+    }
+}
+
+#[test]
+fn exp2_exact() {
+    let x = I16::<8, 0>::new(5).unwrap();
+    let y: U32<{ exp2_bits(5, 0, 0) }, 0> = I16::<5, 0>::new(5).unwrap().exp2::<0>();
+    assert_eq!(y.raw(), 32);
+    assert_eq!(x.wrapping_cast::<I16<6, 0>>().exp2::<0>().raw(), 32);
+    assert_eq!(I16::<6, 0>::MAX.exp2::<0>().raw(), 1 << 31);
+    assert_eq!(I16::<5, 0>::MIN.exp2::<16>().raw(), 1);
+    assert_eq!(I16::<5, 0>::MIN.exp2::<15>().raw(), 1);
+    assert_eq!(I16::<5, 0>::MIN.exp2::<14>().raw(), 0);
+    assert_eq!(I32::<8, 4>::new(-16).unwrap().exp2::<20>().into_f64(), 0.5);
+    assert_eq!(I8::<1, 0>::MIN.exp2::<8>().into_f64(), 0.5);
+}
+
+#[test]
+fn exp2_accuracy() {
+    for raw in -(1 << 15)..(1 << 15) {
+        let x = I32::<16, 12>::new(raw).unwrap();
+        let y: U32<{ 8 + 24 }, 24> = x.exp2::<24>();
+        let exact = 2f64.powf(raw as f64 / 4096.) * 2f64.powi(24);
+        assert!((y.raw() as f64 - exact).abs() <= 0.5 + 1e-6, "{raw}");
+    }
+}
+
+#[test]
+fn round_trip() {
+    // a gain in decibels, by way of log2: 6.0206 dB per factor of two
+    let gain = U32::<32, 16>::from_f64(3.).unwrap();
+    let log = gain.log2::<20>().unwrap();
+    let back: U32<{ exp2_bits(25, 20, 16) }, 16> = log.exp2::<16>();
+    assert!((back.into_f64() - 3.).abs() <= 2f64.powi(-15));
+}
@@ -0,0 +1,78 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn rounding_policies() {
+    let a = I32::<8, 4>::new(-37).unwrap();
+    let b = I32::<8, 4>::new(5).unwrap();
+    fp_exprs! {
+        floor: I32<12, 4> = a * b;
+    }
+    // -185 / 16 = -11.5625
+    assert_eq!(floor.raw(), -12);
+    fp_exprs! {
+        round: nearest;
+        nearest: I32<13, 4> = a * b;
+        exact: I32<17, 8> = a * b;
+        wider: I32<20, 12> = a * b;
+    }
+    assert_eq!(nearest.raw(), -12);
+    assert_eq!(exact.raw(), -185);
+    assert_eq!(wider.raw(), -185 << 4);
+    let c = I32::<16, 8>::new(-40).unwrap();
+    fp_exprs! {
+        round: nearest;
+        ties: I32<13, 4> = c;
+    }
+    // -2.5 rounds away from zero
+    assert_eq!(ties.raw(), -3);
+    fp_exprs! {
+        round: nearest_even;
+        even: I32<13, 4> = c;
+    }
+    assert_eq!(even.raw(), -2);
+    fp_exprs! {
+        round: toward_zero;
+        truncated: I32<12, 4> = a * b;
+    }
+    assert_eq!(truncated.raw(), -11);
+    fp_exprs! {
+        round: ceil;
+        up: I32<13, 4> = a * b;
+        down: I32<14, 4> = -(a * b);
+    }
+    assert_eq!((up.raw(), down.raw()), (-11, 12));
+}
+
+#[test]
+fn saturation_and_signedness() {
+    let x = U32::<16, 8>::MAX;
+    fp_exprs! {
+        signed: I32<17, 8> = x;
+        clipped: sat U8<4, 0> = x;
+    }
+    assert_eq!(signed.raw(), 0xffff);
+    assert_eq!(clipped, U8::MAX);
+    let y = I32::<16, 0>::MIN;
+    fp_exprs! {
+        clipped: sat U16<16, 0> = y;
+        narrow: sat I8<8, 0> = y * y;
+    }
+    assert_eq!(clipped.raw(), 0);
+    assert_eq!(narrow, I8::MAX);
+}
+
+#[test]
+fn chained_statements() {
+    let gain = I32::<16, 15>::from_f64(0.5).unwrap();
+    let x = I32::<16, 15>::from_f64(-0.25).unwrap();
+    fp_exprs! {
+        round: nearest;
+        y: I32<18, 15> = gain * x;
+        z: I32<19, 15> = y + x;
+    }
+    assert_eq!(y.into_f64(), -0.125);
+    assert_eq!(z.into_f64(), -0.375);
+}
@@ -0,0 +1,49 @@
+use fp::*;
+
+#[test]
+fn integer_delay() {
+    let mut d = FarrowDelay::<I16<16, 0>>::new();
+    let zero = U16::new(0).unwrap();
+    let out: Vec<i16> = [5, -7, 100, 3]
+        .iter()
+        .map(|&x| d.process(I16::new(x).unwrap(), zero).raw())
+        .collect();
+    assert_eq!(out, [0, 5, -7, 100]);
+}
+
+#[test]
+fn cubic_is_exact() {
+    // Lagrange interpolation reproduces cubic polynomials exactly.
+    let f = |t: f64| 0.5 * t * t * t - 3. * t * t + 2. * t - 40.;
+    let mut d = FarrowDelay::<I32<32, 0>>::default();
+    for n in 0..4 {
+        d.push(I32::new(f(n as f64) as i32).unwrap());
+    }
+    for mu in [0., 0.25, 0.5, 0.75] {
+        let y = d.interpolate(U16::from_f64(mu).unwrap()).raw() as f64;
+        assert!((y - f(2. - mu)).abs() <= 1., "{mu} {y}");
+    }
+}
+
+#[test]
+fn sine_delay() {
+    // Delay a slow sine wave by 1.3 samples.
+    let mu = U16::<16, 16>::from_f64(0.3).unwrap();
+    let w = 2. * std::f64::consts::PI / 40.;
+    let mut d = FarrowDelay::<I16<16, 15>>::new();
+    for n in 0..200 {
+        let x = (w * n as f64).sin() * 0.9;
+        let y = d.process(I16::from_f64(x).unwrap(), mu).into_f64();
+        if n >= 3 {
+            let expected = (w * (n as f64 - 1.3)).sin() * 0.9;
+            assert!((y - expected).abs() < 2e-4, "{n} {y} {expected}");
+        }
+    }
+    // Overshoot saturates.
+    let mut d = FarrowDelay::<I16<16, 15>>::new();
+    for x in [0, i16::MAX, i16::MAX, 0] {
+        d.push(I16::new(x).unwrap());
+    }
+    let half = U16::from_f64(0.5).unwrap();
+    assert_eq!(d.interpolate(half), I16::MAX);
+}
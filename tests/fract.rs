@@ -0,0 +1,40 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn fract() {
+    let x = I32::<20, 8>::from_f64(-2.25).unwrap();
+    let f: U32<8, 8> = x.fract();
+    assert_eq!(f.into_f64(), 0.75);
+    assert_eq!(U16::<16, 4>::from_f64(9.5).unwrap().fract().into_f64(), 0.5);
+    // no fraction bits
+    let g: U8<0, -2> = I8::<8, -2>::MIN.fract();
+    assert_eq!(g.raw(), 0);
+    assert_eq!(U8::<8, 8>::MAX.fract().raw(), 255);
+}
+
+#[test]
+fn split_int_frac() {
+    for raw in i16::MIN..=i16::MAX {
+        let x = I16::<16, 5>::new(raw).unwrap();
+        let (int, frac) = x.split_int_frac();
+        assert_eq!(int, x.floor());
+        assert_eq!((int.raw() << 5) + frac.raw() as i16, raw);
+    }
+    // an interpolated table lookup
+    let phase = U32::<12, 8>::from_f64(2.75).unwrap();
+    let (index, weight): (U32<4, 0>, U32<8, 8>) = phase.split_int_frac();
+    assert_eq!((index.raw(), weight.into_f64()), (2, 0.75));
+    // the extremes fit
+    let (int, frac) = I8::<8, 8>::MIN.split_int_frac();
+    assert_eq!((int.raw(), frac.raw()), (-1, 128));
+    let (int, frac) = U128::<128, 64>::MAX.split_int_frac();
+    assert_eq!(
+        (int.raw(), frac.raw()),
+        (u64::MAX as u128, u64::MAX as u128)
+    );
+    let (int, frac) = I32::<8, -3>::new(-5).unwrap().split_int_frac();
+    assert_eq!((int.raw(), frac.raw()), (-40, 0));
+}
@@ -2,7 +2,126 @@ use fp::*;
 
 #[test]
 fn good_conversions() {
-    let x: I16<7, 0> = I32::<6, 0>::new(5).unwrap().into_fp();
-    let x: I32<8, 0> = 125i8.into_fp();
-    let x: u16 = U32::<16, 0>::new(5).unwrap().into_fp();
+    let _x: I16<7, 0> = I32::<6, 0>::new(5).unwrap().into_fp();
+    let _x: I32<8, 0> = 125i8.into_fp();
+    let _x: u16 = U32::<16, 0>::new(5).unwrap().into_fp();
+}
+
+#[test]
+fn wrapping_conversions() {
+    let x = I32::<16, 4>::new(0x1234).unwrap();
+    assert_eq!(x.wrapping_set_bits::<8>().raw(), 0x34);
+    assert_eq!(x.wrapping_set_bits::<6>().raw(), -12);
+    assert_eq!(x.wrapping_set_bits::<0>().raw(), 0);
+    assert_eq!(x.wrapping_set_bits::<32>().raw(), 0x1234);
+    let phase = U32::<32, 32>::new(0xdead_beef).unwrap();
+    assert_eq!(phase.wrapping_set_bits::<12>().raw(), 0xeef);
+    let y: I8<8, 4> = x.wrap_into_fp();
+    assert_eq!(y.raw(), 0x34);
+    let y: I8<5, 4> = I32::<16, 4>::new(-17).unwrap().wrap_into_fp();
+    assert_eq!(y.raw(), 15);
+    let z: u8 = 0x1ffu16.wrap_into_fp();
+    assert_eq!(z, 0xff);
+    // widening conversions are unchanged
+    let w: I64<40, 4> = x.wrap_into_fp();
+    assert_eq!(w.raw(), 0x1234);
+}
+
+#[test]
+fn saturating_conversions() {
+    // an accumulator back to a 16-bit output
+    let acc = I64::<40, 12>::new(0x0012_3456).unwrap();
+    let y: I16<15, 12> = acc.saturating_into_fp();
+    assert_eq!(y, I16::MAX);
+    let y: I16<15, 12> = I64::<40, 12>::new(-0x0012_3456)
+        .unwrap()
+        .saturating_into_fp();
+    assert_eq!(y, I16::MIN);
+    let y: I16<15, 12> = I64::<40, 12>::new(-1234).unwrap().saturating_into_fp();
+    assert_eq!(y.raw(), -1234);
+    let z: U8<8, 0> = U32::<20, 0>::new(300).unwrap().saturating_into_fp();
+    assert_eq!(z, U8::MAX);
+    // widening conversions are unchanged
+    let w: U64<40, 0> = U32::<20, 0>::new(300).unwrap().saturating_into_fp();
+    assert_eq!(w.raw(), 300);
+    assert_eq!(1000i32.saturating_into_fp::<i8>(), i8::MAX);
+}
+
+#[test]
+fn saturating_constructors() {
+    // a 32-bit register in Q8.24, into 16-bit Q4.12 storage
+    let x = I16::<16, 12>::from_i32_saturating::<24>(0x0123_4567);
+    assert_eq!(x.raw(), 0x1234);
+    let x = I16::<16, 12>::from_i32_saturating::<24>(0x7fff_ffff);
+    assert_eq!(x, I16::MAX);
+    let x = I16::<16, 12>::from_i32_saturating::<24>(-0x0000_1001);
+    assert_eq!(x.raw(), -2);
+    let x = I16::<16, 12>::from_i64_saturating::<0>(-9);
+    assert_eq!(x, I16::MIN);
+    let x = I16::<16, 12>::from_i64_saturating::<0>(3);
+    assert_eq!(x.raw(), 3 << 12);
+    let x = U16::<10, 0>::from_i128_saturating::<0>(-5);
+    assert_eq!(x.raw(), 0);
+    let x = U16::<10, 0>::from_u64_saturating::<2>(4000);
+    assert_eq!(x.raw(), 1000);
+    let x = U16::<10, 0>::from_u32_saturating::<-1>(600);
+    assert_eq!(x, U16::MAX);
+    let x = I64::<64, 0>::from_u128_saturating::<100>(u128::MAX);
+    assert_eq!(x.raw(), (u128::MAX >> 100) as i64);
+    let x = I64::<64, 0>::from_u128_saturating::<0>(u128::MAX);
+    assert_eq!(x, I64::MAX);
+    // shifts far beyond the raw width
+    let x = I32::<32, 0>::from_i128_saturating::<-200>(1);
+    assert_eq!(x, I32::MAX);
+    let x = I32::<32, 0>::from_i128_saturating::<200>(-1);
+    assert_eq!(x.raw(), -1);
+    // the extremes of i128, whose magnitudes have no leading zeros
+    let x = I32::<16, 4>::from_i128_saturating::<0>(i128::MIN);
+    assert_eq!(x, I32::MIN);
+    let x = I32::<16, 4>::from_i128_saturating::<0>(i128::MAX);
+    assert_eq!(x, I32::MAX);
+    assert_eq!(i128::MIN.saturating_cast::<I32<16, 4>>(), I32::MIN);
+    assert_eq!(i128::MAX.saturating_cast::<I32<16, 4>>(), I32::MAX);
+    assert_eq!(i128::MIN.saturating_into_fp::<i64>(), i64::MIN);
+}
+
+#[test]
+fn try_from_float() {
+    fn parse<T: TryFrom<f64, Error = RangeError>>(x: f64) -> Result<T, RangeError> {
+        let y = T::try_from(x)?;
+        Ok(y)
+    }
+    let x: I16<12, 4> = parse(-1.5).unwrap();
+    assert_eq!(x.raw(), -24);
+    assert!(matches!(
+        parse::<I16<12, 4>>(200.),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(matches!(parse::<U8<8, 0>>(-1.), Err(RangeError::TooSmall)));
+    let y = U8::<8, 4>::try_from(2.75f32).unwrap();
+    assert_eq!(y, U8::from_f32(2.75).unwrap());
+    assert!(U8::<8, 4>::try_from(16f32).is_err());
+}
+
+#[test]
+fn casts() {
+    let x = I16::<12, 4>::new(-24).unwrap(); // -1.5
+    assert_eq!(x.checked_cast::<i32>(), Some(-2));
+    assert_eq!(x.checked_cast::<u8>(), None);
+    assert_eq!(x.saturating_cast::<u8>(), 0);
+    assert_eq!(x.wrapping_cast::<u8>(), 254);
+    assert_eq!(x.checked_cast::<I8<8, 6>>().map(|y| y.raw()), Some(-96));
+    assert_eq!(x.checked_cast::<I8<8, 7>>(), None);
+    assert_eq!(x.saturating_cast::<I8<8, 7>>(), I8::MIN);
+    assert_eq!(x.wrapping_cast::<I8<8, 7>>().raw(), 64); // -192 mod 256
+    let y = 1000u16;
+    assert_eq!(y.checked_cast::<U8<8, 0>>(), None);
+    assert_eq!(y.saturating_cast::<U8<8, 0>>(), U8::MAX);
+    assert_eq!(y.wrapping_cast::<U8<8, 0>>().raw(), 232);
+    assert_eq!(y.checked_cast::<U16<12, -2>>().map(|z| z.raw()), Some(250));
+    assert_eq!(y.wrapping_cast::<U8<0, 0>>().raw(), 0);
+    let big = I64::<64, -60>::new(1).unwrap();
+    assert_eq!(big.checked_cast::<I64<64, 4>>(), None);
+    assert_eq!(big.saturating_cast::<I64<64, 4>>(), I64::MAX);
+    assert_eq!(big.wrapping_cast::<I64<64, 4>>().raw(), 0);
 }
@@ -0,0 +1,79 @@
+use fp::*;
+
+fn random<const R: usize, const C: usize>(rng: &mut Xorshift64) -> Matrix<i8, R, C> {
+    Matrix::new([[0; C]; R].map(|row| row.map(|_| rng.next_u64() as i8)))
+}
+
+#[test]
+fn requantize() {
+    let r = Requantize::from_scale(0.75).unwrap();
+    assert_eq!((r.multiplier().raw(), r.shift()), (3 << 29, 0));
+    let r = Requantize::from_scale(0.001).unwrap();
+    assert!((r.multiplier().into_f64() * 2f64.powi(r.shift()) - 0.001).abs() < 1e-12);
+    assert_eq!(Requantize::from_scale(3.).unwrap().shift(), 2);
+    assert!(Requantize::from_scale(0.).is_none());
+    assert!(Requantize::from_scale((1u64 << 31) as f64).is_none());
+    // rounds up to 2^31, which a multiplier cannot represent
+    assert!(Requantize::from_scale(2147483647.75).is_none());
+    let r = Requantize::from_scale(2147483647.).unwrap();
+    assert_eq!((r.multiplier().raw(), r.shift()), (i32::MAX, 31));
+    // ties round toward positive infinity
+    let half = Requantize::new(I32::from_f64(0.5).unwrap(), 0).unwrap();
+    assert_eq!([3, -3, 5, -5].map(|x| half.apply(x)), [2, -1, 3, -2]);
+    let quarter = I32::from_f64(0.25).unwrap();
+    assert!(matches!(
+        Requantize::new(quarter, 1),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(matches!(
+        Requantize::new(half.multiplier(), 32),
+        Err(RangeError::TooLarge)
+    ));
+}
+
+#[test]
+fn matches_reference() {
+    let mut rng = Xorshift64::new(7);
+    let a = random::<3, 40>(&mut rng);
+    let b = random::<40, 5>(&mut rng);
+    let bias = [1000, -2000, 0, 5, -7];
+    let gemm = QuantGemm::new(Requantize::from_scale(0.0007).unwrap(), -5, 3, 10);
+    assert!(gemm.with_output_range(11, 10).is_none());
+    let gemm = gemm.with_output_range(10, i8::MAX).unwrap();
+    assert_eq!(gemm.output_range(), (10, 127));
+    let acc = gemm.accumulate(&a, &b, &bias);
+    let out = gemm.run(&a, &b, &bias);
+    for i in 0..3 {
+        for (j, &bias) in bias.iter().enumerate() {
+            let expected: i64 = bias as i64
+                + (0..40)
+                    .map(|k| (a.rows()[i][k] as i64 + 5) * (b.rows()[k][j] as i64 - 3))
+                    .sum::<i64>();
+            assert_eq!(acc.rows()[i][j], expected);
+            let y = (expected as f64
+                * gemm.requantize.multiplier().into_f64()
+                * 2f64.powi(gemm.requantize.shift()))
+            .round()
+                + 10.;
+            assert_eq!(out.rows()[i][j] as f64, y.clamp(10., 127.));
+        }
+    }
+}
+
+#[test]
+fn extreme_values() {
+    // the largest accumulator magnitude allowed, with extreme zero points
+    let a = Matrix::new([[-128i8; 1 << 15]]);
+    let b = Matrix::new([[127i8]; 1 << 15]);
+    let gemm = QuantGemm::new(
+        Requantize::from_scale(1. / (1u64 << 32) as f64).unwrap(),
+        127,
+        -128,
+        0,
+    );
+    assert_eq!(
+        gemm.accumulate(&a, &b, &[0]).rows()[0][0],
+        -(255 * 255) << 15
+    );
+    assert_eq!(gemm.run(&a, &b, &[0]).rows()[0][0], 0);
+}
@@ -0,0 +1,73 @@
+use fp::*;
+
+fn point(lat: f64, lon: f64) -> GeoPoint {
+    GeoPoint {
+        lat: Latitude::from_degrees(lat).unwrap(),
+        lon: Longitude::from_degrees(lon).unwrap(),
+    }
+}
+
+fn haversine(a: GeoPoint, b: GeoPoint) -> f64 {
+    let (lat1, lat2) = (a.lat.degrees().to_radians(), b.lat.degrees().to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b.lon.degrees() - a.lon.degrees()).to_radians();
+    let h = (dlat / 2.).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.).sin().powi(2);
+    2. * 6371008.8 * h.sqrt().asin()
+}
+
+#[test]
+fn constructors() {
+    assert_eq!(Latitude::new(900_000_000).unwrap().degrees(), 90.);
+    assert!(matches!(
+        Latitude::new(900_000_001),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(matches!(
+        Longitude::new(-1_800_000_001),
+        Err(RangeError::TooSmall)
+    ));
+    assert_eq!(
+        Longitude::from_degrees(-122.4194155).unwrap().raw(),
+        -1_224_194_155
+    );
+    assert!(Latitude::from_degrees(f64::NAN).is_err());
+    assert!(matches!(
+        Latitude::from_degrees(-91.),
+        Err(RangeError::TooSmall)
+    ));
+}
+
+#[test]
+fn angles() {
+    let lat = Latitude::from_degrees(-45.).unwrap();
+    assert!((lat.to_radians().into_f64() + core::f64::consts::FRAC_PI_4).abs() < 1e-9);
+    assert_eq!(lat.to_turns().raw(), 0xe000_0000);
+    let lon = Longitude::from_degrees(180.).unwrap();
+    assert!((lon.to_radians().into_f64() - core::f64::consts::PI).abs() < 1e-9);
+    assert_eq!(lon.to_turns().raw(), 1 << 31);
+}
+
+#[test]
+fn distances() {
+    let a = point(47.3769, 8.5417);
+    for (lat, lon) in [
+        (47.3770, 8.5418),
+        (47.5, 8.7),
+        (46.9480, 7.4474),
+        (47.3769, 9.4),
+    ] {
+        let b = point(lat, lon);
+        let d = a.distance(b).into_f64();
+        let expected = haversine(a, b);
+        assert!(
+            (d - expected).abs() <= expected * 1e-3 + 0.05,
+            "{d} {expected}"
+        );
+        assert_eq!(b.distance(a), a.distance(b));
+    }
+    // across the antimeridian
+    let (west, east) = (point(-16.5, 179.99), point(-16.5, -179.99));
+    let expected = haversine(west, east);
+    assert!((west.distance(east).into_f64() - expected).abs() < 0.05);
+    assert_eq!(a.distance(a).raw(), 0);
+}
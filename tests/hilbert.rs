@@ -0,0 +1,43 @@
+use fp::*;
+
+#[test]
+fn taps() {
+    let taps = Hilbert::<I16<16, 15>, 7>::taps().map(|t| t.into_f64());
+    assert_eq!(taps[3], 0.);
+    assert_eq!(taps[1], 0.);
+    for (i, &t) in taps.iter().enumerate() {
+        assert!((t + taps[6 - i]).abs() < 1e-9, "antisymmetric");
+    }
+    // the center taps are barely attenuated by the window
+    let w = 0.42 - 0.5 * (std::f64::consts::TAU * 5. / 8.).cos()
+        + 0.08 * (std::f64::consts::TAU * 10. / 8.).cos();
+    assert!((taps[4] - 2. / std::f64::consts::PI * w).abs() < 1e-9);
+    assert_eq!(Hilbert::<I16<16, 15>, 7>::DELAY, 3);
+}
+
+#[test]
+fn envelope() {
+    let mut h = Hilbert::<I32<24, 23>, 63>::new();
+    let w = std::f64::consts::TAU * 0.1;
+    for n in 0..300 {
+        let x = 0.8 * (w * n as f64).cos();
+        let y = h.process(I32::from_f64(x).unwrap());
+        if n >= 62 {
+            let t = (n - Hilbert::<I32<24, 23>, 63>::DELAY) as f64;
+            assert!((y.re.into_f64() - 0.8 * (w * t).cos()).abs() < 1e-6);
+            assert!((y.im.into_f64() - 0.8 * (w * t).sin()).abs() < 0.008, "{n}");
+            let envelope = y.re.into_f64().hypot(y.im.into_f64());
+            assert!((envelope - 0.8).abs() < 0.008);
+        }
+    }
+}
+
+#[test]
+fn saturates() {
+    // the transform of a full-scale square wave has peaks beyond full scale
+    let mut h = Hilbert::<I8<8, 0>, 31>::default();
+    let ys: Vec<_> = (0..64)
+        .map(|n| h.process(if n % 16 < 8 { I8::MAX } else { I8::MIN }).im)
+        .collect();
+    assert!(ys.contains(&I8::MAX) && ys.contains(&I8::MIN));
+}
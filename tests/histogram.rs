@@ -0,0 +1,52 @@
+use fp::*;
+
+#[test]
+fn bins_cover_range() {
+    type H = Histogram<I16<8, 4>, 16>;
+    assert!(H::bin(I16::MIN) == 0);
+    assert!(H::bin(I16::MAX) == 15);
+    assert!(H::bin(I16::new(0).unwrap()) == 8);
+    let (lo, hi) = H::bin_range(8).unwrap();
+    assert!(lo.raw() == 0 && hi.raw() == 15);
+    let (lo, hi) = H::bin_range(0).unwrap();
+    assert!(lo == I16::MIN && hi.raw() == -113);
+    assert!(H::bin_range(16).is_none());
+}
+
+#[test]
+fn quantiles() {
+    let mut h = Histogram::<U32<12, 0>, 256>::new();
+    assert!(h.quantile(U32::new(1 << 15).unwrap()).is_none());
+    for i in 0..4096 {
+        h.add(U32::new(i).unwrap());
+    }
+    assert!(h.total() == 4096 && h.count(3) == Some(16));
+    assert!(h.count(256).is_none());
+    let median = h.quantile(U32::new(1 << 15).unwrap()).unwrap();
+    assert!((median.raw() as i32 - 2048).abs() <= 1);
+    let p90 = h.quantile(U32::new(58982).unwrap()).unwrap();
+    assert!((p90.raw() as i32 - 3686).abs() <= 16);
+    assert!(h.quantile(U32::new(0).unwrap()).unwrap().raw() == 0);
+    assert!(h.quantile(U32::new(1 << 16).unwrap()).unwrap().raw() == 4095);
+    h.clear();
+    assert!(h.total() == 0);
+}
+
+#[test]
+fn wide_bins() {
+    // 98 raw bits select a value within each bin
+    let mut h = Histogram::<I128<100, 0>, 4>::new();
+    for x in [-1 << 99, 0, 1 << 98] {
+        h.add(I128::new(x).unwrap());
+    }
+    let median = h.quantile(U32::new(1 << 15).unwrap()).unwrap();
+    assert!(median.raw() == 1 << 97);
+    assert!(h.quantile(U32::new(1 << 16).unwrap()).unwrap() == I128::MAX);
+    // a single bin spanning the whole range
+    let mut h = Histogram::<I128<127, 0>, 1>::new();
+    h.add(I128::new(0).unwrap());
+    assert!(Histogram::<I128<127, 0>, 1>::bin_range(0) == Some((I128::MIN, I128::MAX)));
+    assert!(h.quantile(U32::new(0).unwrap()).unwrap() == I128::MIN);
+    assert!(h.quantile(U32::new(1 << 15).unwrap()).unwrap().raw() == 0);
+    assert!(h.quantile(U32::new(1 << 16).unwrap()).unwrap() == I128::MAX);
+}
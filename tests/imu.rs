@@ -0,0 +1,55 @@
+use fp::*;
+
+fn v(x: f64, y: f64, z: f64) -> Vector3<I32<32, 16>> {
+    Vector3::new(
+        I32::from_f64(x).unwrap(),
+        I32::from_f64(y).unwrap(),
+        I32::from_f64(z).unwrap(),
+    )
+}
+
+fn filter() -> Mahony {
+    let kp = U32::from_f64(2.).unwrap();
+    let ki = U32::from_f64(1.).unwrap();
+    Mahony::new(kp, ki, U32::from_f64(0.01).unwrap())
+}
+
+#[test]
+fn integrates_yaw_rate() {
+    let mut m = filter();
+    let mut q = Quaternion::identity();
+    // 1 rad/s about z for 1 s, with gravity along z
+    for _ in 0..100 {
+        q = m.update(v(0., 0., 1.), v(0., 0., 9.81));
+    }
+    assert!((q.w.into_f64() - 0.5f64.cos()).abs() < 1e-4);
+    assert!((q.z.into_f64() - 0.5f64.sin()).abs() < 1e-4);
+    assert!(q.x.into_f64().abs() < 1e-6 && q.y.into_f64().abs() < 1e-6);
+}
+
+#[test]
+fn converges_to_level() {
+    let mut m = filter();
+    // start tilted by about 0.5 rad about x
+    let c = |x: f64| I32::from_f64(x).unwrap();
+    m.set_attitude(Quaternion {
+        w: c(0.97),
+        x: c(0.25),
+        y: c(0.),
+        z: c(0.),
+    });
+    let mut q = m.attitude();
+    assert!((q.w.into_f64().powi(2) + q.x.into_f64().powi(2) - 1.).abs() < 1e-8);
+    for _ in 0..1000 {
+        q = m.update(v(0., 0., 0.), v(0., 0., 1.));
+    }
+    assert!((q.w.into_f64() - 1.).abs() < 1e-6, "{q:?}");
+    assert!(q.x.into_f64().abs() < 1e-3);
+}
+
+#[test]
+fn zero_accel_is_ignored() {
+    let mut m = filter();
+    let q = m.update(v(0.5, 0., 0.), v(0., 0., 0.));
+    assert!(q.x.raw() > 0);
+}
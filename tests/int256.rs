@@ -0,0 +1,85 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+const WAD: u128 = 1_000_000_000_000_000_000;
+
+fn wad(raw: u128) -> U256<100, 0> {
+    U256::new(Uint256::from(raw)).unwrap()
+}
+
+#[test]
+fn wad_arithmetic() {
+    // 1.5 * 2.5 = 3.75, in 18-decimal fixed point
+    let (a, b) = (wad(3 * WAD / 2), wad(5 * WAD / 2));
+    let one = U256::<60, 0>::new(Uint256::from(WAD)).unwrap();
+    let product: U256<200, 0> = a * b;
+    let c: U256<200, 0> = product / one;
+    assert_eq!(u128::try_from(c.raw()).unwrap(), 15 * WAD / 4);
+    let sum: U256<101, 0> = a + b;
+    assert_eq!(u128::try_from(sum.raw()).unwrap(), 4 * WAD);
+    let difference: I256<101, 0> = a - b;
+    assert_eq!(i128::try_from(difference.raw()).unwrap(), -(WAD as i128));
+    assert!(u8::try_from(c.raw()).is_err());
+}
+
+#[test]
+fn limbs() {
+    // (2^127 - 1)^2 = 2^254 - 2^128 + 1
+    let x = U256::<127, 0>::new(Uint256::from(u128::MAX >> 1)).unwrap();
+    assert_eq!((x * x).raw().limbs(), ((1 << 126) - 1, 1));
+    let y = I256::<128, 0>::new(Int256::from(i128::MIN)).unwrap();
+    assert_eq!((y * y).raw().limbs(), (1 << 126, 0));
+    assert_eq!((-y).raw().limbs(), (0, 1 << 127));
+    let z = I256::<200, 0>::new(Int256::from_limbs(-5, 7)).unwrap();
+    let w = I256::<8, 0>::new(Int256::from(-2)).unwrap();
+    // (-5 * 2^128 + 7) / -2, rounded toward zero
+    assert_eq!((z / w).raw().limbs(), (2, (1 << 127) - 4));
+    let seven = I256::<8, 0>::new(Int256::from(-7)).unwrap();
+    assert_eq!((seven / w.raw_shr::<1>()).raw(), Int256::from(7));
+    assert_eq!((seven / -w).raw(), Int256::from(-3));
+    // divisors of 256 bits
+    let max = U256::<256, 0>::MAX;
+    let half = U256::<256, 0>::new(Uint256::from_limbs(1 << 127, 1)).unwrap();
+    assert_eq!((max / half).raw(), Uint256::from(1u8));
+    assert_eq!((half / max).raw(), Uint256::from(0u8));
+    assert_eq!((max / max).raw(), Uint256::from(1u8));
+}
+
+#[test]
+fn ranges_and_shifts() {
+    assert_eq!(U256::<256, 0>::MAX.raw().limbs(), (u128::MAX, u128::MAX));
+    assert_eq!(I256::<130, 0>::MIN.raw().limbs(), (-2, 0));
+    assert_eq!(I256::<130, 0>::MAX.raw().limbs(), (1, u128::MAX));
+    assert!(I256::<130, 0>::new(Int256::from_limbs(2, 0)).is_err());
+    let x = I256::<256, 0>::MIN;
+    assert!(x.is_negative());
+    assert_eq!(x.raw_shr::<200>().raw(), Int256::from(-1i128 << 55));
+    assert_eq!(x.raw_shl::<0>(), x);
+    let y = U256::<140, 0>::new(Uint256::from_limbs(1 << 11, 5)).unwrap();
+    assert_eq!(y.raw_shr::<130>().raw(), Uint256::from(1u16 << 9));
+    assert_eq!(y.raw_shl::<116>().raw().limbs(), (1 << 127, 5 << 116));
+}
+
+#[test]
+fn conversions() {
+    let x: I256<64, 18> = I64::<64, 18>::new(-5).unwrap().into_fp();
+    assert_eq!(x.raw(), Int256::from(-5));
+    assert_eq!(I64::<64, 18>::from_fp(x).raw(), -5);
+    let y = I256::<200, 100>::from_f64(-1.25).unwrap();
+    assert_eq!(y.into_f64(), -1.25);
+    assert_eq!(y.into_f32(), -1.25);
+    let big = U256::<256, 0>::from_f64(3. * 2f64.powi(200)).unwrap();
+    assert_eq!(big.raw().limbs(), (3 << 72, 0));
+    assert_eq!(big.into_f64(), 3. * 2f64.powi(200));
+    // 2^200 + 1 rounds to 2^200, and 2^200 + 2^147 + 1 rounds up, past the tie
+    let z = U256::<256, 0>::new(Uint256::from_limbs(1 << 72, 1)).unwrap();
+    assert_eq!(z.into_f64(), 2f64.powi(200));
+    let z = U256::<256, 0>::new(Uint256::from_limbs(1 << 72 | 1 << 19, 1)).unwrap();
+    assert_eq!(z.into_f64(), 2f64.powi(200) + 2f64.powi(148));
+    assert!(U256::<256, 0>::from_f64(-1.).is_err());
+    assert_eq!(I256::<256, 0>::MIN.into_f64(), -(2f64.powi(255)));
+    let m: I256<256, 0> = Int256::from(9).into();
+    assert_eq!(Int256::from(m), Int256::from(9u8));
+}
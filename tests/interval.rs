@@ -0,0 +1,58 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn iv<const B: u32, const S: i32>(lo: f64, hi: f64) -> Interval<I32<B, S>> {
+    Interval::new(I32::from_f64(lo).unwrap(), I32::from_f64(hi).unwrap())
+}
+
+#[test]
+fn bounds() {
+    let x = iv::<10, 4>(2., -1.5);
+    assert_eq!((x.lo().into_f64(), x.hi().into_f64()), (-1.5, 2.));
+    assert!(x.contains(I32::from_f64(0.25).unwrap()));
+    assert!(!x.contains(I32::from_f64(2.0625).unwrap()));
+    assert!(x.contains_zero());
+    assert!(!iv::<10, 4>(0.5, 1.).contains_zero());
+    assert_eq!(x.width_f64(), 3.5);
+    let mut seen = Interval::point(I32::<10, 4>::from_f64(0.5).unwrap());
+    for v in [0.25, 3., -0.75, 1.] {
+        seen.include(I32::from_f64(v).unwrap());
+    }
+    assert_eq!(seen, iv(-0.75, 3.));
+    assert_eq!(seen.hull(iv(5., 6.)), iv(-0.75, 6.));
+    assert_eq!(Interval::<U8<4, 0>>::full().hi().raw(), 15);
+}
+
+#[test]
+fn arithmetic() {
+    let a = iv::<10, 4>(-1.5, 2.);
+    let b = iv::<10, 4>(0.5, 3.);
+    assert_eq!(a + b, iv(-1., 5.));
+    assert_eq!(a - b, iv(-4.5, 1.5));
+    assert_eq!(a * b, iv(-4.5, 6.));
+    assert_eq!(-a, iv(-2., 1.5));
+    let q = a / b;
+    assert_eq!((q.lo().raw(), q.hi().raw()), (-3, 4));
+    // dividing by an interval containing zero is unbounded
+    assert_eq!(b / a, Interval::full());
+}
+
+#[test]
+fn bits_needed() {
+    // 16 bits are allowed, but the values only ever reach [-3, 2.5]
+    let x = iv::<16, 8>(-3., 2.5);
+    assert_eq!(x.bits_needed(), 11);
+    let y = x * x;
+    assert_eq!((y.lo().into_f64(), y.hi().into_f64()), (-7.5, 9.));
+    // 9 needs 4 integer bits, a sign bit and 16 fractional bits
+    assert_eq!(y.bits_needed(), 21);
+    let u = Interval::new(U16::<16, 0>::new(0).unwrap(), U16::new(255).unwrap());
+    assert_eq!(u.bits_needed(), 8);
+    assert_eq!(
+        Interval::point(U16::<16, 0>::new(0).unwrap()).bits_needed(),
+        0
+    );
+    assert_eq!(iv::<10, 4>(-1., 0.).bits_needed(), 5);
+}
@@ -0,0 +1,35 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn scale_slice_const() {
+    // gain of 3/4 in Q15 samples
+    let mut xs = [
+        I16::<16, 15>::MIN,
+        I16::MAX,
+        I16::new(1000).unwrap(),
+        I16::new(-3).unwrap(),
+    ];
+    I16::scale_slice_const::<3, 2>(&mut xs);
+    assert!(xs.map(|x| x.raw()) == [-24576, 24575, 750, -3]);
+    // gain of 5/8 needs 3 bits of shift to stay within BITS
+    let mut xs = [U32::<11, 0>::MAX; 4];
+    U32::scale_slice_const::<5, 3>(&mut xs);
+    assert!(xs.iter().all(|x| x.raw() == 2047 * 5 / 8));
+}
+
+#[test]
+fn axpy_saturates() {
+    let mut y = [I32::<16, 8>::new(100).unwrap(), I32::MAX, I32::MIN];
+    let a = I32::<8, 4>::new(-24).unwrap(); // -1.5
+    let x = [I32::<12, 4>::new(32).unwrap(), I32::MIN, I32::MIN];
+    I32::axpy(&mut y, a, &x).unwrap();
+    assert!(y[0].raw() == 100 - 24 * 32);
+    assert!(y[1] == I32::MAX);
+    assert!(y[2].raw() == I32::<16, 8>::MIN.raw() + 24 * 2048);
+    let before = y;
+    assert!(I32::axpy(&mut y, a, &x[..2]) == Err(LengthMismatch));
+    assert!(y == before);
+}
@@ -0,0 +1,27 @@
+use fp::*;
+
+#[test]
+fn rejects_spikes() {
+    let mut m = MedianFilter::<I16<12, 4>, 5>::new(I16::new(0).unwrap());
+    let xs = [10, 11, 2000, 12, 13, -2000, -2000, 14, 15, 16];
+    let ys: Vec<i16> = xs
+        .iter()
+        .map(|&x| m.process(I16::new(x).unwrap()).raw())
+        .collect();
+    assert!(ys == [0, 0, 10, 11, 12, 12, 12, 12, 13, 14]);
+}
+
+#[test]
+fn matches_sorting() {
+    let mut rng = Xorshift64::new(9);
+    let mut m = MedianFilter::<U32<6, 0>, 7>::new(U32::new(0).unwrap());
+    let mut window = vec![0; 7];
+    for _ in 0..1000 {
+        let x = U32::<6, 0>::random(&mut rng);
+        window.remove(0);
+        window.push(x.raw());
+        let mut sorted = window.clone();
+        sorted.sort();
+        assert!(m.process(x).raw() == sorted[3]);
+    }
+}
@@ -0,0 +1,47 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn sine_levels() {
+    let mut meter = LevelMeter::<I32<16, 0>, 480>::new();
+    let sine = Waveform::<I32<16, 0>, 48000, 1000>::sine();
+    let completed = sine.take(960).filter(|&x| meter.process(x)).count();
+    assert_eq!(completed, 2);
+    assert_eq!(meter.peak().raw(), 32767);
+    assert!((meter.rms().raw() as f64 - 32767. / 2f64.sqrt()).abs() < 2.);
+    let db = meter.rms_dbfs().unwrap().into_f64();
+    assert!((db + 3.0103).abs() < 1e-3, "{db}");
+    assert!(meter.peak_dbfs().unwrap().into_f64().abs() < 1e-3);
+}
+
+#[test]
+fn peak_hold() {
+    let mut meter = LevelMeter::<I32<20, 8>, 4>::default();
+    for x in [0.5, -3., 1., 0.] {
+        meter.process(I32::from_f64(x).unwrap());
+    }
+    assert_eq!(meter.peak_hold(), I32::<21, 8>::from_f64(3.).unwrap());
+    for _ in 0..4 {
+        meter.process(I32::from_f64(-0.25).unwrap());
+    }
+    assert_eq!(meter.peak(), I32::<21, 8>::from_f64(0.25).unwrap());
+    assert_eq!(meter.rms(), I32::<21, 8>::from_f64(0.25).unwrap());
+    assert_eq!(meter.peak_hold(), I32::<21, 8>::from_f64(3.).unwrap());
+    meter.reset_hold();
+    assert_eq!(meter.peak_hold_dbfs(), None);
+}
+
+#[test]
+fn full_scale() {
+    let mut meter = LevelMeter::<I16<8, 0>, 2>::new();
+    meter.process(I16::MIN);
+    meter.process(I16::MIN);
+    assert_eq!(meter.peak().raw(), 128);
+    assert_eq!(meter.rms_dbfs().unwrap().raw(), 0);
+    let mut meter = LevelMeter::<U16<8, 0>, 1>::new();
+    meter.process(U16::new(16).unwrap());
+    // a factor of 16 below full scale
+    assert!((meter.peak_dbfs().unwrap().into_f64() + 24.0824).abs() < 1e-3);
+}
@@ -4,11 +4,11 @@ use fp::*;
 fn min_max_correct() {
     assert!(I64::<0, 10>::MIN.raw() == 0);
     assert!(I64::<0, 10>::MAX.raw() == 0);
-    assert!(I64::<0, 10>::SIGNED);
+    const { assert!(I64::<0, 10>::SIGNED) };
 
     assert!(U64::<0, 0>::MIN.raw() == 0);
     assert!(U64::<0, 0>::MAX.raw() == 0);
-    assert!(!U64::<0, 10>::SIGNED);
+    const { assert!(!U64::<0, 10>::SIGNED) };
 
     assert!(I32::<8, -2>::MIN.raw() == -128);
     assert!(I32::<8, -2>::MAX.raw() == 127);
@@ -3,9 +3,12 @@
 
 use fp::*;
 
-use core::ops::{Mul, Div};
+use core::ops::Mul;
 
-fn validate_mul<A: Num, B: Num, C: Num>() where A: Mul<B, Output=C> {
+fn validate_mul<A, B: Num, C: Num>()
+where
+    A: Num + Mul<B, Output = C>,
+{
     for a in [A::MIN, A::MAX] {
         for b in [B::MIN, B::MAX] {
             assert!(a * b >= C::MIN);
@@ -30,3 +33,297 @@ fn mul_const() {
     let c: I32<7, 0> = a.mul_const::<5>();
     assert!(c.raw() == 4 * 5);
 }
+
+#[test]
+fn rem() {
+    let a = I32::<10, 4>::from_f64(-7.5).unwrap();
+    let b = I32::<6, 2>::from_f64(2.25).unwrap();
+    // -7.5 = -3.25 * 2.25 - 0.1875, where -3.25 = a / b (truncated at shift 2)
+    let q: I32<11, 2> = a / b;
+    let r: I32<6, 4> = a % b;
+    assert_eq!((q.into_f64(), r.into_f64()), (-3.25, -0.1875));
+    assert_eq!(q.raw() * b.raw() + r.raw(), a.raw());
+    let c = U32::<3, 0>::new(7).unwrap();
+    let r: I32<4, 4> = a % c;
+    assert_eq!(r.raw(), -1);
+    let r: U32<3, 0> = c % I32::<8, 0>::new(-3).unwrap();
+    assert_eq!(r.raw(), 1);
+    let r: U32<3, 0> = c % U32::<8, 0>::new(200).unwrap();
+    assert_eq!(r.raw(), 7);
+    // MIN % -1 does not overflow
+    let r: I8<8, 0> = I8::<8, 0>::MIN % I8::<8, 0>::new(-1).unwrap();
+    assert_eq!(r.raw(), 0);
+    assert_eq!((I8::<8, 0>::MIN % U8::<8, 0>::MAX).raw(), -128);
+}
+
+#[test]
+fn euclid() {
+    let a = I32::<10, 4>::from_f64(-7.5).unwrap();
+    let b = I32::<6, 2>::from_f64(2.25).unwrap();
+    // -7.5 = -3.5 * 2.25 + 0.375
+    let q: I32<11, 2> = a.div_euclid(b);
+    let r: I32<6, 4> = a.rem_euclid(b);
+    assert_eq!((q.into_f64(), r.into_f64()), (-3.5, 0.375));
+    assert_eq!(q.raw() * b.raw() + r.raw(), a.raw());
+    // a negative divisor rounds the quotient up
+    let c = I32::<6, 2>::from_f64(-2.25).unwrap();
+    assert_eq!(a.div_euclid(c).into_f64(), 3.5);
+    assert_eq!(a.rem_euclid(c).into_f64(), 0.375);
+    // agrees with Div and Rem when they give a nonnegative remainder
+    let d = -a;
+    assert_eq!(d.div_euclid(b).raw(), (d / b).raw());
+    assert_eq!(d.rem_euclid(b).raw(), (d % b).raw());
+    // wrap a phase into [0, 1)
+    let phase = I32::<16, 8>::from_f64(-0.25).unwrap();
+    let turn = I32::<10, 8>::from_f64(1.).unwrap();
+    let wrapped: I32<10, 8> = phase.rem_euclid(turn);
+    assert_eq!(wrapped.into_f64(), 0.75);
+    let x = U8::<8, 0>::new(200).unwrap();
+    let r: U8<4, 0> = x.rem_euclid(U8::<4, 0>::new(7).unwrap());
+    assert_eq!(r.raw(), 4);
+    let r: U8<3, 0> = U8::<3, 0>::MAX.rem_euclid(x);
+    assert_eq!(r.raw(), 7);
+    assert_eq!(
+        I8::<8, 0>::MIN
+            .rem_euclid(I8::<8, 0>::new(-1).unwrap())
+            .raw(),
+        0
+    );
+}
+
+#[test]
+fn widening_mul() {
+    let a = I32::<20, 8>::MIN;
+    let b = I32::<20, 8>::MAX;
+    let p: I64<40, 16> = a.widening_mul(b);
+    assert_eq!(p.raw(), a.raw() as i64 * b.raw() as i64);
+    let p: I64<40, 16> = a.widening_mul(a);
+    assert_eq!(p.raw(), 1 << 38);
+    let p: U128<128, 0> = U64::<64, 0>::MAX.widening_mul(U64::<64, 0>::MAX);
+    assert_eq!(p.raw(), (u64::MAX as u128) * (u64::MAX as u128));
+    let p: I16<12, 3> = I8::<8, 2>::new(-100)
+        .unwrap()
+        .widening_mul(I8::<4, 1>::new(7).unwrap());
+    assert_eq!(p.raw(), -700);
+}
+
+#[test]
+fn mul_ratio() {
+    // 12-bit ADC counts, with a 3300 mV reference
+    let counts = U16::<12, 0>::new(2048).unwrap();
+    let mv: U16<12, 0> = counts.mul_ratio::<3300, 4096>();
+    assert_eq!(mv.raw(), 1650);
+    let mv: U16<12, 0> = U16::<12, 0>::MAX.mul_ratio::<3300, 4096>();
+    assert_eq!(mv.raw(), 3299);
+    // the product is formed in the wider type
+    let x = I32::<32, 8>::MIN;
+    let y: I32<32, 8> = x.mul_ratio::<999_999, 1_000_000>();
+    assert_eq!(y.raw(), -2147481500);
+    // truncation is toward zero, and a negative ratio flips the extremes
+    let z = I8::<8, 0>::new(-7).unwrap();
+    let w: I8<8, 0> = z.mul_ratio::<-1, 2>();
+    assert_eq!(w.raw(), 3);
+    assert_eq!(I8::<8, 0>::MIN.mul_ratio::<-1, 2>().raw(), 64);
+    let v: U64<63, 0> = U64::<64, 0>::MAX.mul_ratio::<1, 2>();
+    assert_eq!(v, U64::MAX);
+}
+
+#[test]
+fn pow2_consts() {
+    // every 7-bit value, against integer division and multiplication
+    for raw in -64..64 {
+        let x = I8::<7, 0>::new(raw).unwrap();
+        assert_eq!(x.div_const::<1>().raw(), raw);
+        assert_eq!(x.div_const::<4>().raw(), raw / 4);
+        assert_eq!(x.div_const::<64>().raw(), raw / 64);
+        assert_eq!(x.div_const::<-8>().raw(), raw / -8);
+        assert_eq!(x.div_const::<-1>().raw(), -raw);
+        assert_eq!(x.div_const::<-128>().raw(), 0);
+        assert_eq!(x.div_const::<3>().raw(), raw / 3);
+        assert_eq!(x.div_const::<-3>().raw(), raw / -3);
+        assert_eq!(
+            I16::<7, 0>::new(raw as i16)
+                .unwrap()
+                .mul_const::<-256>()
+                .raw(),
+            raw as i16 * -256
+        );
+        assert_eq!(
+            I16::<7, 0>::new(raw as i16)
+                .unwrap()
+                .mul_const::<16>()
+                .raw(),
+            raw as i16 * 16
+        );
+    }
+    assert_eq!(I8::<8, 0>::MIN.div_const::<-128>().raw(), 1);
+    assert_eq!(I8::<8, 0>::MIN.div_const::<2>().raw(), -64);
+    for raw in 0..=255 {
+        let x = U8::<8, 0>::new(raw).unwrap();
+        assert_eq!(x.div_const::<128>().raw(), raw / 128);
+        assert_eq!(x.div_const::<10>().raw(), raw / 10);
+    }
+    assert_eq!(
+        U32::<24, 0>::MAX.mul_const::<256>(),
+        U32::<32, 0>::new(0xffff_ff00).unwrap()
+    );
+}
+
+#[test]
+fn pow_const() {
+    let x = I32::<6, 4>::from_f64(-1.25).unwrap();
+    let y: I32<18, 12> = x.pow_const::<3>();
+    assert_eq!(y.into_f64(), -1.953125);
+    assert_eq!(x.pow_const::<1>(), x);
+    let z: I64<60, 40> = x.into_fp::<_, I64<6, 4>>().pow_const::<10>();
+    assert_eq!(z.into_f64(), 1.25f64.powi(10));
+    // the extremes fit
+    assert_eq!(I8::<4, 0>::MIN.pow_const::<2>().raw(), 64);
+    assert_eq!(I16::<4, 0>::MIN.pow_const::<3>().raw(), -512);
+    assert_eq!(U64::<16, 0>::MAX.pow_const::<4>().raw(), 0xffffu64.pow(4));
+}
+
+#[test]
+fn square_cube() {
+    let x = I16::<8, 4>::from_f64(-1.5).unwrap();
+    let sq: U16<15, 8> = x.square();
+    assert_eq!(sq.into_f64(), 2.25);
+    let cu: I32<22, 12> = x.into_fp::<_, I32<8, 4>>().cube();
+    assert_eq!(cu.into_f64(), -3.375);
+    // the extremes fit, one bit (or two) narrower than Mul would give
+    assert_eq!(
+        I8::<8, 0>::MIN.into_fp::<_, I16<8, 0>>().square().raw(),
+        1 << 14
+    );
+    assert_eq!(I16::<8, 0>::MAX.square().raw(), 127 * 127);
+    assert_eq!(I32::<8, 0>::MIN.cube().raw(), -(1 << 21));
+    assert_eq!(I32::<8, 0>::MAX.cube().raw(), 127 * 127 * 127);
+    let u: U16<16, 8> = U16::<8, 4>::MAX.square();
+    assert_eq!(u.raw(), 255 * 255);
+    let v: U32<24, -3> = U32::<8, -1>::MAX.cube();
+    assert_eq!(v.raw(), 255 * 255 * 255);
+    let m = I8::<4, 0>::MIN;
+    assert_eq!(m.square().into_f64(), (m * m).into_f64());
+}
+
+#[test]
+fn div_precise() {
+    let on = U32::<12, 0>::new(1000).unwrap();
+    let period = U32::<12, 0>::new(3000).unwrap();
+    assert_eq!((on / period).raw(), 0);
+    let duty: U64<28, 16> = on.div_precise::<16, _, _>(period);
+    assert_eq!(duty.raw(), (1000 << 16) / 3000);
+    let error = I16::<10, 4>::from_f64(-3.5).unwrap();
+    let scale = I16::<8, 2>::from_f64(1.5).unwrap();
+    let normalized: I32<19, 10> = error.div_precise::<8, _, _>(scale);
+    assert_eq!(normalized.raw(), (-56 << 8) / 6);
+    // rounds toward zero, and MIN / -1 fits
+    let x: I16<16, 7> = I8::<8, 0>::MIN.div_precise::<7, _, _>(I8::<8, 0>::new(-1).unwrap());
+    assert_eq!(x.raw(), 128 << 7);
+    let y = I64::<64, 0>::MIN.div_precise::<63, _, _>(I64::<64, 0>::MAX);
+    assert_eq!(y.raw(), i64::MIN as i128 * (1 << 63) / i64::MAX as i128);
+}
+
+#[test]
+fn mul_add() {
+    let a = I32::<12, 4>::from_f64(-2.5).unwrap();
+    let b = I32::<10, 6>::from_f64(3.25).unwrap();
+    let c = I32::<18, 10>::from_f64(100.).unwrap();
+    let y: I32<23, 10> = a.mul_add(b, c);
+    assert_eq!(y, a * b + c);
+    assert_eq!(y.into_f64(), 91.875);
+    // the extremes fit
+    let m = I32::<8, 0>::MIN;
+    assert_eq!(
+        m.mul_add(m, I32::<15, 0>::MAX).raw(),
+        (1 << 14) + (1 << 14) - 1
+    );
+    let u = U64::<31, 0>::MAX;
+    let v: U64<63, 0> = u.mul_add(u, U64::<62, 0>::MAX);
+    assert_eq!(v.raw(), (u.raw() * u.raw()) + (1 << 62) - 1);
+    let w = U16::<4, 0>::MAX;
+    assert_eq!(w.mul_add(w, U16::<7, 0>::MAX).raw(), 225 + 127);
+}
+
+#[test]
+fn mul_div() {
+    // 12-bit counts, times a gain of 4000/4096, at shift 0
+    let counts = U16::<12, 0>::new(4095).unwrap();
+    let gain = U16::<12, 0>::new(4000).unwrap();
+    let scale = U16::<13, 0>::new(4096).unwrap();
+    let scaled: U32<24, 0> = counts.mul_div(gain, scale);
+    assert_eq!(scaled.raw(), 4095 * 4000 / 4096);
+    let narrow: U16<12, 0> = scaled.checked_cast().unwrap();
+    assert_eq!(narrow.raw(), 3999);
+    // rounds toward zero, with shifts as for Mul and then Div
+    let a = I32::<16, 8>::from_f64(-1.5).unwrap();
+    let b = I32::<16, 4>::from_f64(2.25).unwrap();
+    let c = I32::<8, 2>::from_f64(-1.25).unwrap();
+    let y: I64<32, 10> = a.mul_div(b, c);
+    assert_eq!(y.raw(), (-384 * 36) / -5);
+    // the extremes fit
+    let m = I64::<64, 0>::MIN;
+    assert_eq!(
+        m.mul_div(m, I64::<64, 0>::new(-1).unwrap()).raw(),
+        -(1 << 126)
+    );
+    assert_eq!(m.mul_div(I64::<64, 0>::MAX, m).raw(), i64::MAX as i128);
+}
+
+#[test]
+fn div_rem() {
+    let n = I32::<16, 8>::from_f64(-7.25).unwrap();
+    let d = I32::<8, 4>::from_f64(1.5).unwrap();
+    let (q, r): (I32<17, 4>, I32<8, 8>) = n.div_rem(d);
+    assert_eq!((q, r), (n / d, n % d));
+    assert_eq!((q * d + r).raw(), n.raw());
+    for (n, d) in [(-128, 7), (127, -3), (-128, -128), (5, 127), (0, -1)] {
+        let (n, d) = (I32::<8, 0>::new(n).unwrap(), I32::<8, 0>::new(d).unwrap());
+        let (q, r) = n.div_rem(d);
+        assert_eq!((q * d + r).raw(), n.raw());
+    }
+    let (q, r) = U8::<8, 0>::MAX.div_rem(U8::<4, 0>::new(10).unwrap());
+    assert_eq!((q.raw(), r.raw()), (25, 5));
+}
+
+#[test]
+fn div_round_ceil() {
+    for n in i8::MIN..=i8::MAX {
+        for d in i8::MIN..=i8::MAX {
+            if d == 0 {
+                continue;
+            }
+            let (x, y) = (
+                I16::<8, 0>::new(n as i16).unwrap(),
+                I16::<8, 0>::new(d as i16).unwrap(),
+            );
+            let exact = n as f64 / d as f64;
+            let nearest = exact.abs().round().copysign(exact);
+            assert_eq!(x.div_round(y).raw() as f64, nearest, "{n} / {d}");
+            assert_eq!(x.div_ceil(y).raw() as f64, exact.ceil(), "{n} / {d}");
+        }
+    }
+    for n in 0..=u8::MAX {
+        for d in 1..=u8::MAX {
+            let (x, y) = (U8::<8, 0>::new(n).unwrap(), U8::<8, 0>::new(d).unwrap());
+            assert_eq!(
+                x.div_round(y).raw() as u32,
+                (2 * n as u32 + d as u32) / (2 * d as u32)
+            );
+            assert_eq!(x.div_ceil(y).raw(), n.div_ceil(d));
+        }
+    }
+    // timer ticks for a period, with shifts as for Div
+    let period = U32::<24, 4>::new(1000 << 4).unwrap();
+    let tick = U32::<8, 4>::new(3 << 4).unwrap();
+    let ticks: U32<24, 0> = period.div_round(tick);
+    assert_eq!(ticks.raw(), 333);
+    assert_eq!(period.div_ceil(tick).raw(), 334);
+    assert_eq!(
+        U128::<128, 0>::MAX
+            .div_round(U128::<128, 0>::new(2).unwrap())
+            .raw(),
+        1 << 127
+    );
+    assert_eq!(I64::<63, 0>::MIN.div_round(I64::<64, 0>::MIN).raw(), 1);
+}
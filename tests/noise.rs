@@ -0,0 +1,85 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn deterministic() {
+    let mut a = Xorshift64::new(12345);
+    let mut b = Xorshift64::new(12345);
+    for _ in 0..100 {
+        assert!(I32::<20, 4>::random(&mut a) == I32::<20, 4>::random(&mut b));
+    }
+    assert!(Xorshift64::new(0).next_u64() != 0);
+}
+
+#[test]
+fn random_in_range() {
+    let mut rng = Xorshift64::new(1);
+    let (mut lo, mut hi) = (0, 0);
+    for _ in 0..10000 {
+        let x = I16::<5, 0>::random(&mut rng);
+        lo = lo.min(x.raw());
+        hi = hi.max(x.raw());
+        let y = U128::<100, 0>::random(&mut rng);
+        assert!(y <= U128::MAX);
+        assert!(U8::<0, 0>::random(&mut rng).raw() == 0);
+    }
+    assert!(lo == -16 && hi == 15);
+}
+
+#[test]
+fn tpdf_is_centered() {
+    let mut rng = Xorshift64::new(7);
+    let mut sum = 0i64;
+    for _ in 0..10000 {
+        let x: I32<9, 0> = I32::<8, 0>::random_tpdf(&mut rng);
+        sum += x.raw() as i64;
+    }
+    // the mean of the sum of two draws from [-128, 127] is -1
+    assert!((sum / 10000 + 1).abs() <= 2);
+}
+
+#[test]
+fn stochastic_rounding() {
+    let mut rng = Xorshift64::new(3);
+    // exact results are unchanged
+    let x = I32::<12, 4>::new(-8).unwrap();
+    for _ in 0..100 {
+        assert_eq!(x.raw_shr_stochastic::<2>(&mut rng).raw(), -2);
+    }
+    // 1.25 and -1.25 LSB round up a quarter and three quarters of the time
+    let (mut up, mut down) = (0, 0);
+    for _ in 0..10000 {
+        let y: I32<10, 2> = I32::<12, 4>::new(5)
+            .unwrap()
+            .raw_shr_stochastic::<2>(&mut rng);
+        assert!(y.raw() == 1 || y.raw() == 2);
+        up += (y.raw() == 2) as i32;
+        let z = I32::<12, 4>::new(-5)
+            .unwrap()
+            .raw_shr_stochastic::<2>(&mut rng);
+        assert!(z.raw() == -2 || z.raw() == -1);
+        down += (z.raw() == -2) as i32;
+    }
+    assert!((up - 2500).abs() < 200, "{up}");
+    assert!((down - 2500).abs() < 200, "{down}");
+    // the same seed gives the same roundings
+    let (mut a, mut b) = (Xorshift64::new(9), Xorshift64::new(9));
+    let w = U64::<64, 0>::new(0x1234_5678_9abc_def0).unwrap();
+    for _ in 0..100 {
+        assert_eq!(
+            w.raw_shr_stochastic::<40>(&mut a),
+            w.raw_shr_stochastic::<40>(&mut b)
+        );
+    }
+    // results saturate at the MAX of the output
+    for _ in 0..100 {
+        assert_eq!(
+            U8::<8, 0>::MAX.raw_shr_stochastic::<1>(&mut rng),
+            U8::<7, -1>::MAX
+        );
+        let v = U128::<128, 0>::MAX.raw_shr_stochastic::<100>(&mut rng);
+        assert_eq!(v, U128::<28, -100>::MAX);
+    }
+}
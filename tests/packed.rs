@@ -0,0 +1,59 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn twelve_bit_samples() {
+    let samples: Vec<I16<12, 11>> = (-2048..2048)
+        .step_by(7)
+        .map(|r| I16::new(r).unwrap())
+        .collect();
+    let packed: PackedVec<I16<12, 11>> = samples.iter().copied().collect();
+    assert_eq!(packed.len(), samples.len());
+    assert_eq!(packed.as_bytes().len(), (samples.len() * 3).div_ceil(2));
+    assert!(packed.iter().eq(samples.iter().copied()));
+    assert_eq!(packed.get(samples.len()), None);
+}
+
+#[test]
+fn layout() {
+    let mut v = PackedVec::<U16<12, 0>>::new();
+    v.push(U16::new(0xabc).unwrap());
+    v.push(U16::new(0x123).unwrap());
+    assert_eq!(v.as_bytes(), [0xbc, 0x3a, 0x12]);
+    assert_eq!(
+        v.set(0, U16::new(0xfff).unwrap()),
+        Some(U16::new(0xabc).unwrap())
+    );
+    assert_eq!(v.as_bytes(), [0xff, 0x3f, 0x12]);
+    assert_eq!(v.set(2, U16::new(0).unwrap()), None);
+    assert_eq!(v.pop(), Some(U16::new(0x123).unwrap()));
+    assert_eq!(v.as_bytes(), [0xff, 0x0f]);
+    assert_eq!(v.pop().map(|x| x.raw()), Some(0xfff));
+    assert!(v.is_empty() && v.pop().is_none());
+}
+
+#[test]
+fn odd_widths() {
+    let mut v = PackedVec::<I64<61, 3>>::with_capacity(10);
+    v.extend([
+        I64::MIN,
+        I64::MAX,
+        I64::new(-1).unwrap(),
+        I64::new(0).unwrap(),
+    ]);
+    assert_eq!(v.as_bytes().len(), 31);
+    assert_eq!(v.get(0), Some(I64::MIN));
+    assert_eq!(v.get(1), Some(I64::MAX));
+    assert_eq!(v.get(2).unwrap().raw(), -1);
+    let mut a = PackedArray::<I8<3, 0>, 5>::new(I8::new(-3).unwrap());
+    assert_eq!(a.as_bytes().len(), 2);
+    a.set(4, I8::new(3).unwrap());
+    assert_eq!(
+        a.iter().map(|x| x.raw()).collect::<Vec<_>>(),
+        [-3, -3, -3, -3, 3]
+    );
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.get(5), None);
+}
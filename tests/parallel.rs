@@ -0,0 +1,94 @@
+#![cfg(feature = "parallel")]
+
+use fp::*;
+
+#[test]
+fn par_tree_sum_matches_serial() {
+    let xs: Vec<_> = (0..100_000)
+        .map(|i| I64::<20, 3>::new(i % 1000 - 500).unwrap())
+        .collect();
+    let serial: I64<37, 3> = tree_sum_slice(&xs).unwrap();
+    let parallel: I64<37, 3> = par_tree_sum_slice(&xs).unwrap();
+    assert!(serial == parallel);
+    assert!(par_tree_sum_slice::<_, I64<36, 3>>(&xs).is_err());
+    let empty: [I64<20, 3>; 0] = [];
+    assert!(par_tree_sum_slice::<_, I64<20, 3>>(&empty).unwrap().raw() == 0);
+}
+
+#[test]
+fn par_conversions_match_serial() {
+    let xs: Vec<_> = (0..20_000)
+        .map(|i| I32::<16, 8>::new(i % 30_000 - 15_000).unwrap())
+        .collect();
+    let floats = par_into_f64_slice(&xs);
+    assert!(floats.iter().zip(&xs).all(|(&f, x)| f == x.into_f64()));
+    let back: Vec<I32<16, 8>> = par_from_f64_slice(&floats).unwrap();
+    assert!(back == xs);
+    let mut floats = floats;
+    floats[15_000] = 1000.;
+    floats[17_000] = -1000.;
+    assert!(matches!(
+        par_from_f64_slice::<I32<16, 8>>(&floats),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(par_from_f64_slice::<I32<16, 8>>(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn par_dot_matches_serial() {
+    let a: Vec<_> = (0..10_000)
+        .map(|i| I16::<12, 4>::new(((i * 37) % 4000 - 2000) as i16).unwrap())
+        .collect();
+    let b: Vec<_> = (0..10_000)
+        .map(|i| I16::<10, 2>::new(((i * 11) % 1000 - 500) as i16).unwrap())
+        .collect();
+    let expected: i128 = a
+        .iter()
+        .zip(&b)
+        .map(|(x, y)| x.raw() as i128 * y.raw() as i128)
+        .sum();
+    let y: I64<36, 6> = par_dot(&a, &b).unwrap();
+    assert!(y.raw() as i128 == expected);
+    assert!(matches!(
+        par_dot::<_, _, I64<35, 6>>(&a, &b),
+        Err(SliceError::Range(RangeError::TooLarge))
+    ));
+    assert!(matches!(
+        par_dot::<_, _, I64<36, 6>>(&a, &b[1..]),
+        Err(SliceError::LengthMismatch)
+    ));
+    let empty: [I16<12, 4>; 0] = [];
+    assert!(par_dot::<_, _, I64<24, 8>>(&empty, &empty).unwrap().raw() == 0);
+}
+
+#[test]
+fn par_convolve_matches_serial() {
+    let a: Vec<_> = (0..9000)
+        .map(|i| I16::<8, 0>::new((i % 200 - 100) as i16).unwrap())
+        .collect();
+    let b: Vec<_> = (0..31).map(|i| I16::<6, 0>::new(i - 15).unwrap()).collect();
+    for mode in [ConvolveMode::Full, ConvolveMode::Same, ConvolveMode::Valid] {
+        let serial: Vec<I32<19, 0>> = convolve(&a, &b, mode).unwrap();
+        assert!(par_convolve::<_, _, I32<19, 0>>(&a, &b, mode).unwrap() == serial);
+        let serial: Vec<I32<19, 0>> = correlate(&b, &a, mode).unwrap();
+        assert!(par_correlate::<_, _, I32<19, 0>>(&b, &a, mode).unwrap() == serial);
+    }
+    assert!(par_convolve::<_, _, I32<18, 0>>(&a, &b, ConvolveMode::Full).is_err());
+}
+
+#[test]
+fn par_welford_matches_serial() {
+    let mut rng = Xorshift64::new(3);
+    let xs: Vec<_> = (0..50_000)
+        .map(|_| I32::<24, 8>::random(&mut rng))
+        .collect();
+    let mut serial = Welford::new();
+    xs.iter().for_each(|&x| serial.push(x));
+    let parallel = par_welford(&xs);
+    assert!(parallel.count() == serial.count());
+    assert!(parallel.mean() == serial.mean());
+    assert!(parallel.m2_raw() == serial.m2_raw());
+    assert!(parallel.variance::<U64<64, 16>>() == serial.variance());
+    assert!(parallel.std_dev() == serial.std_dev());
+    assert!(par_welford::<I32<24, 8>>(&[]).count() == 0);
+}
@@ -0,0 +1,33 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn matches_stages() {
+    let mut dc = DcBlocker::<I16<12, 8>, 6>::new();
+    let mut median = MedianFilter::<I16<12, 8>, 3>::new(I16::new(0).unwrap());
+    let mut min_max = SlidingMinMax::<I16<12, 8>, 4>::new();
+    let mut pipeline = DcBlocker::<I16<12, 8>, 6>::new()
+        .then(FnBlock(|x: I16<13, 8>| x.saturate::<12>()))
+        .then(MedianFilter::<I16<12, 8>, 3>::new(I16::new(0).unwrap()))
+        .then(SlidingMinMax::<I16<12, 8>, 4>::new());
+    let mut rng = Xorshift64::new(3);
+    for _ in 0..1000 {
+        let x = I16::<12, 8>::random(&mut rng);
+        let y = median.process(dc.process(x).saturate::<12>());
+        assert!(pipeline.process(x) == min_max.process(y));
+    }
+}
+
+#[test]
+fn generic_stage() {
+    fn run<B: Block<I16<12, 8>, Output = I16<12, 8>>>(b: &mut B, xs: &[i16]) -> Vec<i16> {
+        xs.iter()
+            .map(|&x| b.process(I16::new(x).unwrap()).raw())
+            .collect()
+    }
+    let mut stage =
+        FnBlock(|x: I16<12, 8>| x).then(MedianFilter::<_, 3>::new(I16::new(0).unwrap()));
+    assert!(run(&mut stage, &[5, 6, 100, 7]) == [0, 5, 6, 7]);
+}
@@ -0,0 +1,95 @@
+use fp::*;
+
+type Wide = I128<128, 64>;
+
+#[test]
+fn known_values() {
+    let p16 = |x: f64| P16::from_fp(I32::<32, 16>::from_f64(x).unwrap()).to_bits();
+    assert_eq!(
+        [1., 0.5, -1., 1.5, 2., 0.].map(p16),
+        [0x4000, 0x3000, 0xc000, 0x4800, 0x5000, 0]
+    );
+    assert_eq!(
+        P32::from_fp(I32::<32, 16>::from_f64(1.).unwrap()).to_bits(),
+        0x4000_0000
+    );
+    let x = P16::from_bits(0x4800).to_fp::<I32<32, 16>>().unwrap();
+    assert_eq!(x.into_f64(), 1.5);
+    assert_eq!(P16::MAX.to_fp::<Wide>().unwrap().raw(), 1 << (28 + 64));
+    assert_eq!(
+        P16::MIN_POSITIVE.to_fp::<Wide>().unwrap().raw(),
+        1 << (64 - 28)
+    );
+}
+
+#[test]
+fn round_trip() {
+    for bits in 0..1 << 16 {
+        let p = P16::from_bits(bits);
+        if p.is_nar() {
+            assert_eq!(p.to_fp::<Wide>(), Err(PositError::NaR));
+        } else {
+            assert_eq!(P16::from_fp(p.to_fp::<Wide>().unwrap()), p);
+        }
+    }
+    for bits in [
+        0x4000_0001,
+        0x3fff_ffff,
+        0x5234_5678,
+        0x2345_6789,
+        0xc000_0001,
+    ] {
+        let p = P32::from_bits(bits);
+        assert_eq!(P32::from_fp(p.to_fp::<Wide>().unwrap()), p);
+    }
+}
+
+#[test]
+fn nearest_even() {
+    // ties (at 1 + 1/64 and 1 + 3/64) go to the even bit pattern
+    let p8 = |raw| P8::from_fp(I32::<32, 6>::new(raw).unwrap()).to_bits();
+    assert_eq!([64, 65, 66, 67].map(p8), [0x40, 0x40, 0x41, 0x42]);
+    // every value of an I16 is rounded to the nearest P8
+    let value = |p: P8| p.to_fp::<I64<64, 32>>().unwrap().raw();
+    for raw in i16::MIN..=i16::MAX {
+        let x = (raw as i64) << 23;
+        let p = P8::from_fp(I16::<16, 9>::new(raw).unwrap());
+        for q in [p.to_bits().wrapping_sub(1), p.to_bits() + 1].map(P8::from_bits) {
+            if q.is_nar() || (q == P8::ZERO && raw != 0) {
+                continue;
+            }
+            let (dp, dq) = ((value(p) - x).abs(), (value(q) - x).abs());
+            assert!(
+                dp < dq || (dp == dq && p.to_bits().is_multiple_of(2)),
+                "{raw}"
+            );
+        }
+    }
+}
+
+#[test]
+fn saturation() {
+    let big = I64::<64, 0>::new(1 << 40).unwrap();
+    assert_eq!(P16::from_fp(big), P16::MAX);
+    assert_eq!(
+        P16::from_fp(I64::<64, 0>::new(-1 << 40).unwrap()).to_bits(),
+        P16::MAX.to_bits().wrapping_neg() & 0xffff
+    );
+    let tiny = I64::<64, 40>::new(1).unwrap();
+    assert_eq!(P16::from_fp(tiny), P16::MIN_POSITIVE);
+    assert_eq!(
+        P16::from_fp(I64::<64, 40>::new(-1).unwrap()).to_bits(),
+        0xffff
+    );
+    assert_eq!(P16::MAX.to_fp::<I32<32, 16>>(), Err(PositError::TooLarge));
+    let minus_one = P16::from_bits(0xc000);
+    assert_eq!(minus_one.to_fp::<U32<32, 16>>(), Err(PositError::TooSmall));
+    // ties away from zero
+    let half = P16::from_bits(0x3000);
+    assert_eq!(half.to_fp::<I32<32, 0>>().unwrap().raw(), 1);
+    assert_eq!(
+        P16::from_bits(0xd000).to_fp::<I32<32, 0>>().unwrap().raw(),
+        -1
+    );
+    assert_eq!(P16::MIN_POSITIVE.to_fp::<I32<32, 16>>().unwrap().raw(), 0);
+}
@@ -0,0 +1,38 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn mul_promotes() {
+    let a = Promote(I32::<20, 10>::from_f64(-300.5).unwrap());
+    let b = Promote(I32::<20, 12>::from_f64(100.25).unwrap());
+    let c: Promote<I64<40, 22>> = a * b;
+    assert_eq!(c.0.into_f64(), -300.5 * 100.25);
+    let small = Promote(I32::<10, 0>::new(-512).unwrap());
+    let d: Promote<I32<20, 0>> = small * small;
+    assert_eq!(d.0.raw(), 262144);
+}
+
+#[test]
+fn chain() {
+    let x = Promote(U8::<8, 0>::MAX);
+    let y: Promote<U16<16, 0>> = x * x;
+    let z: Promote<U32<24, 0>> = y * x;
+    let w: Promote<U64<48, 0>> = z * z;
+    assert_eq!(w.0.raw(), 255u64.pow(6));
+    let v: Promote<U128<96, 0>> = w * w;
+    assert_eq!(v.0.raw(), 255u128.pow(12));
+}
+
+#[test]
+fn add_promotes() {
+    let a = Promote(I16::<16, 4>::MIN);
+    let b: Promote<I32<17, 4>> = a + a;
+    assert_eq!(b.0.raw(), 2 * i16::MIN as i32);
+    let c = Promote(I16::<15, 4>::MAX);
+    let d: Promote<I16<16, 4>> = c + c;
+    assert_eq!(d.0.raw(), 2 * (i16::MAX / 2));
+    let f: Promote<I32<18, 4>> = b + Promote(I16::<16, 4>::MAX);
+    assert_eq!(f.0.raw(), 2 * i16::MIN as i32 + i16::MAX as i32);
+}
@@ -0,0 +1,89 @@
+use fp::*;
+
+#[test]
+fn to_ratio_exact() {
+    assert_eq!(to_ratio(I16::<12, 4>::new(-24).unwrap()), Some((-3, 2)));
+    assert_eq!(to_ratio(I16::<12, 4>::new(0).unwrap()), Some((0, 1)));
+    assert_eq!(to_ratio(U8::<8, -3>::new(5).unwrap()), Some((40, 1)));
+    assert_eq!(to_ratio(I64::<64, 64>::new(3).unwrap()), Some((3, 1 << 64)));
+    assert_eq!(to_ratio(I8::<8, 127>::new(1).unwrap()), None);
+    assert_eq!(
+        to_ratio(I8::<8, -126>::new(-2).unwrap()),
+        Some((i128::MIN, 1))
+    );
+    assert_eq!(to_ratio(I8::<8, -126>::new(2).unwrap()), None);
+}
+
+#[test]
+fn from_ratio_rounding() {
+    type Q = I32<32, 2>;
+    // 7/10 = 2.8 quarters
+    assert_eq!(from_ratio::<Q, Floor>(7, 10).unwrap().raw(), 2);
+    assert_eq!(from_ratio::<Q, Nearest>(7, 10).unwrap().raw(), 3);
+    assert_eq!(from_ratio::<Q, Floor>(-7, 10).unwrap().raw(), -3);
+    assert_eq!(from_ratio::<Q, Nearest>(7, -10).unwrap().raw(), -3);
+    // 5/8 = 2.5 quarters: ties away from zero
+    assert_eq!(from_ratio::<Q, Nearest>(5, 8).unwrap().raw(), 3);
+    assert_eq!(from_ratio::<Q, Nearest>(-5, 8).unwrap().raw(), -3);
+    assert_eq!(from_ratio::<Q, Floor>(-1, 4).unwrap().raw(), -1);
+    // ...or to even
+    assert_eq!(from_ratio::<Q, NearestEven>(5, 8).unwrap().raw(), 2);
+    assert_eq!(from_ratio::<Q, NearestEven>(-7, 8).unwrap().raw(), -4);
+    assert_eq!(from_ratio::<Q, NearestEven>(7, 10).unwrap().raw(), 3);
+    assert_eq!(from_ratio::<Q, TowardZero>(-7, 10).unwrap().raw(), -2);
+    assert_eq!(from_ratio::<Q, AwayFromZero>(7, 10).unwrap().raw(), 3);
+    assert_eq!(from_ratio::<Q, AwayFromZero>(-1, 2).unwrap().raw(), -2);
+    assert_eq!(from_ratio::<Q, AwayFromZero>(-1, 1000).unwrap().raw(), -1);
+    // negative shifts
+    type C = I32<32, -4>;
+    assert_eq!(from_ratio::<C, Floor>(47, 1).unwrap().raw(), 2);
+    assert_eq!(from_ratio::<C, Nearest>(47, 1).unwrap().raw(), 3);
+    assert_eq!(from_ratio::<C, Floor>(-33, 1).unwrap().raw(), -3);
+    // round trips through to_ratio
+    for raw in [-1000, -1, 0, 1, 12345] {
+        let x = I32::<20, 7>::new(raw).unwrap();
+        let (n, d) = to_ratio(x).unwrap();
+        assert_eq!(from_ratio::<I32<20, 7>, Floor>(n, d).unwrap(), x);
+    }
+    // a ratio which needs more than 128 bits of intermediate
+    let third = from_ratio::<I128<128, 126>, Nearest>(1, 3).unwrap();
+    assert_eq!(third.raw(), ((1u128 << 126) / 3) as i128);
+}
+
+#[test]
+fn from_ratio_range() {
+    type Q = I8<8, 4>;
+    assert!(from_ratio::<Q, Floor>(-8, 1).is_ok());
+    assert!(matches!(
+        from_ratio::<Q, Floor>(8, 1),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(matches!(
+        from_ratio::<Q, Floor>(-9, 1),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(matches!(
+        from_ratio::<U8<8, 0>, Nearest>(-1, 3),
+        Ok(x) if x.raw() == 0
+    ));
+    assert!(matches!(
+        from_ratio::<U8<8, 0>, Floor>(-1, 3),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(matches!(
+        from_ratio::<Q, Floor>(1, 0),
+        Err(RangeError::TooLarge)
+    ));
+    assert!(matches!(
+        from_ratio::<Q, Floor>(-1, 0),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(matches!(
+        from_ratio::<I128<128, 0>, Floor>(i128::MIN, 1),
+        Ok(x) if x.raw() == i128::MIN
+    ));
+    assert!(matches!(
+        from_ratio::<I64<64, 100>, Floor>(i128::MAX, 1),
+        Err(RangeError::TooLarge)
+    ));
+}
@@ -0,0 +1,64 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn mul_rational_const() {
+    // milliseconds to 1024-Hz ticks, and back
+    let ms = U16::<10, 0>::MAX;
+    let ticks: U16<10, 0> = ms.mul_rational_const::<1000, 1024>();
+    assert_eq!(ticks.raw(), 999);
+    let x = U16::<10, 0>::new(5).unwrap();
+    assert_eq!(x.mul_rational_const::<1000, 1024>().raw(), 5);
+    let back: U16<11, 0> = ticks.mul_rational_const::<1024, 1000>();
+    assert_eq!(back.raw(), 1023);
+    // signed values round to nearest, ties away from zero
+    let xs = [-6, -5, -1, 0, 5, 6].map(|x| I8::<8, 0>::new(x).unwrap());
+    let halves = xs.map(|x| x.mul_rational_const::<1, 4>().raw());
+    assert_eq!(halves, [-2, -1, 0, 0, 1, 2]);
+    // negative ratios, with the bound taken from MIN and MAX
+    let y: I8<7, 0> = I8::<8, 0>::MIN.mul_rational_const::<-1, 3>();
+    assert_eq!(y.raw(), 43);
+    let y: I8<7, 0> = I8::<8, 0>::MAX.mul_rational_const::<-1, 3>();
+    assert_eq!(y.raw(), -42);
+    // an unchanged width needs no extra bits
+    let z: I32<32, 8> = I32::<32, 8>::MIN.mul_rational_const::<3, 4>();
+    assert_eq!(z.raw(), i32::MIN / 4 * 3);
+    let w: U64<64, 0> = U64::<64, 0>::MAX.mul_rational_const::<{ u64::MAX }, { u64::MAX }>();
+    assert_eq!(w.raw(), u64::MAX);
+}
+
+#[test]
+fn mul_rational_bits() {
+    assert_eq!(I16::<16, 0>::mul_rational_bits(3, 4), 16);
+    // 32767 / 2 rounds up to 16384, which needs a 16th bit
+    assert_eq!(I16::<16, 0>::mul_rational_bits(1, 2), 16);
+    assert_eq!(I16::<16, 0>::mul_rational_bits(1, 3), 15);
+    assert_eq!(I16::<16, 0>::mul_rational_bits(-1, 1), 17);
+    assert_eq!(U8::<8, 0>::mul_rational_bits(0, 7), 0);
+    assert_eq!(U8::<8, 0>::mul_rational_bits(1, 255), 1);
+}
+
+#[test]
+fn unit_conversions() {
+    // encoder counts (4096 per turn) to degrees, with 8 fractional bits
+    let counts = I32::<16, 0>::new(-1000).unwrap();
+    let degrees: I32<21, 8> = scale_by_ratio::<_, _, 360, 4096>(counts);
+    assert_eq!(degrees.raw(), -22500); // -87.890625 exactly
+                                       // milliseconds (Q8) back to ticks at 1 kHz / 3, rounded once
+    let ms = U16::<16, 8>::new(5 << 8).unwrap();
+    let ticks: U16<16, 0> = scale_by_ratio::<_, _, 1, 3>(ms);
+    assert_eq!(ticks.raw(), 2);
+    // ties away from zero, at a negative ratio
+    let x = I8::<8, 0>::new(3).unwrap();
+    let y: I8<8, 0> = scale_by_ratio::<_, _, { -1 }, 2>(x);
+    assert_eq!(y.raw(), -2);
+    let y: I8<8, 0> = scale_by_ratio::<_, _, { -1 }, 2>(I8::<8, 0>::new(-3).unwrap());
+    assert_eq!(y.raw(), 2);
+    // the extremes of the input fit exactly
+    let z: I64<64, 0> = scale_by_ratio::<_, _, 1, 1>(I64::<64, 0>::MIN);
+    assert_eq!(z, I64::MIN);
+    let w: I128<128, 0> = scale_by_ratio::<_, _, { i64::MAX }, 1>(U64::<64, 0>::MAX);
+    assert_eq!(w.raw(), u64::MAX as i128 * i64::MAX as i128);
+}
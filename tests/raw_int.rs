@@ -0,0 +1,74 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use core::ops::{Shl, Shr};
+use fp::*;
+
+/// A raw type defined outside the crate (here, only a wrapper around `i64`).
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Wide(i64);
+
+impl Shl<u32> for Wide {
+    type Output = Self;
+    fn shl(self, n: u32) -> Self {
+        Wide(self.0 << n)
+    }
+}
+
+impl Shr<u32> for Wide {
+    type Output = Self;
+    fn shr(self, n: u32) -> Self {
+        Wide(self.0 >> n)
+    }
+}
+
+impl RawInt for Wide {
+    type Fp<const B: u32, const S: i32> = FpWide<B, S>;
+    const BITS: u32 = 64;
+    const MIN: Self = Wide(i64::MIN);
+    const MAX: Self = Wide(i64::MAX);
+    const SIGNED: bool = true;
+    fn from_f32_lossy(val: f32) -> Self {
+        Wide(val as i64)
+    }
+    fn from_f64_lossy(val: f64) -> Self {
+        Wide(val as i64)
+    }
+    fn into_f32_lossy(self) -> f32 {
+        self.0 as f32
+    }
+    fn into_f64_lossy(self) -> f64 {
+        self.0 as f64
+    }
+}
+
+impl<const BITS: u32> RawBits<BITS> for Wide {
+    const MIN: Self = Wide(if BITS == 0 {
+        0
+    } else {
+        i64::MIN >> (64 - BITS)
+    });
+    const MAX: Self = Wide(if BITS == 0 {
+        0
+    } else {
+        i64::MAX >> (64 - BITS)
+    });
+}
+
+fp_struct!(pub FpWide, Wide);
+
+#[test]
+fn external_raw_type() {
+    assert_eq!(FpWide::<20, 4>::MAX.raw(), Wide((1 << 19) - 1));
+    assert_eq!(FpWide::<20, 4>::MIN.raw(), Wide(-1 << 19));
+    assert!(FpWide::<20, 4>::new(Wide(1 << 19)).is_err());
+    let x = FpWide::<20, 4>::from_f64(-2.5).unwrap();
+    assert_eq!(x.raw(), Wide(-40));
+    assert_eq!(x.into_f64(), -2.5);
+    assert!(x.is_negative() && x.signum().raw() == -1);
+    let y: FpWide<18, 2> = x.raw_shr::<2>();
+    assert_eq!(y.raw(), Wide(-10));
+    let z: FpWide<64, 0> = Wide(7).into();
+    assert_eq!(Wide::from(z), Wide(7));
+    assert!(FpWide::<8, 0>::try_from(200.).is_err());
+}
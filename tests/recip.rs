@@ -0,0 +1,78 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+/// `2^e / d`, rounded to nearest with ties away from zero.
+fn exact(d: u128, e: u32) -> u128 {
+    ((1u128 << (e + 1)) / d).div_ceil(2)
+}
+
+#[test]
+fn recip() {
+    let x = U32::<16, 8>::from_f64(2.5).unwrap();
+    let y: U32<{ recip_bits(8, 16, false) }, 16> = x.recip::<16>().unwrap();
+    assert_eq!(y.raw(), exact(640, 24) as u32);
+    let z: I32<{ recip_bits(8, 16, true) }, 16> =
+        I32::<16, 8>::new(-640).unwrap().recip::<16>().unwrap();
+    assert_eq!(z.raw(), -(y.raw() as i32));
+    assert!(matches!(
+        U8::<8, 0>::new(0).unwrap().recip::<4>(),
+        Err(RangeError::TooLarge)
+    ));
+    // the extremes fit
+    assert_eq!(I8::<8, 3>::new(1).unwrap().recip::<3>().unwrap().raw(), 64);
+    assert_eq!(
+        I8::<8, 3>::new(-1).unwrap().recip::<2>().unwrap().raw(),
+        -32
+    );
+    assert_eq!(
+        U64::<64, 31>::new(1).unwrap().recip::<32>().unwrap().raw(),
+        1 << 63
+    );
+    assert_eq!(U64::<64, 0>::MAX.recip::<63>().unwrap().raw(), 1);
+}
+
+#[test]
+fn recip_exhaustive() {
+    for raw in 1..=u16::MAX {
+        let x = U16::<16, 4>::new(raw).unwrap();
+        assert_eq!(
+            x.recip::<11>().unwrap().raw() as u128,
+            exact(raw as u128, 15)
+        );
+        let x = U32::<16, 4>::new(raw as u32).unwrap();
+        assert_eq!(
+            x.recip::<20>().unwrap().raw() as u128,
+            exact(raw as u128, 24)
+        );
+    }
+    for raw in i16::MIN..=i16::MAX {
+        if raw != 0 {
+            let q = I16::<16, 0>::new(raw).unwrap().recip::<14>().unwrap().raw();
+            let magnitude = exact(raw.unsigned_abs() as u128, 14) as i16;
+            assert_eq!(q, if raw < 0 { -magnitude } else { magnitude });
+        }
+    }
+}
+
+#[test]
+fn recip_wide() {
+    let mut d = 0x9e37_79b9_7f4a_7c15u64;
+    for _ in 0..10000 {
+        d = d.rotate_left(7) ^ d.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        for shift in [0, 17, 40, 63] {
+            let x = U64::<64, 0>::new(d >> shift).unwrap();
+            if x.raw() != 0 {
+                let y = x.recip::<63>().unwrap().raw();
+                assert_eq!(y as u128, exact(x.raw() as u128, 63), "{}", x.raw());
+            }
+        }
+        let x = I64::<64, 20>::new(d as i64 >> 30).unwrap();
+        if x.raw() != 0 {
+            let y = x.recip::<40>().unwrap().raw();
+            let magnitude = exact(x.raw().unsigned_abs() as u128, 60) as i64;
+            assert_eq!(y, if x.raw() < 0 { -magnitude } else { magnitude });
+        }
+    }
+}
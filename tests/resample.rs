@@ -0,0 +1,35 @@
+use fp::*;
+
+#[test]
+fn output_rate() {
+    let mut r = Resampler::<I16<16, 15>, 44100, 48000>::new();
+    let mut count = 0;
+    for _ in 0..44100 {
+        r.process(I16::new(1000).unwrap(), |_| count += 1);
+    }
+    assert!(count == 48000);
+}
+
+#[test]
+fn ramp_is_reproduced() {
+    // Catmull-Rom interpolation is exact for linear input.
+    let mut r = Resampler::<I32<20, 8>, 1, 4>::new();
+    let mut out = Vec::new();
+    for i in 0..10 {
+        r.process(I32::new(i * 256).unwrap(), |y| out.push(y.raw()));
+    }
+    assert!(out.len() == 40);
+    // two samples of latency, and the first full history is available at
+    // the third input sample
+    assert!(out[12..].iter().zip(4..).all(|(&y, i)| y == i * 64));
+}
+
+#[test]
+fn output_saturates() {
+    let mut r = Resampler::<I8<8, 0>, 1, 3>::new();
+    let mut max = 0;
+    for x in [0, 127, 127, 0, 0] {
+        r.process(I8::new(x).unwrap(), |y| max = max.max(y.raw()));
+    }
+    assert!(max == 127);
+}
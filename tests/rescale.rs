@@ -0,0 +1,125 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn rescaled(raw: i32) -> [i32; 5] {
+    // from 4 fractional bits to 1
+    let x = I32::<12, 4>::new(raw).unwrap();
+    [
+        x.rescale::<1, Floor>().raw(),
+        x.rescale::<1, TowardZero>().raw(),
+        x.rescale::<1, AwayFromZero>().raw(),
+        x.rescale::<1, Nearest>().raw(),
+        x.rescale::<1, NearestEven>().raw(),
+    ]
+}
+
+#[test]
+fn rounding_modes() {
+    // in units of 1/2: 2.25, 2.5, 3.5, 2.75, 3
+    assert_eq!(rescaled(18), [2, 2, 3, 2, 2]);
+    assert_eq!(rescaled(20), [2, 2, 3, 3, 2]);
+    assert_eq!(rescaled(28), [3, 3, 4, 4, 4]);
+    assert_eq!(rescaled(22), [2, 2, 3, 3, 3]);
+    assert_eq!(rescaled(24), [3, 3, 3, 3, 3]);
+    assert_eq!(rescaled(-18), [-3, -2, -3, -2, -2]);
+    assert_eq!(rescaled(-20), [-3, -2, -3, -3, -2]);
+    assert_eq!(rescaled(-28), [-4, -3, -4, -4, -4]);
+}
+
+#[test]
+fn output_bits() {
+    let x = I32::<12, 4>::MAX;
+    // rounding up can carry into another bit
+    let y: I32<10, 1> = x.rescale::<1, NearestEven>();
+    assert_eq!(y.raw(), 256);
+    let y: I32<9, 1> = x.rescale::<1, Floor>();
+    assert_eq!(y.raw(), 255);
+    let y: I32<9, 1> = I32::<12, 4>::MIN.rescale::<1, TowardZero>();
+    assert_eq!(y.raw(), -256);
+    // a left shift needs more bits, and is exact
+    let y: I32<16, 8> = x.rescale::<8, AwayFromZero>();
+    assert_eq!(y.raw(), 2047 << 4);
+    // shifting out every bit leaves a sign bit (and a carry)
+    let y: I32<2, -12> = I32::<12, 4>::new(-1)
+        .unwrap()
+        .rescale::<-12, AwayFromZero>();
+    assert_eq!(y.raw(), -1);
+    let y: U32<0, -12> = U32::<12, 4>::MAX.rescale::<-12, Floor>();
+    assert_eq!(y.raw(), 0);
+    let y: U32<1, -12> = U32::<12, 4>::MAX.rescale::<-12, Nearest>();
+    assert_eq!(y.raw(), 0);
+    let y: U64<64, 0> = U64::<64, 0>::MAX.rescale::<0, Nearest>();
+    assert_eq!(y, U64::MAX);
+}
+
+#[test]
+fn ceil_mode() {
+    let x = I32::<12, 4>::new(18).unwrap();
+    assert_eq!(x.rescale::<1, Ceil>().raw(), 3);
+    assert_eq!(
+        I32::<12, 4>::new(-18).unwrap().rescale::<1, Ceil>().raw(),
+        -2
+    );
+    assert_eq!(I32::<12, 4>::new(16).unwrap().rescale::<1, Ceil>().raw(), 2);
+    // the carry is counted in the output bits
+    let y: U8<8, 0> = U8::<8, 1>::MAX.rescale::<0, Ceil>();
+    assert_eq!(y.raw(), 128);
+}
+
+#[test]
+fn integer_part() {
+    // Q16.16 positions to whole pixels
+    let position = I32::<32, 16>::from_f64(-2.5).unwrap();
+    let floor: I32<16, 0> = position.floor();
+    let ceil: I32<17, 0> = position.ceil();
+    let round: I32<17, 0> = position.round();
+    let trunc: I32<16, 0> = position.trunc();
+    assert_eq!(
+        [floor.raw(), ceil.raw(), round.raw(), trunc.raw()],
+        [-3, -2, -3, -2]
+    );
+    for raw in -64..64 {
+        let x = I16::<8, 3>::new(raw).unwrap();
+        let v = raw as f64 / 8.;
+        assert_eq!(x.floor().raw() as f64, v.floor());
+        assert_eq!(x.ceil().raw() as f64, v.ceil());
+        assert_eq!(x.round().raw() as f64, v.round());
+        assert_eq!(x.trunc().raw() as f64, v.trunc());
+    }
+    // the extremes fit
+    assert_eq!(U8::<8, 4>::MAX.ceil().raw(), 16);
+    assert_eq!(U8::<8, 4>::MAX.round().raw(), 16);
+    assert_eq!(I8::<8, 7>::MIN.floor().raw(), -1);
+    assert_eq!(I8::<8, 8>::new(-1).unwrap().ceil().raw(), 0);
+    // an integer type is unchanged, and a negative shift is multiplied out
+    assert_eq!(I16::<8, -2>::new(-3).unwrap().floor().raw(), -12);
+}
+
+#[test]
+fn round_lsb() {
+    let lsb = |raw: i32| {
+        let x = I32::<12, 4>::new(raw).unwrap();
+        let (a, b, c) = (
+            x.raw_shr::<2>(),
+            x.round_lsb::<2>(),
+            x.round_lsb_even::<2>(),
+        );
+        [a.raw(), b.raw(), c.raw()]
+    };
+    // in units of 1/4: 1.25, 1.5, 2.5, 1.75
+    assert_eq!(lsb(5), [1, 1, 1]);
+    assert_eq!(lsb(6), [1, 2, 2]);
+    assert_eq!(lsb(10), [2, 3, 2]);
+    assert_eq!(lsb(7), [1, 2, 2]);
+    assert_eq!(lsb(-6), [-2, -2, -2]);
+    assert_eq!(lsb(-10), [-3, -3, -2]);
+    assert_eq!(lsb(-7), [-2, -2, -2]);
+    // the same output type as raw_shr, saturating at its MAX
+    let max = I32::<12, 4>::MAX;
+    let _: I32<10, 2> = max.round_lsb::<2>();
+    assert_eq!(max.round_lsb::<2>(), I32::<10, 2>::MAX);
+    assert_eq!(U8::<8, 0>::MAX.round_lsb_even::<1>(), U8::<7, -1>::MAX);
+    assert_eq!(I8::<8, 0>::MIN.round_lsb::<3>().raw(), -16);
+}
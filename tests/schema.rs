@@ -0,0 +1,22 @@
+use fp::*;
+
+#[test]
+fn json_schema() {
+    assert_eq!(
+        I16::<12, 4>::json_schema(),
+        concat!(
+            r#"{"type":"number","minimum":-128,"maximum":127.9375,"multipleOf":0.0625,"#,
+            r#""description":"fixed-point I16<12, 4>"}"#
+        )
+    );
+    assert_eq!(
+        U8::<8, -2>::json_schema(),
+        concat!(
+            r#"{"type":"number","minimum":0,"maximum":1020,"multipleOf":4,"#,
+            r#""description":"fixed-point U8<8, -2>"}"#
+        )
+    );
+    // bounds are exact, even where f64 would round
+    assert!(I64::<64, 0>::json_schema()
+        .contains(r#""minimum":-9223372036854775808,"maximum":9223372036854775807,"#));
+}
@@ -0,0 +1,4 @@
+#[test]
+fn passes() {
+    assert_eq!(fp::selftest::run(), Ok(()));
+}
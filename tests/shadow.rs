@@ -0,0 +1,30 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn exact_ops_do_not_diverge() {
+    let a = Shadow::<I32<10, 4>>::from_f64(3.25).unwrap();
+    let b = Shadow::<I32<10, 4>>::from_f64(-1.5).unwrap();
+    let c = (a + b) * (a - (-b));
+    assert!(c.shadow() == (3.25 - 1.5) * (3.25 - 1.5));
+    assert!(c.worst_divergence() == 0.);
+}
+
+#[test]
+fn divergence_is_tracked() {
+    // 0.1 is not representable with 4 fractional bits
+    let a = Shadow::<I32<10, 4>>::from_f64(0.1).unwrap();
+    assert!(a.divergence() == 0.1 - 0.0625);
+    assert!(a.divergence_lsbs() > 0.59 && a.divergence_lsbs() < 0.61);
+    // truncation by raw_shr loses precision, and the loss persists in `worst`
+    let b = Shadow::<I32<10, 4>>::from_f64(1.9375).unwrap();
+    let c = b.map(|v| v.raw_shr::<4>(), |s| s);
+    assert!(c.divergence() == 0.9375);
+    let d = c + Shadow::new(I32::<6, 0>::new(1).unwrap());
+    assert!(d.divergence() == 0.9375);
+    assert!(d.worst_divergence() == 0.9375);
+    let e = Shadow::<I32<10, 4>>::new(I32::new(4).unwrap());
+    assert!((a * e).worst_divergence() == a.divergence());
+}
@@ -0,0 +1,42 @@
+use fp::*;
+
+/// Average level of `n` output codes, as a fraction of full scale.
+fn average<const OUT_BITS: u32>(x: I16<16, 15>, n: usize) -> f64 {
+    let mut sd = SigmaDelta::<I16<16, 15>, OUT_BITS>::new();
+    let steps = ((1 << OUT_BITS) - 1) as f64;
+    let mut sum = 0.;
+    for _ in 0..n {
+        let code = sd.process(x).raw() as f64;
+        sum += 2. * code / steps - 1.;
+        assert!(sd.error().abs() <= 32768. / steps);
+    }
+    sum / n as f64
+}
+
+#[test]
+fn one_bit() {
+    for x in [0., 0.25, -0.7, 0.999] {
+        let avg = average::<1>(I16::from_f64(x).unwrap(), 100_000);
+        assert!((avg - x).abs() < 1e-4, "{x} {avg}");
+    }
+    let mut sd = SigmaDelta::<I16<16, 15>, 1>::default();
+    // zero input toggles between the two levels
+    let codes: Vec<u8> = (0..6)
+        .map(|_| sd.process(I16::new(0).unwrap()).raw())
+        .collect();
+    assert_eq!(codes, [1, 0, 1, 0, 1, 0]);
+}
+
+#[test]
+fn few_bit() {
+    for x in [0., 1. / 3., -0.123] {
+        let avg = average::<3>(I16::from_f64(x).unwrap(), 10_000);
+        assert!((avg - x).abs() < 1e-4, "{x} {avg}");
+    }
+    // full-scale inputs saturate without winding up
+    let mut sd = SigmaDelta::<I16<16, 15>, 3>::new();
+    for _ in 0..1000 {
+        assert_eq!(sd.process(I16::MIN).raw(), 0);
+    }
+    assert_eq!(sd.process(I16::new(0).unwrap()).raw(), 4);
+}
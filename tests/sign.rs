@@ -0,0 +1,77 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn predicates() {
+    for (raw, sign) in [(-128, -1), (-1, -1), (0, 0), (1, 1), (127, 1)] {
+        let x = I8::<8, 4>::new(raw).unwrap();
+        assert_eq!(x.signum().raw(), sign);
+        assert_eq!(
+            (x.is_negative(), x.is_zero(), x.is_positive()),
+            (sign < 0, sign == 0, sign > 0)
+        );
+    }
+    assert!(U32::<32, 0>::MAX.is_positive());
+    assert!(!U32::<32, 0>::MIN.is_negative());
+    assert_eq!(U32::<32, 0>::MIN.signum().raw(), 0);
+    assert_eq!(I128::<128, 0>::MIN.signum().raw(), -1);
+    assert!(0u8.is_zero() && (-5i64).is_negative());
+    assert!(I16::<0, 0>::MAX.is_zero());
+}
+
+/// Find a root of `f` between `lo` and `hi`, where `f(lo)` and `f(hi)` have
+/// opposite signs, to within one LSB.
+fn bisect<F: Num, G: Num>(f: impl Fn(F) -> G, mut lo: F, mut hi: F) -> F
+where
+    F::Raw: Into<i128> + TryFrom<i128>,
+{
+    let sign = f(lo).signum();
+    loop {
+        let (l, h): (i128, i128) = (lo.raw().into(), hi.raw().into());
+        if h - l <= 1 {
+            return lo;
+        }
+        let mid = F::new((l + h).div_euclid(2).try_into().ok().unwrap()).unwrap();
+        if f(mid).signum() == sign {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+}
+
+#[test]
+fn bisection() {
+    // x^2 - 2, for 0 <= x < 4 with 12 fractional bits
+    let f = |x: U32<14, 12>| {
+        let two = U32::<28, 24>::new(2 << 24).unwrap();
+        x * x - two
+    };
+    let root = bisect(f, U32::new(0).unwrap(), U32::MAX);
+    assert_eq!(root.raw(), (2f64.sqrt() * 4096.) as u32);
+}
+
+// generic over any unsigned number, by way of the signed type
+fn negated<F: IntoSigned>(x: F) -> <F::Signed as core::ops::Neg>::Output
+where
+    F::Signed: core::ops::Neg,
+{
+    -x.into_signed()
+}
+
+#[test]
+fn generic_signedness() {
+    let x = U16::<12, 4>::new(40).unwrap();
+    let y: I16<13, 4> = x.into_signed();
+    assert_eq!(y.raw(), 40);
+    assert_eq!(negated(x).raw(), -40);
+    assert!(IntoUnsigned::into_unsigned(y) == Some(x));
+    assert!(IntoUnsigned::into_unsigned(I8::<4, 0>::new(-3).unwrap()).is_none());
+    assert_eq!(unsafe { IntoUnsigned::into_unsigned_unchecked(y) }, x);
+    assert_eq!(
+        IntoSigned::into_signed(U64::<63, 0>::MAX),
+        I64::<64, 0>::MAX
+    );
+}
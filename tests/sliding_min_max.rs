@@ -0,0 +1,25 @@
+use fp::*;
+
+#[test]
+fn matches_brute_force() {
+    let mut rng = Xorshift64::new(5);
+    let mut w = SlidingMinMax::<I32<10, 3>, 9>::new();
+    assert!(w.min().is_none());
+    let mut xs = Vec::new();
+    for i in 0..2000 {
+        let x = I32::<10, 3>::random(&mut rng);
+        xs.push(x);
+        let window = &xs[xs.len().saturating_sub(9)..];
+        let (min, max) = w.process(x);
+        assert!(min == *window.iter().min().unwrap());
+        assert!(max == *window.iter().max().unwrap());
+        assert!(w.max() == Some(max));
+        // long monotonic runs exercise eviction from the front
+        if i % 500 == 0 {
+            for j in 0..20 {
+                xs.push(I32::new(j).unwrap());
+                w.process(I32::new(j).unwrap());
+            }
+        }
+    }
+}
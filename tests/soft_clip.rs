@@ -0,0 +1,49 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn cubic(x: f64) -> f64 {
+    let x = x.clamp(-1., 1.);
+    1.5 * x - 0.5 * x * x * x
+}
+
+#[test]
+fn cubic_curve() {
+    let clip = SoftClip::<I32<32, 16>>::cubic();
+    for i in -40000..40000 {
+        let x = I32::<32, 16>::new(i * 5).unwrap();
+        let y: I32<18, 16> = clip.process(x);
+        assert!((y.into_f64() - cubic(x.into_f64())).abs() <= 0.5 / 65536. + 1e-9);
+    }
+    assert_eq!(clip.process(I32::MAX), I32::<18, 16>::from_f64(1.).unwrap());
+    assert_eq!(
+        clip.process(I32::MIN),
+        I32::<18, 16>::from_f64(-1.).unwrap()
+    );
+}
+
+#[test]
+fn tanh_curve() {
+    let clip = SoftClip::<I64<64, 40>>::tanh();
+    let mut worst: f64 = 0.;
+    for i in -12000..12000 {
+        let x = I64::<64, 40>::from_f64(i as f64 / 1000.).unwrap();
+        let y: I64<42, 40> = clip.process(x);
+        worst = worst.max((y.into_f64() - x.into_f64().tanh()).abs());
+    }
+    assert!(worst < 3e-5, "{worst}");
+}
+
+#[test]
+fn odd_and_narrow() {
+    let clip = SoftClip::<I16<16, 12>>::tanh();
+    for raw in i16::MIN..=i16::MAX {
+        let x = I16::<16, 12>::new(raw).unwrap();
+        let y: I16<14, 12> = clip.process(x);
+        if raw > i16::MIN {
+            assert_eq!(clip.process(I16::new(-raw).unwrap()).raw(), -y.raw());
+        }
+        assert!((y.into_f64() - x.into_f64().tanh()).abs() < 3e-5 + 0.5 / 4096.);
+    }
+}
@@ -0,0 +1,33 @@
+use fp::*;
+
+#[test]
+fn order_preserving() {
+    let mut rng = Xorshift64::new(5);
+    let mut xs: Vec<I16<12, 4>> = (0..1000).map(|_| I16::random(&mut rng)).collect();
+    xs.extend([I16::MIN, I16::MAX, I16::new(0).unwrap()]);
+    let mut keys: Vec<u16> = xs.iter().map(|x| x.to_sort_key()).collect();
+    xs.sort();
+    keys.sort();
+    let sorted: Vec<I16<12, 4>> = keys
+        .iter()
+        .map(|&k| I16::from_sort_key(k).unwrap())
+        .collect();
+    assert!(sorted == xs);
+}
+
+#[test]
+fn keys() {
+    assert_eq!(I8::<8, 0>::MIN.to_sort_key(), 0);
+    assert_eq!(I8::<8, 0>::new(-1).unwrap().to_sort_key(), 0x7f);
+    assert_eq!(I8::<8, 0>::new(0).unwrap().to_sort_key(), 0x80);
+    assert_eq!(I8::<8, 0>::MAX.to_sort_key(), 0xff);
+    assert_eq!(U32::<20, 3>::new(12345).unwrap().to_sort_key(), 12345);
+    assert!(matches!(
+        I8::<4, 0>::from_sort_key(0),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(matches!(
+        U8::<4, 0>::from_sort_key(16),
+        Err(RangeError::TooLarge)
+    ));
+}
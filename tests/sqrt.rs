@@ -0,0 +1,43 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn sqrt() {
+    let x = U16::<16, 8>::from_f64(2.25).unwrap();
+    let y: U16<8, 4> = x.sqrt();
+    assert_eq!(y.into_f64(), 1.5);
+    // an odd shift is made even first, so no resolution is lost
+    let x = U32::<20, 3>::from_f64(2.).unwrap();
+    let y: U32<11, 2> = x.sqrt();
+    assert_eq!(y.into_f64(), 1.25);
+    let x = U8::<8, -3>::new(2).unwrap();
+    let y: U8<5, -1> = x.sqrt();
+    assert_eq!(y.raw(), 2);
+}
+
+#[test]
+fn sqrt_exhaustive() {
+    for raw in 0..=u16::MAX {
+        let even = U16::<16, 0>::new(raw).unwrap().sqrt();
+        assert_eq!(even.raw() as u32, (raw as u32).isqrt());
+        let odd = U16::<16, 1>::new(raw).unwrap().sqrt();
+        assert_eq!(odd.raw() as u32, (2 * raw as u32).isqrt());
+    }
+}
+
+#[test]
+fn sqrt_extremes() {
+    assert_eq!(U8::<8, 0>::MAX.sqrt().raw(), 15);
+    assert_eq!(U8::<8, 1>::MAX.sqrt().raw(), 22);
+    assert_eq!(U64::<64, 0>::MAX.sqrt().raw(), u32::MAX as u64);
+    assert_eq!(U128::<128, 0>::MAX.sqrt().raw(), u64::MAX as u128);
+    let root: U128<65, 1> = U128::<128, 1>::MAX.sqrt();
+    // floor(sqrt(2^129 - 2))
+    assert_eq!(root.raw(), 26087635650665564424);
+    for x in [1u128 << 100, (1 << 127) + 12345, u128::MAX - 1] {
+        let root = U128::<128, 0>::new(x).unwrap().sqrt().raw();
+        assert_eq!(root, x.isqrt());
+    }
+}
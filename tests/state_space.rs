@@ -0,0 +1,59 @@
+use fp::*;
+
+#[test]
+fn double_integrator() {
+    type T = I8<4, 0>;
+    let sys = StateSpace::<T, I32<32, 0>, I32<16, 0>, 2, 1, 1>::new(
+        Matrix::from_f64([[1., 1.], [0., 1.]]).unwrap(),
+        Matrix::from_f64([[0.], [1.]]).unwrap(),
+        Matrix::from_f64([[1., 0.]]).unwrap(),
+        Matrix::from_f64([[0.]]).unwrap(),
+    );
+    assert_eq!(
+        StateSpace::<T, I32<32, 0>, I32<16, 0>, 2, 1, 1>::ACC_BITS,
+        38
+    );
+    let mut sys = sys;
+    for n in 0..200 {
+        let [y] = sys.update([I32::new(1).unwrap()]);
+        // the output saturates once it exceeds the range of I32<16, 0>
+        assert_eq!(y.raw(), (n * (n - 1) / 2).min(32767), "{n}");
+    }
+    assert_eq!(sys.state()[1].raw(), 200);
+    sys.reset();
+    assert_eq!(sys.state().map(|x| x.raw()), [0, 0]);
+}
+
+#[test]
+fn lowpass() {
+    // x[n + 1] = 0.9 x[n] + 0.1 u[n], y[n] = x[n] + 0.5 u[n], with a wide state
+    type T = I32<18, 16>;
+    type X = I32<32, 24>;
+    type F = I16<16, 12>;
+    assert_eq!(StateSpace::<T, X, F, 1, 1, 1>::ACC_SHIFT, 40);
+    assert_eq!(StateSpace::<T, X, F, 1, 1, 1>::ACC_BITS, 18 + 32 + 1);
+    let mut sys = StateSpace::<T, X, F, 1, 1, 1>::new(
+        Matrix::from_f64([[0.9]]).unwrap(),
+        Matrix::from_f64([[0.1]]).unwrap(),
+        Matrix::from_f64([[1.]]).unwrap(),
+        Matrix::from_f64([[0.5]]).unwrap(),
+    );
+    let u = F::from_f64(2.).unwrap();
+    let ys: Vec<f64> = (0..200).map(|_| sys.update([u])[0].into_f64()).collect();
+    assert_eq!(ys[0], 1.);
+    let exact = |n: i32| 1. + 2. * (1. - 0.9f64.powi(n));
+    // coefficient quantization of 0.9 and 0.1 in Q16 dominates the error
+    for (n, y) in ys.iter().enumerate() {
+        assert!((y - exact(n as i32)).abs() < 1e-3, "{n}");
+    }
+    assert!((sys.state()[0].into_f64() - 2.).abs() < 1e-3);
+}
+
+#[test]
+fn matrix() {
+    let m = Matrix::<I16<8, 4>, 2, 3>::from_f64([[1., 2., 3.], [-1., 0.5, 0.]]).unwrap();
+    assert_eq!(m.get(1, 1).unwrap().raw(), 8);
+    assert_eq!(m.get(2, 0), None);
+    assert_eq!(m.transpose().rows()[2][0], m.rows()[0][2]);
+    assert!(Matrix::<I16<8, 4>, 1, 1>::from_f64([[8.]]).is_err());
+}
@@ -0,0 +1,84 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn clog2_values() {
+    assert!(clog2(0) == 0);
+    assert!(clog2(1) == 0);
+    assert!(clog2(2) == 1);
+    assert!(clog2(3) == 2);
+    assert!(clog2(1024) == 10);
+    assert!(clog2(1025) == 11);
+}
+
+#[test]
+fn tree_sum_limits() {
+    let xs = [I16::<10, 4>::MIN; 20];
+    let s: I16<15, 4> = tree_sum(&xs);
+    assert!(s.raw() == 20 * -512);
+    let xs = [U32::<8, 0>::MAX; 3];
+    let s: U32<10, 0> = tree_sum(&xs);
+    assert!(s.raw() == 3 * 255);
+}
+
+#[test]
+fn tree_sum_slice_checks_length() {
+    let xs: Vec<_> = (0..1000).map(|i| I32::<11, 0>::new(i).unwrap()).collect();
+    let s: I32<21, 0> = tree_sum_slice(&xs).unwrap();
+    assert!(s.raw() == 999 * 1000 / 2);
+    assert!(tree_sum_slice::<_, I32<20, 0>>(&xs).is_err());
+}
+
+#[test]
+fn sum_into_accumulator() {
+    let xs = [I32::<20, 8>::MAX; 1000];
+    let s: I64<40, 8> = sum_into(xs).unwrap();
+    assert_eq!(s.raw(), 1000 * ((1 << 19) - 1));
+    let s = xs.iter().copied().sum::<Result<I64<40, 8>, _>>().unwrap();
+    assert_eq!(s.raw(), 1000 * ((1 << 19) - 1));
+    assert!(matches!(
+        xs.iter().copied().sum::<Result<I32<28, 8>, _>>(),
+        Err(RangeError::TooLarge)
+    ));
+    // only the final sum is checked
+    let ys = [I8::<8, 0>::MAX, I8::MAX, I8::MIN, I8::MIN];
+    let s: I8<8, 0> = sum_into(ys).unwrap();
+    assert_eq!(s.raw(), -2);
+    assert!(matches!(
+        sum_into::<I8<8, 0>, _>(ys.into_iter().skip(2)),
+        Err(RangeError::TooSmall)
+    ));
+    let zs = (0..100u16).map(|i| U16::<16, 0>::new(i * 600).unwrap());
+    let s: Result<U128<128, 0>, _> = zs.sum();
+    assert_eq!(s.unwrap().raw(), 600 * 99 * 100 / 2);
+    let empty: [I16<4, 0>; 0] = [];
+    assert_eq!(sum_into::<I16<0, 0>, _>(empty).unwrap().raw(), 0);
+}
+
+#[test]
+fn dot_product() {
+    let taps = [
+        I16::<8, 7>::from_f64(0.25).unwrap(),
+        I16::<8, 7>::from_f64(0.5).unwrap(),
+        I16::<8, 7>::from_f64(0.25).unwrap(),
+    ];
+    let xs = [
+        I16::<6, 2>::new(4).unwrap(),
+        I16::new(8).unwrap(),
+        I16::new(12).unwrap(),
+    ];
+    let y: I16<16, 9> = dot(&taps, &xs);
+    assert_eq!(y.into_f64(), 2.);
+    // the extremes fit
+    let a = [I32::<12, 0>::MIN; 5];
+    let s: I32<27, 0> = dot(&a, &a);
+    assert_eq!(s.raw(), 5 << 22);
+    let b = [U64::<20, 4>::MAX; 1000];
+    let c = [U64::<30, -2>::MAX; 1000];
+    let s: U64<60, 2> = dot(&b, &c);
+    assert_eq!(s.raw(), 1000 * ((1 << 20) - 1) * ((1 << 30) - 1));
+    let s: I8<8, 0> = dot::<I8<4, 0>, I8<4, 0>, 0>(&[], &[]);
+    assert_eq!(s.raw(), 0);
+}
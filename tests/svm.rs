@@ -0,0 +1,73 @@
+use fp::*;
+
+fn command(magnitude: f64, degrees: f64) -> (I32<20, 16>, I32<20, 16>) {
+    let theta = degrees.to_radians();
+    (
+        I32::from_f64(magnitude * theta.cos()).unwrap(),
+        I32::from_f64(magnitude * theta.sin()).unwrap(),
+    )
+}
+
+#[test]
+fn zero_vector() {
+    let out = svm(I32::<20, 16>::new(0).unwrap(), I32::new(0).unwrap());
+    assert_eq!(out.duties.map(|d| d.raw()), [32768; 3]);
+}
+
+#[test]
+fn extreme_formats() {
+    // the most bits and fractional bits together: BITS + SHIFT = 96
+    let zero = I64::<64, 32>::new(0).unwrap();
+    let out = svm(zero, zero);
+    assert_eq!(out.duties.map(|d| d.raw()), [32768; 3]);
+    let (alpha, beta) = command(0.25, 0.);
+    let wide = svm(
+        I64::<64, 32>::from_f64(alpha.into_f64()).unwrap(),
+        I64::<64, 32>::from_f64(beta.into_f64()).unwrap(),
+    );
+    assert_eq!(wide, svm(alpha, beta));
+}
+
+#[test]
+fn sectors() {
+    for k in 0..6 {
+        let (alpha, beta) = command(0.5, 30. + 60. * k as f64);
+        assert_eq!(svm(alpha, beta).sector, k + 1);
+        let (alpha, beta) = command(0.5, 1. + 60. * k as f64);
+        assert_eq!(svm(alpha, beta).sector, k + 1);
+        let (alpha, beta) = command(0.5, 59. + 60. * k as f64);
+        assert_eq!(svm(alpha, beta).sector, k + 1);
+    }
+}
+
+#[test]
+fn linear_range() {
+    let m = 1. / 3f64.sqrt();
+    for degrees in (0..360).step_by(7) {
+        let (alpha, beta) = command(m * 0.999, degrees as f64);
+        let d = svm(alpha, beta).duties.map(|d| d.into_f64());
+        // line-to-line voltages match the inverse Clarke transform
+        let (a, b) = (alpha.into_f64(), beta.into_f64());
+        let v = [
+            a,
+            -a / 2. + b * 3f64.sqrt() / 2.,
+            -a / 2. - b * 3f64.sqrt() / 2.,
+        ];
+        for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+            assert!(((d[i] - d[j]) - (v[i] - v[j])).abs() < 1e-4);
+        }
+        // the zero vectors are split evenly
+        let max = d.iter().cloned().fold(0., f64::max);
+        let min = d.iter().cloned().fold(1., f64::min);
+        assert!((max + min - 1.).abs() < 1e-4);
+        assert!(min > 0. && max < 1.);
+    }
+}
+
+#[test]
+fn overmodulation_clamps() {
+    let (alpha, beta) = command(2., 10.);
+    let d = svm(alpha, beta).duties;
+    assert_eq!(d[0].into_f64(), 1.);
+    assert_eq!(d[2].into_f64(), 0.);
+}
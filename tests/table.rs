@@ -0,0 +1,68 @@
+use fp::const_math::{exp, ln, sin};
+use fp::*;
+
+const GAMMA: FpTable<U8<8, 8>, U16<16, 15>, 257> =
+    fp_table!(|x: f64| fp::const_math::powf(x, 2.2), 257, U8<8, 8>, U16<16, 15>);
+
+// Steinhart-Hart equation for a 10k NTC thermistor in a divider with a 10k
+// resistor: ADC reading (as a fraction of full scale) to temperature in Celsius.
+const THERMISTOR: FpTable<U16<12, 12>, I32<32, 16>, 65> = fp_table!(
+    |x: f64| {
+        let x = if x < 1. / 64. { 1. / 64. } else { x };
+        let r = 10000. * (1. - x) / x;
+        let ln_r = ln(if r < 1. { 1. } else { r });
+        1. / (1.009249e-3 + 2.378405e-4 * ln_r + 2.019202e-7 * ln_r * ln_r * ln_r) - 273.15
+    },
+    65,
+    U16<12, 12>,
+    I32<32, 16>
+);
+
+#[test]
+fn gamma() {
+    assert_eq!(GAMMA.get(0).unwrap().raw(), 0);
+    assert_eq!(GAMMA.get(256).unwrap().raw(), 32768);
+    assert_eq!(GAMMA.get(257), None);
+    // built at compile time even outside const position
+    let gamma = fp_table!(|x: f64| fp::const_math::powf(x, 2.2), 257, U8<8, 8>, U16<16, 15>);
+    assert_eq!(gamma.get(128), GAMMA.get(128));
+    for code in 0..=255u8 {
+        let x = U8::<8, 8>::new(code).unwrap();
+        let exact = (code as f64 / 256.).powf(2.2);
+        assert!((GAMMA.lookup(x).into_f64() - exact).abs() <= 0.5 / 32768.);
+        assert_eq!(GAMMA.interpolate(x), GAMMA.lookup(x));
+    }
+}
+
+#[test]
+fn thermistor() {
+    let mut previous = f64::NEG_INFINITY;
+    for raw in 64..4096 {
+        let x = U16::<12, 12>::new(raw).unwrap();
+        let t = THERMISTOR.interpolate(x).into_f64();
+        assert!(t > previous);
+        previous = t;
+    }
+    // the divider reads one half at the thermistor's nominal resistance
+    let half = U16::<12, 12>::from_f64(0.5).unwrap();
+    let ln_r = 10000f64.ln();
+    let t = 1. / (1.009249e-3 + 2.378405e-4 * ln_r + 2.019202e-7 * ln_r.powi(3)) - 273.15;
+    assert!((THERMISTOR.lookup(half).into_f64() - t).abs() < 1e-4);
+}
+
+#[test]
+fn signed_input() {
+    const SINE: FpTable<I16<16, 13>, I32<32, 30>, 1025> = fp_table!(
+        |x: f64| sin(x) * exp(0.),
+        1025,
+        I16<16, 13>,
+        I32<32, 30>
+    );
+    let mut worst: f64 = 0.;
+    for raw in i16::MIN..=i16::MAX {
+        let x = I16::<16, 13>::new(raw).unwrap();
+        worst = worst.max((SINE.interpolate(x).into_f64() - x.into_f64().sin()).abs());
+    }
+    assert!(worst < 8e-6, "{worst}");
+    assert_eq!(SINE.raw().len(), 1025);
+}
@@ -0,0 +1,108 @@
+use fp::telemetry::*;
+use fp::*;
+
+#[test]
+fn round_trip() {
+    let mut buf = [0; 4 * MAX_RECORD_LEN];
+    let mut len = 0;
+    len += Record::new(I16::<12, 8>::from_f64(-3.5).unwrap())
+        .encode(&mut buf[len..])
+        .unwrap();
+    len += Record::new(U32::<32, -4>::MAX)
+        .encode(&mut buf[len..])
+        .unwrap();
+    len += Record::new(I128::<128, 100>::MIN)
+        .encode(&mut buf[len..])
+        .unwrap();
+    len += Record::new(U8::<0, 0>::new(0).unwrap())
+        .encode(&mut buf[len..])
+        .unwrap();
+    assert_eq!(len, 4 + 6 + 19 + 2);
+
+    let mut values = vec![];
+    let mut pos = 0;
+    while pos < len {
+        let (record, n) = Record::decode(&buf[pos..len]).unwrap();
+        values.push(record);
+        pos += n;
+    }
+    assert_eq!(values[0].into_f64(), -3.5);
+    assert_eq!(
+        values[0].format,
+        Format {
+            signed: true,
+            bits: 12,
+            shift: 8
+        }
+    );
+    assert_eq!(values[0].into_fp::<I16<12, 8>>(), I16::from_f64(-3.5).ok());
+    assert_eq!(values[0].into_fp::<I16<12, 7>>(), None);
+    assert_eq!(values[1].into_f64(), u32::MAX as f64 * 16.);
+    assert_eq!(values[2].raw, i128::MIN);
+    assert_eq!(values[3].format.bits, 0);
+}
+
+#[test]
+fn layout() {
+    let mut buf = [0; MAX_RECORD_LEN];
+    let n = Record::new(I16::<12, -1>::new(-1).unwrap())
+        .encode(&mut buf)
+        .unwrap();
+    // tag = zigzag(-1) << 1 | 1 = 3; BITS = 12; value = 0xfff
+    assert_eq!(buf[..n], [3, 12, 0xff, 0x0f]);
+    let n = Record::new(U16::<16, 100>::new(0x1234).unwrap())
+        .encode(&mut buf)
+        .unwrap();
+    // tag = 200 << 1 = 400 = 0b11_0010000
+    assert_eq!(buf[..n], [0x90, 0x03, 16, 0x34, 0x12]);
+}
+
+#[test]
+fn errors() {
+    let mut buf = [0; MAX_RECORD_LEN];
+    let n = Record::new(I64::<40, 20>::MAX).encode(&mut buf).unwrap();
+    for i in 0..n {
+        assert_eq!(Record::decode(&buf[..i]), Err(DecodeError::Truncated));
+    }
+    assert_eq!(
+        Record::new(I64::<40, 20>::MAX).encode(&mut buf[..n - 1]),
+        None
+    );
+    // stray bits beyond BITS
+    assert_eq!(
+        Record::decode(&[3, 12, 0xff, 0x1f]),
+        Err(DecodeError::Invalid)
+    );
+    // too many bits
+    assert_eq!(Record::decode(&[0, 129]), Err(DecodeError::Invalid));
+    for (signed, bits) in [(true, 129), (false, 128), (true, u32::MAX)] {
+        let record = Record {
+            format: Format {
+                signed,
+                bits,
+                shift: 0,
+            },
+            raw: -1,
+        };
+        assert_eq!(record.encode(&mut buf), None);
+    }
+    assert_eq!(Record::decode(&[0x80; 6]), Err(DecodeError::Invalid));
+}
+
+#[test]
+fn extreme_shifts() {
+    // a tag of zigzag(i32::MIN) << 1 | 1 = 2^33 - 1, then an 8-bit raw value of 1
+    let (record, n) = Record::decode(&[0xff, 0xff, 0xff, 0xff, 0x1f, 8, 1]).unwrap();
+    assert_eq!(n, 7);
+    assert_eq!(record.format.shift, i32::MIN);
+    assert_eq!(record.into_f64(), f64::INFINITY);
+    let record = Record {
+        format: Format {
+            signed: true,
+            bits: 8,
+            shift: i32::MAX,
+        },
+        raw: 1,
+    };
+    assert_eq!(record.into_f64(), 0.);
+}
@@ -0,0 +1,101 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+fn turns(t: f64) -> U32<32, 32> {
+    U32::new((t.rem_euclid(1.) * 2f64.powi(32)) as u32).unwrap()
+}
+
+#[test]
+fn quadrants() {
+    let one = 1 << 30;
+    for (t, cos, sin) in [
+        (0., one, 0),
+        (0.25, 0, one),
+        (0.5, -one, 0),
+        (0.75, 0, -one),
+    ] {
+        let (c, s) = trig::cos_sin::<30>(turns(t));
+        assert_eq!((c.raw(), s.raw()), (cos, sin), "at {t} turns");
+    }
+    assert_eq!(trig::cos::<0>(turns(0.5)).raw(), -1);
+    assert_eq!(trig::sin::<8>(turns(1. / 12.)).raw(), 128);
+}
+
+#[test]
+fn accuracy() {
+    for i in 0..10007u64 {
+        let phase = U32::<32, 32>::new((i * 429_183_853 % (1 << 32)) as u32).unwrap();
+        let radians = phase.into_f64() * core::f64::consts::TAU;
+        let (c, s) = trig::cos_sin::<30>(phase);
+        assert!((c.into_f64() - radians.cos()).abs() <= 2f64.powi(-30));
+        assert!((s.into_f64() - radians.sin()).abs() <= 2f64.powi(-30));
+        let s16 = trig::sin::<16>(phase);
+        assert!((s16.into_f64() - radians.sin()).abs() <= 2f64.powi(-16));
+    }
+}
+
+#[test]
+fn matches_lut() {
+    for i in 0..1000u32 {
+        let phase = U32::<32, 32>::new(i.wrapping_mul(4_294_967)).unwrap();
+        let lut = cos_lut(phase).into_f64();
+        let cordic = trig::cos::<30>(phase).into_f64();
+        assert!((lut - cordic).abs() < 5e-6);
+    }
+}
+
+#[test]
+fn atan2() {
+    let v = |x: i32, y: i32| I32::<32, 8>::new(y).unwrap().atan2(I32::new(x).unwrap());
+    assert_eq!(v(0, 0).raw(), 0);
+    assert_eq!(v(5, 0).raw(), 0);
+    assert_eq!(v(0, 5).raw(), 1 << 30);
+    assert_eq!(v(0, -5).raw(), -(1 << 30));
+    assert_eq!(v(-5, 0).raw(), i32::MIN);
+    assert_eq!(v(3, 3).raw(), 1 << 29);
+    assert_eq!(v(-7, -7).raw(), -3 << 29);
+    for i in 0..10007i64 {
+        let (x, y) = ((i * 7919 % 20001) - 10000, (i * 104729 % 20001) - 10000);
+        let angle = v(x as i32, y as i32).into_f64();
+        let expected = (y as f64).atan2(x as f64) / core::f64::consts::TAU;
+        let error = (angle - expected).abs();
+        // the negative x axis may round either way
+        assert!(error.min(1. - error) <= 2f64.powi(-32), "({x}, {y})");
+    }
+}
+
+#[test]
+fn atan2_inverts_cos_sin() {
+    for i in 0..1000u32 {
+        let phase = U32::<32, 32>::new(i.wrapping_mul(4_294_967)).unwrap();
+        let (c, s) = trig::cos_sin::<30>(phase);
+        let angle: U32<32, 32> = s.atan2(c).wrapping_cast();
+        assert!((angle.raw().wrapping_sub(phase.raw()) as i32).abs() <= 8);
+    }
+}
+
+#[test]
+fn hypot() {
+    let h = |x: i16, y: i16| I16::<16, 4>::new(x).unwrap().hypot(I16::new(y).unwrap());
+    let m: U16<16, 4> = h(3, 4);
+    assert_eq!(m.raw(), 5);
+    assert_eq!(h(0, 0).raw(), 0);
+    assert_eq!(h(-12, 0).raw(), 12);
+    assert_eq!(h(i16::MIN, i16::MIN).raw(), 46341);
+    for x in (-32768i64..32768).step_by(97) {
+        for y in (-32768..32768).step_by(89) {
+            let exact = ((x * x + y * y) as f64).sqrt();
+            assert!((h(x as i16, y as i16).raw() as f64 - exact).abs() <= 0.5 + 1e-9);
+        }
+    }
+    let wide = I64::<64, 0>::MIN.hypot(I64::MIN);
+    // 2^63 * sqrt(2)
+    assert!(wide.raw().abs_diff(13043817825332782212) <= 2);
+    let (x, y) = (
+        I64::<64, 0>::new(3 << 40).unwrap(),
+        I64::<64, 0>::new(-4 << 40).unwrap(),
+    );
+    assert!(x.hypot(y).raw().abs_diff(5 << 40) <= 1);
+}
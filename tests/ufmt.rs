@@ -0,0 +1,78 @@
+#![cfg(feature = "ufmt")]
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::ufmt::*;
+use fp::*;
+
+/// A fixed-capacity writer, as on a target without allocation.
+struct Buf {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl uWrite for Buf {
+    type Error = BufferTooSmall;
+    fn write_str(&mut self, s: &str) -> Result<(), BufferTooSmall> {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(BufferTooSmall)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+fn display(x: &impl uDisplay) -> String {
+    let mut buf = Buf {
+        bytes: [0; 64],
+        len: 0,
+    };
+    x.fmt(&mut Formatter::new(&mut buf)).unwrap();
+    String::from_utf8(buf.bytes[..buf.len].to_vec()).unwrap()
+}
+
+fn debug(x: &impl uDebug) -> String {
+    let mut buf = Buf {
+        bytes: [0; 64],
+        len: 0,
+    };
+    x.fmt(&mut Formatter::new(&mut buf)).unwrap();
+    String::from_utf8(buf.bytes[..buf.len].to_vec()).unwrap()
+}
+
+#[test]
+fn display_is_exact_decimal() {
+    assert_eq!(display(&I16::<12, 3>::new(-99).unwrap()), "-12.375");
+    assert_eq!(display(&U8::<8, -4>::new(3).unwrap()), "48");
+    assert_eq!(display(&I128::<128, 0>::MIN), i128::MIN.to_string());
+    assert_eq!(
+        display(&U32::<32, 32>::MAX),
+        "0.99999999976716935634613037109375"
+    );
+}
+
+#[test]
+fn debug_matches_core_debug() {
+    let x = I16::<12, 3>::new(-99).unwrap();
+    assert_eq!(debug(&x), format!("{x:?}"));
+    assert_eq!(debug(&x), "I16(-99)");
+    assert_eq!(
+        debug(&U128::<128, 7>::MAX),
+        format!("{:?}", U128::<128, 7>::MAX)
+    );
+}
+
+#[test]
+fn writer_errors_propagate() {
+    let mut buf = Buf {
+        bytes: [0; 64],
+        len: 60,
+    };
+    let x = I32::<32, 0>::MIN;
+    assert_eq!(
+        uDisplay::fmt(&x, &mut Formatter::new(&mut buf)),
+        Err(BufferTooSmall)
+    );
+}
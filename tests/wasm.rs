@@ -0,0 +1,47 @@
+#![cfg(feature = "wasm")]
+
+use fp::*;
+
+#[test]
+fn round_trip() {
+    let x = I16::<12, 4>::new(-24).unwrap();
+    let js = JsFixed::from_fp(x).unwrap();
+    assert_eq!(js, JsFixed { raw: -24, shift: 4 });
+    assert_eq!(js.to_fp::<I16<12, 4>>().unwrap(), x);
+    assert_eq!(js.to_f64(), -1.5);
+    assert_eq!(js.to_decimal_string(), "-1.5");
+    let y = U64::<64, 32>::new(u64::MAX).unwrap();
+    assert!(JsFixed::from_fp(y).is_none());
+    let z = I64::<64, 40>::new(i64::MAX).unwrap();
+    let js = JsFixed::from_fp(z).unwrap();
+    assert_eq!(js.to_fp::<I64<64, 40>>().unwrap(), z);
+    assert_eq!(
+        js.to_decimal_string(),
+        "8388607.9999999999990905052982270717620849609375"
+    );
+}
+
+#[test]
+fn conversions() {
+    let js = JsFixed { raw: 3, shift: 2 }; // 0.75
+    assert_eq!(js.to_fp::<I8<8, 4>>().unwrap().raw(), 12);
+    assert_eq!(js.to_fp::<I8<8, 1>>().unwrap().raw(), 2); // ties away from zero
+    assert_eq!(js.to_fp::<I8<8, 0>>().unwrap().raw(), 1);
+    assert!(matches!(js.to_fp::<I8<8, 8>>(), Err(RangeError::TooLarge)));
+    let neg = JsFixed {
+        raw: -1,
+        shift: -70,
+    };
+    assert!(matches!(
+        neg.to_fp::<I64<64, 0>>(),
+        Err(RangeError::TooSmall)
+    ));
+    assert!(matches!(neg.to_fp::<U8<8, 0>>(), Err(RangeError::TooSmall)));
+    assert_eq!(
+        JsFixed { raw: 0, shift: -70 }
+            .to_fp::<U8<8, 0>>()
+            .unwrap()
+            .raw(),
+        0
+    );
+}
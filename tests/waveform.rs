@@ -0,0 +1,42 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use fp::*;
+
+#[test]
+fn sin_lut_accuracy() {
+    for i in 0..4096u32 {
+        let turns = U32::<32, 32>::new(i << 20).unwrap();
+        let exact = (turns.into_f64() * core::f64::consts::TAU).sin();
+        assert!((sin_lut(turns).into_f64() - exact).abs() < 5e-6);
+        let exact = (turns.into_f64() * core::f64::consts::TAU).cos();
+        assert!((cos_lut(turns).into_f64() - exact).abs() < 5e-6);
+    }
+    assert!(sin_lut(U32::new(1 << 30).unwrap()).raw() == 1 << 30);
+}
+
+#[test]
+fn shapes_are_exact() {
+    let square: Vec<I16<16, 15>> = Waveform::<_, 8, 1>::square().take(8).collect();
+    assert!(square
+        .iter()
+        .map(|x| x.raw())
+        .eq([32767, 32767, 32767, 32767, -32767, -32767, -32767, -32767]));
+    let tri: Vec<I16<3, 0>> = Waveform::<_, 8, 1>::triangle().take(8).collect();
+    assert!(tri.iter().map(|x| x.raw()).eq([0, 2, 3, 2, 0, -2, -3, -2]));
+    let saw: Vec<U8<8, 0>> = Waveform::<_, 4, 1>::sawtooth().take(4).collect();
+    assert!(saw.iter().map(|x| x.raw()).eq([128, 192, 1, 64]));
+    let sine: Vec<I32<8, 0>> = Waveform::<_, 4, 1>::sine().take(5).collect();
+    assert!(sine.iter().map(|x| x.raw()).eq([0, 127, 0, -127, 0]));
+}
+
+#[test]
+fn sine_frequency() {
+    // 1 kHz at 48 kHz: one full period every 48 samples
+    let mut osc = Waveform::<I32<24, 23>, 48000, 1000>::sine();
+    let first: Vec<_> = osc.by_ref().take(48).collect();
+    let period: Vec<_> = osc.take(48).collect();
+    for (a, b) in first.iter().zip(&period) {
+        assert!((a.raw() - b.raw()).abs() <= 1);
+    }
+}
@@ -0,0 +1,104 @@
+use fp::*;
+
+#[test]
+fn small_sample() {
+    let mut w = Welford::<I16<16, 4>>::new();
+    assert_eq!(w.mean(), None);
+    assert_eq!(w.variance::<U32<32, 8>>(), None);
+    for x in [2., 4., 4., 4., 5., 5., 7., 9.] {
+        w.push(I16::from_f64(x).unwrap());
+    }
+    assert_eq!(w.count(), 8);
+    assert_eq!(w.mean().unwrap().into_f64(), 5.);
+    // M2 = 32, in units of 2^-8
+    assert_eq!(w.m2_raw(), 32 << 8);
+    assert_eq!(w.variance::<U32<32, 8>>().unwrap().into_f64(), 4.);
+    assert_eq!(
+        w.sample_variance::<U32<32, 16>>().unwrap().raw(),
+        (32 << 16) / 7
+    );
+    assert_eq!(w.std_dev().unwrap().into_f64(), 2.);
+    w.reset();
+    assert_eq!(w.count(), 0);
+    w.push(I16::from_f64(1.).unwrap());
+    assert_eq!(w.sample_variance::<U32<32, 8>>(), None);
+    assert_eq!(w.variance::<U32<32, 8>>().unwrap().raw(), 0);
+}
+
+#[test]
+fn rounding_and_saturation() {
+    let mut w = Welford::<I8<8, 0>>::default();
+    for x in [-3, -2] {
+        w.push(I8::new(x).unwrap());
+    }
+    // -2.5 rounds away from zero
+    assert_eq!(w.mean().unwrap().raw(), -3);
+    let mut w = Welford::<I32<32, 0>>::new();
+    w.push(I32::MIN);
+    w.push(I32::MAX);
+    let v: U16<16, 0> = w.variance().unwrap();
+    assert_eq!(v, U16::MAX);
+    assert_eq!(w.std_dev().unwrap(), I32::MAX);
+}
+
+#[test]
+fn fractional_m2() {
+    // M2 = 0.5 is not an integer in units of the squared LSB
+    let mut w = Welford::<I16<16, 0>>::new();
+    for x in [0, 1] {
+        w.push(I16::new(x).unwrap());
+    }
+    assert_eq!(w.m2_raw(), 0);
+    // the sample variance 0.5 and the variance 0.25 round away from zero
+    assert_eq!(w.sample_variance::<I32<32, 0>>().unwrap().raw(), 1);
+    assert_eq!(w.variance::<I32<32, 2>>().unwrap().raw(), 1);
+    assert_eq!(w.variance::<I32<32, 1>>().unwrap().raw(), 1);
+    assert_eq!(w.variance::<I32<32, 0>>().unwrap().raw(), 0);
+    // the standard deviation 0.5 rounds up too
+    assert_eq!(w.std_dev().unwrap().raw(), 1);
+    // M2 = 2 / 3, so the variance is 2 / 9 and the sample variance 1 / 3
+    w.push(I16::new(1).unwrap());
+    assert_eq!(w.variance::<I32<32, 8>>().unwrap().raw(), 57); // 56.89
+    assert_eq!(w.sample_variance::<I32<32, 8>>().unwrap().raw(), 85); // 85.33
+    assert_eq!(w.std_dev().unwrap().raw(), 0); // 0.471
+}
+
+#[test]
+fn merge() {
+    let xs = [3, -7, 12, 5, 5, -20, 9].map(|x| I16::<16, 2>::new(x).unwrap());
+    let mut whole = Welford::new();
+    xs.iter().for_each(|&x| whole.push(x));
+    for split in 0..=xs.len() {
+        let (mut a, mut b) = (Welford::new(), Welford::new());
+        xs[..split].iter().for_each(|&x| a.push(x));
+        xs[split..].iter().for_each(|&x| b.push(x));
+        a.merge(&b);
+        assert_eq!(a.count(), whole.count());
+        assert_eq!(a.mean(), whole.mean());
+        assert_eq!(a.m2_raw(), whole.m2_raw());
+        assert_eq!(
+            a.sample_variance::<U32<32, 8>>(),
+            whole.sample_variance::<U32<32, 8>>()
+        );
+    }
+}
+
+#[test]
+fn no_drift() {
+    // a large offset with a small spread, over many samples
+    let mut w = Welford::<I32<32, 16>>::new();
+    let mut rng = Xorshift64::new(5);
+    let (mut sum, mut sum_squares) = (0i128, 0i128);
+    let n = 1_000_000;
+    for _ in 0..n {
+        let x = 30_000 << 16 | I32::<12, 0>::random(&mut rng).raw() & 0xfff;
+        w.push(I32::new(x).unwrap());
+        let d = (x - (30_000 << 16)) as i128;
+        sum += d;
+        sum_squares += d * d;
+    }
+    let m2 = sum_squares - sum * sum / n;
+    assert!((w.m2_raw() as i128 - m2).abs() <= 1);
+    let mean = (30_000i128 << 16) + (sum + n / 2) / n;
+    assert_eq!(w.mean().unwrap().raw() as i128, mean);
+}
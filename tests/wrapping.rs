@@ -0,0 +1,47 @@
+use fp::*;
+
+#[test]
+fn phase_accumulator() {
+    // a 24-bit phase, in turns
+    let step = Wrapping(U32::<24, 24>::new(0x50_0000).unwrap());
+    let mut phase = Wrapping(U32::<24, 24>::new(0xf0_0000).unwrap());
+    phase += step;
+    assert_eq!(phase.0.raw(), 0x40_0000);
+    phase = phase - step - step;
+    assert_eq!(phase.0.raw(), 0xa0_0000);
+    assert_eq!((-phase).0.raw(), 0x60_0000);
+    let x = Wrapping(I8::<4, 0>::MAX);
+    assert_eq!((x + Wrapping(I8::new(1).unwrap())).0, I8::MIN);
+    assert_eq!((-Wrapping(I8::<4, 0>::MIN)).0, I8::MIN);
+}
+
+#[test]
+fn mul() {
+    // every product of 6-bit numbers with 2 fractional bits, against a reference
+    for a in -32..32 {
+        for b in -32..32 {
+            let x = Wrapping(I16::<6, 2>::new(a).unwrap());
+            let y = Wrapping(I16::<6, 2>::new(b).unwrap());
+            let expected = (((a * b) >> 2) as i8) << 2 >> 2;
+            assert_eq!((x * y).0.raw(), expected as i16);
+        }
+    }
+    let z = Wrapping(U64::<64, 32>::MAX);
+    assert_eq!((z * z).0.raw(), 0xffff_fffe_0000_0000);
+    let w = Wrapping(I128::<128, -4>::new(-3).unwrap());
+    assert_eq!((w * w).0.raw(), 9 << 4);
+    let v = Wrapping(I128::<128, 0>::MIN);
+    assert_eq!((v * v).0.raw(), 0);
+    assert_eq!((v * Wrapping(I128::new(-1).unwrap())).0, I128::MIN);
+}
+
+#[test]
+fn assign() {
+    let mut phase = Wrapping(U16::<12, 12>::new(0xf00).unwrap());
+    phase += Wrapping(U16::new(0x200).unwrap());
+    assert_eq!(phase.0.raw(), 0x100);
+    phase -= Wrapping(U16::new(0x300).unwrap());
+    assert_eq!(phase.0.raw(), 0xe00);
+    phase *= Wrapping(U16::new(0x800).unwrap());
+    assert_eq!(phase.0.raw(), 0x700);
+}